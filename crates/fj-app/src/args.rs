@@ -17,6 +17,57 @@ pub struct Args {
     #[clap(short, long)]
     pub export: Option<PathBuf>,
 
+    /// Simplify the exported mesh to at most this many triangles
+    ///
+    /// Sharp edges are preserved. Only use this for exports that are meant for
+    /// visualization.
+    #[clap(long)]
+    pub max_triangles: Option<usize>,
+
+    /// Simplify the exported mesh, as long as the error stays below this value
+    ///
+    /// Sharp edges are preserved. Only use this for exports that are meant for
+    /// visualization.
+    #[clap(long)]
+    pub max_decimation_error: Option<f64>,
+
+    /// Export PLY files as ASCII, instead of binary
+    #[clap(long)]
+    pub ply_ascii: bool,
+
+    /// Write colors per vertex to PLY files, instead of per face
+    #[clap(long)]
+    pub ply_vertex_colors: bool,
+
+    /// Export STL files as ASCII, instead of binary
+    #[clap(long)]
+    pub stl_ascii: bool,
+
+    /// Write triangle colors to binary STL files
+    ///
+    /// Uses the color encoding of VisCAM and SolidView.
+    #[clap(long)]
+    pub stl_colors: bool,
+
+    /// Print the mass properties of the model, instead of displaying it
+    #[clap(long)]
+    pub mass_properties: bool,
+
+    /// Measure how far the mesh deviates from the model
+    ///
+    /// Prints the result, if the model is exported or its mass properties are
+    /// printed. Otherwise, the points with the largest deviation are shown as
+    /// debug info in the model viewer.
+    #[clap(long)]
+    pub analyze_deviation: bool,
+
+    /// Check whether the mesh is a closed manifold without self-intersections
+    ///
+    /// Prints the result, instead of displaying the model. The analysis can
+    /// also be enabled from within the model viewer.
+    #[clap(long)]
+    pub analyze_mesh: bool,
+
     /// Parameters for the model, each in the form `key=value`
     #[clap(short, long, parse(try_from_str = parse_parameters))]
     pub parameters: Option<Parameters>,
@@ -24,6 +75,28 @@ pub struct Args {
     /// Model deviation tolerance
     #[clap[short, long, parse(try_from_str = parse_tolerance)]]
     pub tolerance: Option<Tolerance>,
+
+    /// Maximum angle between neighboring segments of curves, in degrees
+    #[clap(long)]
+    pub angular_deviation: Option<f64>,
+
+    /// Maximum length of the segments of approximated edges
+    #[clap(long)]
+    pub max_edge_length: Option<f64>,
+
+    /// Minimum number of segments that circles are approximated with
+    #[clap(long)]
+    pub min_circle_segments: Option<u64>,
+
+    /// Refine the mesh until no triangle edge is longer than this
+    #[clap(long, parse(try_from_str = parse_positive))]
+    pub max_triangle_edge_length: Option<f64>,
+
+    /// Refine the mesh until no triangle angle is below this, in degrees
+    ///
+    /// Values above 30 degrees might prevent the refinement from finishing.
+    #[clap(long, parse(try_from_str = parse_positive))]
+    pub min_triangle_angle: Option<f64>,
 }
 
 impl Args {
@@ -66,3 +139,13 @@ fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
 
     Ok(tolerance)
 }
+
+fn parse_positive(input: &str) -> anyhow::Result<f64> {
+    let value = f64::from_str(input)?;
+
+    if value <= 0. {
+        return Err(anyhow!("Value must be above zero"));
+    }
+
+    Ok(value)
+}
@@ -11,6 +11,9 @@ use serde::Deserialize;
 pub struct Config {
     pub default_path: Option<PathBuf>,
     pub default_model: Option<PathBuf>,
+    pub angular_deviation: Option<f64>,
+    pub max_edge_length: Option<f64>,
+    pub min_circle_segments: Option<u64>,
 }
 
 impl Config {
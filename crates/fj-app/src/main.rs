@@ -18,9 +18,17 @@ mod config;
 use std::path::PathBuf;
 
 use anyhow::{anyhow, Context as _};
-use fj_export::export;
+use fj_export::{
+    export_brep, export_with_options, is_brep_format, ExportOptions, Metadata,
+    PlyColors, PlyOptions, StlOptions,
+};
 use fj_host::{Model, Parameters};
-use fj_interop::status_report::StatusReport;
+use fj_interop::{
+    analysis::MeshAnalysis, decimation::Decimation, deviation::MeshDeviation,
+    mass_properties::MassProperties, status_report::StatusReport,
+};
+use fj_kernel::algorithms::Refinement;
+use fj_math::Scalar;
 use fj_operations::shape_processor::ShapeProcessor;
 use fj_window::run::run;
 use tracing_subscriber::fmt::format;
@@ -59,15 +67,91 @@ fn main() -> anyhow::Result<()> {
         .with_context(|| format!("Failed to load model: {}", path.display()))?;
     let parameters = args.parameters.unwrap_or_else(Parameters::empty);
 
+    // Arguments take precedence over the configuration file.
     let shape_processor = ShapeProcessor {
         tolerance: args.tolerance,
+        angular_deviation: args
+            .angular_deviation
+            .or(config.angular_deviation)
+            .map(|degrees| Scalar::from_f64(degrees.to_radians())),
+        max_edge_length: args
+            .max_edge_length
+            .or(config.max_edge_length)
+            .map(Scalar::from_f64),
+        min_circle_segments: args
+            .min_circle_segments
+            .or(config.min_circle_segments),
+        refinement: Refinement {
+            max_edge_length: args
+                .max_triangle_edge_length
+                .map(Scalar::from_f64),
+            min_angle: args
+                .min_triangle_angle
+                .map(|degrees| Scalar::from_f64(degrees.to_radians())),
+        },
+        analyze_deviation: args.analyze_deviation,
+        analyze_mesh: args.analyze_mesh,
     };
 
-    if let Some(path) = args.export {
-        let shape = model.load_once(&parameters, &mut status)?;
-        let shape = shape_processor.process(&shape)?;
+    if args.export.is_some() || args.mass_properties || args.analyze_mesh {
+        let (shape, metadata) =
+            model.load_once_with_metadata(&parameters, &mut status)?;
 
-        export(&shape.mesh, &path)?;
+        // Only formats that contain the exact geometry need the shells of the
+        // model. Sewing those is expensive, and might fail for models that
+        // can still be exported as a mesh.
+        let needs_brep = args.export.as_deref().map_or(false, is_brep_format);
+        let (shape, shells) = if needs_brep {
+            let (shape, shells) = shape_processor.process_with_brep(&shape)?;
+            (shape, Some(shells))
+        } else {
+            (shape_processor.process(&shape)?, None)
+        };
+
+        if args.mass_properties {
+            print_mass_properties(&shape.mass_properties);
+        }
+        if let Some(deviation) = &shape.deviation {
+            print_deviation(deviation);
+        }
+        if let Some(analysis) = &shape.analysis {
+            print_mesh_analysis(analysis);
+        }
+        if let Some(path) = args.export {
+            let decimation = Decimation {
+                max_triangles: args.max_triangles,
+                max_error: args.max_decimation_error.map(Scalar::from_f64),
+            };
+            let options = ExportOptions {
+                decimation: (decimation != Decimation::default())
+                    .then_some(decimation),
+                parts: shape.parts.clone(),
+                metadata: Some(Metadata {
+                    name: metadata.name,
+                    description: metadata
+                        .description
+                        .or(metadata.short_description),
+                    license: metadata.license,
+                }),
+                ply: PlyOptions {
+                    ascii: args.ply_ascii,
+                    colors: if args.ply_vertex_colors {
+                        PlyColors::PerVertex
+                    } else {
+                        PlyColors::PerFace
+                    },
+                },
+                stl: StlOptions {
+                    ascii: args.stl_ascii,
+                    colors: args.stl_colors,
+                },
+            };
+
+            match shells {
+                Some(shells) => export_brep(&shells, &path, &options)?,
+                None => export_with_options(&shape.mesh, &path, &options)?,
+            }
+        }
 
         return Ok(());
     }
@@ -77,3 +161,46 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn print_mass_properties(mass_properties: &MassProperties) {
+    let [x, y, z] = mass_properties.centroid.coords.components;
+
+    println!("Volume: {}", mass_properties.volume);
+    println!("Surface area: {}", mass_properties.area);
+    println!("Centroid: {} {} {}", x, y, z);
+    println!("Inertia tensor (relative to centroid, unit density):");
+    for [a, b, c] in mass_properties.inertia {
+        println!("    {} {} {}", a, b, c);
+    }
+}
+
+fn print_deviation(deviation: &MeshDeviation) {
+    println!("Maximum deviation: {}", deviation.max());
+    println!("Mean deviation: {}", deviation.mean());
+    println!("Deviation per face:");
+    for face in &deviation.faces {
+        let [x, y, z] = face.worst_point.coords.components;
+
+        println!(
+            "    Face {}: max {} (at {} {} {}), mean {}",
+            face.face, face.max, x, y, z, face.mean
+        );
+    }
+}
+
+fn print_mesh_analysis(analysis: &MeshAnalysis) {
+    println!("Connected components: {}", analysis.num_components);
+    println!("Boundary edges: {}", analysis.boundary_edges.len());
+    println!("Non-manifold edges: {}", analysis.non_manifold_edges.len());
+    println!("Flipped triangles: {}", analysis.flipped_triangles.len());
+    println!(
+        "Intersecting triangle pairs: {}",
+        analysis.intersecting_triangles.len()
+    );
+
+    if analysis.is_valid() {
+        println!("The mesh is a closed manifold without self-intersections.");
+    } else {
+        println!("The mesh is not a valid solid.");
+    }
+}
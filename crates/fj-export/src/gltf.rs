@@ -0,0 +1,374 @@
+//! Export to the glTF 2.0 format
+//!
+//! Both the JSON-based format, with the binary data embedded as a data URI,
+//! and the binary GLB container are supported. Neither refers to any external
+//! resources.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Point, Scalar, Vector};
+use serde_json::{json, Value};
+
+use crate::Error;
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+const TRIANGLES: u32 = 4;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+pub fn export_gltf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+    let (mut document, buffer) = build(mesh);
+
+    if !buffer.is_empty() {
+        document["buffers"][0]["uri"] = Value::String(format!(
+            "data:application/octet-stream;base64,{}",
+            base64::encode(&buffer)
+        ));
+    }
+
+    let mut file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut file, &document)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+pub fn export_glb(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+    let (document, buffer) = build(mesh);
+
+    let mut file = BufWriter::new(File::create(path)?);
+    write_glb(&document, &buffer, &mut file)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+/// Build the glTF document and its binary buffer
+///
+/// glTF has a single normal per vertex, so a vertex of the mesh is duplicated
+/// for every distinct normal it has. Triangles are grouped into one primitive
+/// per color, each with its own material.
+fn build(mesh: &Mesh<Point<3>>) -> (Value, Vec<u8>) {
+    let mut vertices = Vec::new();
+    let mut vertex_indices: HashMap<(Point<3>, Vector<3>), u32> =
+        HashMap::new();
+    let mut primitives: BTreeMap<Color, Vec<u32>> = BTreeMap::new();
+
+    for triangle in mesh.triangles() {
+        let points = triangle.inner.points();
+        let normals = triangle.vertex_normals();
+
+        let indices = primitives.entry(triangle.color).or_default();
+        for vertex in points.into_iter().zip(normals) {
+            let index = *vertex_indices.entry(vertex).or_insert_with(|| {
+                vertices.push(vertex);
+                vertices.len() as u32 - 1
+            });
+            indices.push(index);
+        }
+    }
+
+    let mut document = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": "Fornjot",
+        },
+        "scene": 0,
+        "scenes": [{ "nodes": [] }],
+    });
+
+    if vertices.is_empty() {
+        return (document, Vec::new());
+    }
+
+    let mut buffer = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+
+    let mut push_view = |buffer: &mut Vec<u8>, data: Vec<u8>, target| {
+        buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": buffer.len(),
+            "byteLength": data.len(),
+            "target": target,
+        }));
+        buffer.extend(data);
+
+        buffer_views.len() - 1
+    };
+
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+
+    for (point, normal) in &vertices {
+        let point = point.coords.components.map(Scalar::into_f32);
+        for (i, &coord) in point.iter().enumerate() {
+            min[i] = min[i].min(coord);
+            max[i] = max[i].max(coord);
+        }
+
+        positions.extend(point.iter().flat_map(|coord| coord.to_le_bytes()));
+        normals.extend(
+            normal
+                .components
+                .map(Scalar::into_f32)
+                .iter()
+                .flat_map(|coord| coord.to_le_bytes()),
+        );
+    }
+
+    let view = push_view(&mut buffer, positions, ARRAY_BUFFER);
+    accessors.push(json!({
+        "bufferView": view,
+        "componentType": FLOAT,
+        "count": vertices.len(),
+        "type": "VEC3",
+        "min": min,
+        "max": max,
+    }));
+    let view = push_view(&mut buffer, normals, ARRAY_BUFFER);
+    accessors.push(json!({
+        "bufferView": view,
+        "componentType": FLOAT,
+        "count": vertices.len(),
+        "type": "VEC3",
+    }));
+
+    let mut materials = Vec::new();
+    let mut mesh_primitives = Vec::new();
+
+    for (color, indices) in primitives {
+        let count = indices.len();
+        let data = indices.iter().flat_map(|index| index.to_le_bytes());
+
+        let view = push_view(&mut buffer, data.collect(), ELEMENT_ARRAY_BUFFER);
+        accessors.push(json!({
+            "bufferView": view,
+            "componentType": UNSIGNED_INT,
+            "count": count,
+            "type": "SCALAR",
+        }));
+
+        materials.push(material(color));
+        mesh_primitives.push(json!({
+            "attributes": {
+                "POSITION": 0,
+                "NORMAL": 1,
+            },
+            "indices": accessors.len() - 1,
+            "material": materials.len() - 1,
+            "mode": TRIANGLES,
+        }));
+    }
+
+    document["scenes"][0]["nodes"] = json!([0]);
+    document["nodes"] = json!([{
+        // glTF uses a y-up coordinate system, while Fornjot uses z-up.
+        "rotation": [-0.5f64.sqrt(), 0., 0., 0.5f64.sqrt()],
+        "mesh": 0,
+    }]);
+    document["meshes"] = json!([{ "primitives": mesh_primitives }]);
+    document["materials"] = Value::Array(materials);
+    document["accessors"] = Value::Array(accessors);
+    document["bufferViews"] = Value::Array(buffer_views);
+    document["buffers"] = json!([{ "byteLength": buffer.len() }]);
+
+    (document, buffer)
+}
+
+fn material(color: Color) -> Value {
+    // The colors of the mesh are in sRGB, but glTF expects linear factors.
+    let [r, g, b, a] = color.0.map(|channel| f64::from(channel) / 255.);
+    let [r, g, b] = [r, g, b].map(|channel| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+
+    let alpha_mode = if color.0[3] == u8::MAX {
+        "OPAQUE"
+    } else {
+        "BLEND"
+    };
+
+    json!({
+        "pbrMetallicRoughness": {
+            "baseColorFactor": [r, g, b, a],
+            "metallicFactor": 0.,
+            "roughnessFactor": 1.,
+        },
+        "alphaMode": alpha_mode,
+    })
+}
+
+fn write_glb(
+    document: &Value,
+    buffer: &[u8],
+    mut writer: impl Write,
+) -> Result<(), Error> {
+    let mut json = serde_json::to_vec(document)?;
+
+    // Chunks must be aligned to 4 bytes. The JSON chunk is padded with spaces,
+    // the binary chunk with zeros.
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    let mut buffer = buffer.to_vec();
+    while buffer.len() % 4 != 0 {
+        buffer.push(0);
+    }
+
+    let mut length = 12 + 8 + json.len();
+    if !buffer.is_empty() {
+        length += 8 + buffer.len();
+    }
+    let length = u32::try_from(length).map_err(|_| Error::InvalidFileSize)?;
+
+    writer.write_all(&GLB_MAGIC.to_le_bytes())?;
+    writer.write_all(&GLB_VERSION.to_le_bytes())?;
+    writer.write_all(&length.to_le_bytes())?;
+
+    let mut chunks = vec![(CHUNK_JSON, json)];
+    if !buffer.is_empty() {
+        chunks.push((CHUNK_BIN, buffer));
+    }
+
+    for (kind, data) in chunks {
+        // The chunk length is bounded by the total length, checked above.
+        writer.write_all(&(data.len() as u32).to_le_bytes())?;
+        writer.write_all(&kind.to_le_bytes())?;
+        writer.write_all(&data)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+    use serde_json::Value;
+
+    use super::{build, write_glb, CHUNK_BIN, CHUNK_JSON, GLB_MAGIC};
+
+    #[test]
+    fn glb_round_trip() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+        let opaque = Color([255, 0, 0, 255]);
+        let transparent = Color([0, 0, 255, 128]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, c, b], opaque);
+        mesh.push_triangle([a, b, d], opaque);
+        mesh.push_triangle([b, c, d], transparent);
+        mesh.push_triangle([c, a, d], transparent);
+
+        let (document, buffer) = build(&mesh);
+        let mut glb = Vec::new();
+        write_glb(&document, &buffer, &mut glb)?;
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes([
+                glb[offset],
+                glb[offset + 1],
+                glb[offset + 2],
+                glb[offset + 3],
+            ])
+        };
+
+        assert_eq!(read_u32(0), GLB_MAGIC);
+        assert_eq!(read_u32(8) as usize, glb.len());
+
+        let json_length = read_u32(12) as usize;
+        assert_eq!(read_u32(16), CHUNK_JSON);
+        let json: Value = serde_json::from_slice(&glb[20..20 + json_length])?;
+
+        let bin_offset = 20 + json_length;
+        let bin_length = read_u32(bin_offset) as usize;
+        assert_eq!(read_u32(bin_offset + 4), CHUNK_BIN);
+        let bin = &glb[bin_offset + 8..bin_offset + 8 + bin_length];
+
+        // Every triangle has a different normal, so no vertices are shared.
+        let primitives = json["meshes"][0]["primitives"]
+            .as_array()
+            .expect("Expected primitives");
+        assert_eq!(primitives.len(), 2);
+        assert_eq!(json["accessors"][0]["count"], 12);
+
+        let alpha_modes = json["materials"]
+            .as_array()
+            .expect("Expected materials")
+            .iter()
+            .map(|material| material["alphaMode"].as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(alpha_modes, [Some("OPAQUE"), Some("BLEND")]);
+
+        // Reconstruct the triangles from the binary data.
+        let accessor_data = |accessor: usize| {
+            let view = json["accessors"][accessor]["bufferView"]
+                .as_u64()
+                .expect("Expected buffer view") as usize;
+            let view = &json["bufferViews"][view];
+            let offset = view["byteOffset"].as_u64().unwrap_or(0) as usize;
+            let length =
+                view["byteLength"].as_u64().expect("Expected length") as usize;
+
+            bin[offset..offset + length]
+                .chunks_exact(4)
+                .map(|bytes| [bytes[0], bytes[1], bytes[2], bytes[3]])
+                .collect::<Vec<_>>()
+        };
+
+        let positions = accessor_data(0)
+            .into_iter()
+            .map(f32::from_le_bytes)
+            .collect::<Vec<_>>();
+
+        let mut num_triangles = 0;
+        for primitive in primitives {
+            let indices =
+                primitive["indices"].as_u64().expect("Expected indices");
+            let indices = accessor_data(indices as usize)
+                .into_iter()
+                .map(u32::from_le_bytes)
+                .collect::<Vec<_>>();
+
+            for triangle in indices.chunks_exact(3) {
+                let points = [triangle[0], triangle[1], triangle[2]].map(|i| {
+                    let i = i as usize * 3;
+                    Point::from([
+                        positions[i] as f64,
+                        positions[i + 1] as f64,
+                        positions[i + 2] as f64,
+                    ])
+                });
+
+                assert!(mesh.contains_triangle(points));
+                num_triangles += 1;
+            }
+        }
+        assert_eq!(num_triangles, 4);
+
+        Ok(())
+    }
+}
@@ -14,27 +14,84 @@
 
 #![warn(missing_docs)]
 
-use std::{fs::File, path::Path};
+mod gltf;
+mod obj;
+mod ply;
+mod step;
+mod threemf;
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::Path,
+};
 
 use thiserror::Error;
 
-use fj_interop::mesh::Mesh;
-use fj_math::{Point, Triangle};
+use fj_interop::{
+    decimation::{decimate, Decimation},
+    mesh::{Color, Mesh},
+};
+use fj_kernel::{algorithms::validate::Validated, objects::Shell};
+use fj_math::{Point, Scalar};
 
 /// Export the provided mesh to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
-/// the provided path is used to switch between supported types.
+/// Currently 3MF, glTF (`.gltf` & `.glb`), OBJ, PLY & STL file types are
+/// supported.
+/// The case insensitive file extension of the provided path is used to switch
+/// between supported types.
+///
+/// OBJ files are accompanied by an MTL file with the same name, which contains
+/// the colors of the mesh.
+///
+/// STEP files contain the exact geometry of the model, which the mesh doesn't
+/// provide. Use [`export_brep`] for those.
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+    export_with_options(mesh, path, &ExportOptions::default())
+}
+
+/// Export the provided mesh to the file at the given path, using options
+///
+/// Works like [`export`], but allows for processing the mesh before exporting
+/// it.
+pub fn export_with_options(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let decimated;
+    let mesh = match &options.decimation {
+        Some(decimation) => {
+            decimated = decimate(mesh, decimation);
+            &decimated
+        }
+        None => mesh,
+    };
+
     match path.extension() {
         Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
-            export_3mf(mesh, path)
+            threemf::export(mesh, path, options)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "GLB" => {
+            gltf::export_glb(mesh, path)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "GLTF" => {
+            gltf::export_gltf(mesh, path)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+            obj::export(mesh, path, options)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "PLY" => {
+            ply::export(mesh, path, &options.ply)
         }
         Some(extension) if extension.to_ascii_uppercase() == "STL" => {
-            export_stl(mesh, path)
+            export_stl(mesh, path, options)
         }
+        Some(_) if is_brep_format(path) => Err(Error::BRepRequired),
         Some(extension) => Err(Error::InvalidExtension(
             extension.to_string_lossy().into_owned(),
         )),
@@ -42,60 +99,177 @@ pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     }
 }
 
-fn export_3mf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    let vertices = mesh.vertices().map(|vertex| vertex.into()).collect();
+/// Export the boundary representation of a model to the file at the given path
+///
+/// This function will create a file if it does not exist, and will truncate it
+/// if it does.
+///
+/// Each shell is written as a separate part. Neighboring faces of a shell need
+/// to share their edges, for the shell to be recognized as closed.
+///
+/// Currently only STEP (`.step` & `.stp`) files are supported. The metadata
+/// from the options is used; everything else only applies to meshes.
+pub fn export_brep(
+    shells: &[Validated<Shell>],
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    match path.extension() {
+        Some(_) if is_brep_format(path) => step::export(shells, path, options),
+        Some(extension) => Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        )),
+        None => Err(Error::NoExtension),
+    }
+}
 
-    let indices: Vec<_> = mesh.indices().collect();
-    let triangles = indices
-        .chunks(3)
-        .map(|triangle| {
-            [
-                triangle[0] as usize,
-                triangle[1] as usize,
-                triangle[2] as usize,
-            ]
-        })
-        .collect();
+/// Indicate whether the file at the given path needs the boundary
+/// representation of a model
+///
+/// If so, use [`export_brep`] to export it. Otherwise, use [`export`] or
+/// [`export_with_options`].
+pub fn is_brep_format(path: &Path) -> bool {
+    match path.extension() {
+        Some(extension) => {
+            let extension = extension.to_ascii_uppercase();
+            extension == "STEP" || extension == "STP"
+        }
+        None => false,
+    }
+}
 
-    let mesh = threemf::TriangleMesh {
-        vertices,
-        triangles,
-    };
+/// Options for exporting a mesh
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExportOptions {
+    /// Simplify the mesh before exporting it
+    ///
+    /// Only useful for exports that are meant for visualization, like previews
+    /// of big assemblies.
+    pub decimation: Option<Decimation>,
 
-    threemf::write(path, &mesh)?;
+    /// The faces that make up each part of the model
+    ///
+    /// Each range refers to the [`FaceId`]s of the mesh's triangles. Formats
+    /// that support multiple objects write each part as a separate object.
+    /// Triangles that don't belong to any part are written as an additional
+    /// object.
+    ///
+    /// [`FaceId`]: fj_interop::mesh::FaceId
+    pub parts: Vec<Range<usize>>,
 
-    Ok(())
+    /// Information about the model, for formats that support it
+    pub metadata: Option<Metadata>,
+
+    /// Options that only apply to PLY files
+    pub ply: PlyOptions,
+
+    /// Options that only apply to STL files
+    pub stl: StlOptions,
+}
+
+/// Options for exporting to PLY
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PlyOptions {
+    /// Write ASCII PLY, instead of binary PLY
+    pub ascii: bool,
+
+    /// Whether to write colors per face or per vertex
+    pub colors: PlyColors,
+}
+
+/// Where colors are written to in PLY files
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PlyColors {
+    /// Write a color for each face
+    ///
+    /// Vertices are shared between all faces.
+    #[default]
+    PerFace,
+
+    /// Write a color for each vertex
+    ///
+    /// Not all software supports colors per face. Vertices that are shared
+    /// between faces of different colors are duplicated.
+    PerVertex,
 }
 
-fn export_stl(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    let points = mesh
+/// Options for exporting to STL
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StlOptions {
+    /// Write ASCII STL, instead of binary STL
+    ///
+    /// The name of the model, from [`ExportOptions::metadata`], is used as the
+    /// name of the solid. If it isn't available, the file name is used.
+    pub ascii: bool,
+
+    /// Write the color of each triangle to its attribute bytes
+    ///
+    /// Uses the 15-bit color encoding of VisCAM and SolidView. Only applies to
+    /// binary STL.
+    pub colors: bool,
+}
+
+/// Information about the exported model
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Metadata {
+    /// The name of the model
+    pub name: String,
+
+    /// A description of the model
+    pub description: Option<String>,
+
+    /// The license that the model is released under
+    pub license: Option<String>,
+}
+
+fn export_stl(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let mut file = BufWriter::new(File::create(path)?);
+
+    if options.stl.ascii {
+        let name = match &options.metadata {
+            Some(metadata) => metadata.name.clone(),
+            None => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        };
+
+        write_stl_ascii(mesh, &name, &mut file)?;
+        file.flush()?;
+
+        return Ok(());
+    }
+
+    let triangles = mesh
         .triangles()
-        .map(|triangle| triangle.inner.points())
-        .collect::<Vec<_>>();
+        .map(|triangle| {
+            let [v1, v2, v3] = triangle
+                .inner
+                .points()
+                .map(|point| point.coords.components.map(|s| s.into_f32()));
+            let normal =
+                triangle.inner.normal().components.map(|s| s.into_f32());
 
-    let vertices = points.iter().map(|points| {
-        points.map(|point| point.coords.components.map(|s| s.into_f32()))
-    });
-
-    let normals = points
-        .iter()
-        .map(|&points| points.into())
-        .map(|triangle: Triangle<3>| triangle.normal())
-        .map(|vector| vector.components.map(|s| s.into_f32()));
-
-    let triangles = vertices
-        .zip(normals)
-        .map(|([v1, v2, v3], normal)| stl::Triangle {
-            normal,
-            v1,
-            v2,
-            v3,
-            attr_byte_count: 0,
+            let attr_byte_count = if options.stl.colors {
+                stl_color(triangle.color)
+            } else {
+                0
+            };
+
+            stl::Triangle {
+                normal,
+                v1,
+                v2,
+                v3,
+                attr_byte_count,
+            }
         })
         .collect::<Vec<_>>();
 
-    let mut file = File::create(path)?;
-
     let binary_stl_file = stl::BinaryStlFile {
         header: stl::BinaryStlHeader {
             header: [0u8; 80],
@@ -108,10 +282,49 @@ fn export_stl(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     };
 
     stl::write_stl(&mut file, &binary_stl_file)?;
+    file.flush()?;
 
     Ok(())
 }
 
+fn write_stl_ascii(
+    mesh: &Mesh<Point<3>>,
+    name: &str,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    // The name ends at the first whitespace, for many readers.
+    let name = name.split_whitespace().collect::<Vec<_>>().join("_");
+
+    writeln!(writer, "solid {name}")?;
+
+    for triangle in mesh.triangles() {
+        let [nx, ny, nz] =
+            triangle.inner.normal().components.map(Scalar::into_f64);
+        writeln!(writer, "  facet normal {nx:e} {ny:e} {nz:e}")?;
+        writeln!(writer, "    outer loop")?;
+        for point in triangle.inner.points() {
+            let [x, y, z] = point.coords.components.map(Scalar::into_f64);
+            writeln!(writer, "      vertex {x:e} {y:e} {z:e}")?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+
+    writeln!(writer, "endsolid {name}")?;
+
+    Ok(())
+}
+
+/// Encode a color in the attribute bytes of a binary STL triangle
+///
+/// This is the encoding used by VisCAM and SolidView: 5 bits per channel, with
+/// blue in the lowest bits, and the highest bit set to mark the color as
+/// valid. Alpha can't be represented.
+fn stl_color(Color([r, g, b, _]): Color) -> u16 {
+    let [r, g, b] = [r, g, b].map(|channel| u16::from(channel >> 3));
+    0x8000 | r << 10 | g << 5 | b
+}
+
 /// An error that can occur while exporting
 #[derive(Debug, Error)]
 pub enum Error {
@@ -123,6 +336,10 @@ pub enum Error {
     #[error("unrecognized extension found `{0:?}`")]
     InvalidExtension(String),
 
+    /// The format requires the boundary representation, not just a mesh
+    #[error("exporting to this format requires the boundary representation")]
+    BRepRequired,
+
     /// I/O error whilst exporting to file
     #[error("I/O error whilst exporting to file")]
     Io(#[from] std::io::Error),
@@ -131,7 +348,53 @@ pub enum Error {
     #[error("maximum triangle count exceeded")]
     InvalidTriangleCount,
 
-    /// Threemf error whilst exporting to 3MF file
-    #[error("threemf error whilst exporting to 3MF file")]
-    ThreeMF(#[from] threemf::Error),
+    /// Maximum file size of the format exceeded
+    #[error("maximum file size of the format exceeded")]
+    InvalidFileSize,
+
+    /// JSON error whilst exporting to glTF file
+    #[error("JSON error whilst exporting to glTF file")]
+    Json(#[from] serde_json::Error),
+
+    /// Zip error whilst exporting to 3MF file
+    #[error("zip error whilst exporting to 3MF file")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use super::{stl_color, write_stl_ascii};
+
+    #[test]
+    fn stl_ascii() -> anyhow::Result<()> {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+            Color::default(),
+        );
+
+        let mut stl = Vec::new();
+        write_stl_ascii(&mesh, "my model", &mut stl)?;
+        let stl = String::from_utf8(stl)?;
+
+        let lines = stl.lines().map(str::trim).collect::<Vec<_>>();
+        assert_eq!(lines[0], "solid my_model");
+        assert_eq!(lines[1], "facet normal 0e0 0e0 1e0");
+        assert_eq!(lines[3], "vertex 0e0 0e0 0e0");
+        assert_eq!(lines.last(), Some(&"endsolid my_model"));
+        assert_eq!(lines.len(), 9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stl_colors() {
+        assert_eq!(stl_color(Color([0, 0, 0, 255])), 0x8000);
+        assert_eq!(stl_color(Color([255, 0, 0, 255])), 0xfc00);
+        assert_eq!(stl_color(Color([0, 255, 0, 255])), 0x83e0);
+        assert_eq!(stl_color(Color([0, 0, 255, 0])), 0x801f);
+    }
 }
@@ -0,0 +1,240 @@
+//! Export to the Wavefront OBJ format
+//!
+//! Colors are written as materials, to an MTL file next to the OBJ file. Each
+//! part of the model becomes a separate object.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::Path,
+};
+
+use fj_interop::mesh::{Color, Mesh, Triangle};
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{Error, ExportOptions};
+
+pub fn export(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let mtl_path = path.with_extension("mtl");
+    let mtl_name = mtl_path.file_name().unwrap_or_default().to_string_lossy();
+    let object_name = path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| String::from("model"));
+
+    let mut obj = BufWriter::new(File::create(path)?);
+    write_obj(mesh, &options.parts, &object_name, &mtl_name, &mut obj)?;
+    obj.flush()?;
+
+    let mut mtl = BufWriter::new(File::create(&mtl_path)?);
+    write_mtl(mesh, &mut mtl)?;
+    mtl.flush()?;
+
+    Ok(())
+}
+
+/// Write the geometry of the mesh
+///
+/// Each part is written as an object, named after the part's position. Without
+/// any parts, the whole mesh is written as a single object. Triangles that
+/// don't belong to any of the parts are written to an additional object.
+///
+/// Within each object, triangles are grouped by the face they were created
+/// from. Triangles without a face are written to a group of their own.
+fn write_obj(
+    mesh: &Mesh<Point<3>>,
+    parts: &[Range<usize>],
+    object_name: &str,
+    mtl_name: &str,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    writeln!(writer, "mtllib {mtl_name}")?;
+
+    for vertex in mesh.vertices() {
+        let [x, y, z] = vertex.coords.components.map(Scalar::into_f64);
+        writeln!(writer, "v {x} {y} {z}")?;
+    }
+
+    let indices = mesh.indices().collect::<Vec<_>>();
+    let triangles = mesh.triangles().zip(indices.chunks_exact(3));
+
+    let mut normals: HashMap<Vector<3>, usize> = HashMap::new();
+    let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+    for (triangle, indices) in triangles {
+        let normal_indices = triangle.vertex_normals().map(|normal| {
+            let next = normals.len();
+            let index = *normals.entry(normal).or_insert(next);
+
+            if index == next {
+                let [x, y, z] = normal.components.map(Scalar::into_f64);
+                writeln!(writer, "vn {x} {y} {z}")?;
+            }
+
+            Ok::<_, io::Error>(index)
+        });
+        let [n0, n1, n2] = normal_indices;
+        let normal_indices = [n0?, n1?, n2?];
+
+        let vertex_indices = [indices[0], indices[1], indices[2]];
+
+        let part = triangle
+            .face
+            .and_then(|face| {
+                parts.iter().position(|faces| faces.contains(&face.0))
+            })
+            .unwrap_or(parts.len());
+
+        groups.entry((part, triangle.face)).or_default().push((
+            triangle,
+            vertex_indices,
+            normal_indices,
+        ));
+    }
+
+    let mut current_part = None;
+
+    for ((part, face), triangles) in groups {
+        if current_part != Some(part) {
+            if parts.is_empty() {
+                writeln!(writer, "o {object_name}")?;
+            } else {
+                writeln!(writer, "o {object_name}_part_{}", part + 1)?;
+            }
+            current_part = Some(part);
+        }
+
+        match face {
+            Some(face) => writeln!(writer, "g face_{}", face.0)?,
+            None => writeln!(writer, "g mesh")?,
+        }
+
+        let mut current_color = None;
+
+        for (Triangle { color, .. }, vertices, normals) in triangles {
+            if current_color != Some(color) {
+                writeln!(writer, "usemtl {}", material_name(color))?;
+                current_color = Some(color);
+            }
+
+            // OBJ indices start at 1.
+            write!(writer, "f")?;
+            for (vertex, normal) in vertices.into_iter().zip(normals) {
+                write!(writer, " {}//{}", vertex + 1, normal + 1)?;
+            }
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a material for each color of the mesh
+fn write_mtl(mesh: &Mesh<Point<3>>, mut writer: impl Write) -> io::Result<()> {
+    let mut colors = mesh
+        .triangles()
+        .map(|triangle| triangle.color)
+        .collect::<Vec<_>>();
+    colors.sort();
+    colors.dedup();
+
+    for color in colors {
+        let [r, g, b, a] = color.0.map(|channel| f64::from(channel) / 255.);
+
+        writeln!(writer, "newmtl {}", material_name(color))?;
+        writeln!(writer, "Kd {r} {g} {b}")?;
+        writeln!(writer, "d {a}")?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+fn material_name(Color([r, g, b, a]): Color) -> String {
+    format!("color_{r:02x}{g:02x}{b:02x}{a:02x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, FaceId, Mesh, Triangle};
+    use fj_math::Point;
+
+    use super::{write_mtl, write_obj};
+
+    #[test]
+    fn groups_and_materials() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 128]);
+
+        let mut mesh = Mesh::new();
+        mesh.push(Triangle::new([a, c, b], red).with_face(FaceId(0)));
+        mesh.push(Triangle::new([a, b, d], blue).with_face(FaceId(1)));
+        mesh.push(Triangle::new([b, c, d], blue).with_face(FaceId(1)));
+
+        let mut obj = Vec::new();
+        write_obj(&mesh, &[], "model", "model.mtl", &mut obj)?;
+        let obj = String::from_utf8(obj)?;
+
+        let count = |prefix: &str| {
+            obj.lines().filter(|line| line.starts_with(prefix)).count()
+        };
+        assert_eq!(count("o "), 1);
+        assert_eq!(count("v "), 4);
+        assert_eq!(count("vn "), 3);
+        assert_eq!(count("f "), 3);
+        assert_eq!(count("g "), 2);
+        assert_eq!(count("usemtl "), 2);
+        assert!(obj.contains("f 1//1 2//1 3//1"));
+
+        let mut mtl = Vec::new();
+        write_mtl(&mesh, &mut mtl)?;
+        let mtl = String::from_utf8(mtl)?;
+
+        assert!(mtl.contains("newmtl color_ff0000ff"));
+        assert!(mtl.contains("newmtl color_0000ff80"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parts() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+        let red = Color([255, 0, 0, 255]);
+
+        let mut mesh = Mesh::new();
+        mesh.push(Triangle::new([a, c, b], red).with_face(FaceId(0)));
+        mesh.push(Triangle::new([a, b, d], red).with_face(FaceId(1)));
+        mesh.push(Triangle::new([b, c, d], red).with_face(FaceId(2)));
+        mesh.push(Triangle::new([c, a, d], red));
+
+        let mut obj = Vec::new();
+        write_obj(&mesh, &[0..1, 1..3], "model", "model.mtl", &mut obj)?;
+        let obj = String::from_utf8(obj)?;
+
+        let objects = obj
+            .lines()
+            .filter(|line| line.starts_with("o "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            objects,
+            ["o model_part_1", "o model_part_2", "o model_part_3"]
+        );
+        assert_eq!(
+            obj.lines().filter(|line| line.starts_with("g ")).count(),
+            4
+        );
+
+        Ok(())
+    }
+}
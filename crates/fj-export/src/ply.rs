@@ -0,0 +1,371 @@
+//! Export to the Polygon File Format (PLY)
+//!
+//! Coordinates are written in double precision, so no accuracy is lost. Colors
+//! can be written per face or per vertex.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Point, Scalar};
+
+use crate::{Error, PlyColors, PlyOptions};
+
+pub fn export(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &PlyOptions,
+) -> Result<(), Error> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write_ply(mesh, options, &mut file)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+fn write_ply(
+    mesh: &Mesh<Point<3>>,
+    options: &PlyOptions,
+    mut writer: impl Write,
+) -> Result<(), Error> {
+    let (vertices, faces) = match options.colors {
+        PlyColors::PerFace => {
+            let vertices = mesh
+                .vertices()
+                .map(|vertex| (vertex, None))
+                .collect::<Vec<_>>();
+            let indices = mesh.indices().collect::<Vec<_>>();
+            let faces = mesh
+                .triangles()
+                .zip(indices.chunks_exact(3))
+                .map(|(triangle, indices)| {
+                    ([indices[0], indices[1], indices[2]], Some(triangle.color))
+                })
+                .collect::<Vec<_>>();
+
+            (vertices, faces)
+        }
+        PlyColors::PerVertex => {
+            // A vertex can only have one color, so vertices that are shared
+            // between triangles of different colors are duplicated.
+            let mut vertices = Vec::new();
+            let mut indices = HashMap::new();
+
+            let faces = mesh
+                .triangles()
+                .map(|triangle| {
+                    let face = triangle.inner.points().map(|point| {
+                        *indices.entry((point, triangle.color)).or_insert_with(
+                            || {
+                                vertices.push((point, Some(triangle.color)));
+                                vertices.len() as u32 - 1
+                            },
+                        )
+                    });
+
+                    (face, None)
+                })
+                .collect::<Vec<_>>();
+
+            (vertices, faces)
+        }
+    };
+
+    let format = if options.ascii {
+        "ascii"
+    } else {
+        "binary_little_endian"
+    };
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format {format} 1.0")?;
+    writeln!(writer, "comment Exported by Fornjot")?;
+    writeln!(writer, "element vertex {}", vertices.len())?;
+    for coord in ["x", "y", "z"] {
+        writeln!(writer, "property double {coord}")?;
+    }
+    if options.colors == PlyColors::PerVertex {
+        write_color_properties(&mut writer)?;
+    }
+    writeln!(writer, "element face {}", faces.len())?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    if options.colors == PlyColors::PerFace {
+        write_color_properties(&mut writer)?;
+    }
+    writeln!(writer, "end_header")?;
+
+    for (point, color) in vertices {
+        let coords = point.coords.components.map(Scalar::into_f64);
+
+        if options.ascii {
+            let [x, y, z] = coords;
+            write!(writer, "{x} {y} {z}")?;
+            write_color_ascii(color, &mut writer)?;
+            writeln!(writer)?;
+        } else {
+            for coord in coords {
+                writer.write_all(&coord.to_le_bytes())?;
+            }
+            if let Some(Color(color)) = color {
+                writer.write_all(&color)?;
+            }
+        }
+    }
+
+    for (indices, color) in faces {
+        if options.ascii {
+            let [a, b, c] = indices;
+            write!(writer, "3 {a} {b} {c}")?;
+            write_color_ascii(color, &mut writer)?;
+            writeln!(writer)?;
+        } else {
+            writer.write_all(&[3])?;
+            for index in indices {
+                writer.write_all(&index.to_le_bytes())?;
+            }
+            if let Some(Color(color)) = color {
+                writer.write_all(&color)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_color_properties(mut writer: impl Write) -> io::Result<()> {
+    for channel in ["red", "green", "blue", "alpha"] {
+        writeln!(writer, "property uchar {channel}")?;
+    }
+
+    Ok(())
+}
+
+fn write_color_ascii(
+    color: Option<Color>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    if let Some(Color([r, g, b, a])) = color {
+        write!(writer, " {r} {g} {b} {a}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Read};
+
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use crate::{PlyColors, PlyOptions};
+
+    use super::write_ply;
+
+    #[test]
+    fn round_trip() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0.1, 0.2, 1.3]]
+                .map(Point::from);
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 128]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, c, b], red);
+        mesh.push_triangle([a, b, d], red);
+        mesh.push_triangle([b, c, d], blue);
+        mesh.push_triangle([c, a, d], blue);
+
+        for ascii in [false, true] {
+            for colors in [PlyColors::PerFace, PlyColors::PerVertex] {
+                let options = PlyOptions { ascii, colors };
+
+                let mut ply = Vec::new();
+                write_ply(&mesh, &options, &mut ply)?;
+                let ply = read_ply(ply.as_slice())?;
+
+                let num_vertices = match colors {
+                    PlyColors::PerFace => 4,
+                    // All vertices are shared between both colors.
+                    PlyColors::PerVertex => 8,
+                };
+                assert_eq!(ply.vertices.len(), num_vertices, "{options:?}");
+                assert_eq!(ply.faces.len(), 4, "{options:?}");
+
+                for (triangle, (indices, face_color)) in
+                    mesh.triangles().zip(&ply.faces)
+                {
+                    let points = indices.iter().map(|&index| {
+                        let (point, _) = ply.vertices[index as usize];
+                        Point::from(point)
+                    });
+                    assert!(points.eq(triangle.inner.points()), "{options:?}");
+
+                    let vertex_colors = match colors {
+                        PlyColors::PerFace => vec![*face_color],
+                        PlyColors::PerVertex => indices
+                            .iter()
+                            .map(|&index| ply.vertices[index as usize].1)
+                            .collect(),
+                    };
+                    for color in vertex_colors {
+                        assert_eq!(
+                            color,
+                            Some(triangle.color.0),
+                            "{options:?}"
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The contents of a PLY file
+    struct Ply {
+        vertices: Vec<([f64; 3], Option<[u8; 4]>)>,
+        faces: Vec<(Vec<u32>, Option<[u8; 4]>)>,
+    }
+
+    /// Read a PLY file
+    ///
+    /// Only supports what [`write_ply`] writes.
+    fn read_ply(mut reader: impl BufRead) -> anyhow::Result<Ply> {
+        let mut elements: Vec<(String, usize, Vec<String>)> = Vec::new();
+        let mut ascii = false;
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let words = line.split_whitespace().collect::<Vec<_>>();
+
+            match words.as_slice() {
+                ["ply"] | ["comment", ..] => {}
+                ["format", format, "1.0"] => {
+                    ascii = match *format {
+                        "ascii" => true,
+                        "binary_little_endian" => false,
+                        format => anyhow::bail!("Unsupported format {format}"),
+                    };
+                }
+                ["element", name, count] => elements.push((
+                    name.to_string(),
+                    count.parse()?,
+                    Vec::new(),
+                )),
+                ["property", .., name] => {
+                    let (_, _, properties) = elements
+                        .last_mut()
+                        .ok_or_else(|| anyhow::anyhow!("Property first"))?;
+                    properties.push(name.to_string());
+                }
+                ["end_header"] => break,
+                _ => anyhow::bail!("Unexpected header line: {line}"),
+            }
+        }
+
+        let mut body = Vec::new();
+        reader.read_to_end(&mut body)?;
+        let mut values = Values {
+            ascii,
+            words: String::from_utf8_lossy(&body)
+                .split_whitespace()
+                .map(String::from)
+                .collect::<Vec<_>>()
+                .into_iter(),
+            bytes: body.as_slice(),
+        };
+
+        let mut ply = Ply {
+            vertices: Vec::new(),
+            faces: Vec::new(),
+        };
+
+        for (name, count, properties) in elements {
+            let has_color = properties.iter().any(|name| name == "red");
+
+            for _ in 0..count {
+                match name.as_str() {
+                    "vertex" => {
+                        let point =
+                            [values.f64()?, values.f64()?, values.f64()?];
+                        let color =
+                            has_color.then(|| values.color()).transpose()?;
+                        ply.vertices.push((point, color));
+                    }
+                    "face" => {
+                        let num_indices = values.u8()?;
+                        let indices = (0..num_indices)
+                            .map(|_| values.u32())
+                            .collect::<Result<_, _>>()?;
+                        let color =
+                            has_color.then(|| values.color()).transpose()?;
+                        ply.faces.push((indices, color));
+                    }
+                    name => anyhow::bail!("Unexpected element {name}"),
+                }
+            }
+        }
+
+        Ok(ply)
+    }
+
+    struct Values<'r> {
+        ascii: bool,
+        words: std::vec::IntoIter<String>,
+        bytes: &'r [u8],
+    }
+
+    impl Values<'_> {
+        fn take<const N: usize>(&mut self) -> anyhow::Result<[u8; N]> {
+            anyhow::ensure!(self.bytes.len() >= N, "Unexpected end of file");
+            let (bytes, rest) = self.bytes.split_at(N);
+            self.bytes = rest;
+            Ok(bytes.try_into()?)
+        }
+
+        fn word<T: std::str::FromStr>(&mut self) -> anyhow::Result<T>
+        where
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            let word = self
+                .words
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Unexpected end of file"))?;
+            Ok(word.parse()?)
+        }
+
+        fn f64(&mut self) -> anyhow::Result<f64> {
+            if self.ascii {
+                self.word()
+            } else {
+                Ok(f64::from_le_bytes(self.take()?))
+            }
+        }
+
+        fn u32(&mut self) -> anyhow::Result<u32> {
+            if self.ascii {
+                self.word()
+            } else {
+                Ok(u32::from_le_bytes(self.take()?))
+            }
+        }
+
+        fn u8(&mut self) -> anyhow::Result<u8> {
+            if self.ascii {
+                self.word()
+            } else {
+                Ok(u8::from_le_bytes(self.take()?))
+            }
+        }
+
+        fn color(&mut self) -> anyhow::Result<[u8; 4]> {
+            Ok([self.u8()?, self.u8()?, self.u8()?, self.u8()?])
+        }
+    }
+}
@@ -0,0 +1,986 @@
+//! Export to STEP (ISO 10303-21), using the AP214 schema
+//!
+//! Unlike the other formats, STEP files contain the exact boundary
+//! representation of the model, not an approximation of it. Planes, swept
+//! surfaces, lines, and circles are written as such.
+//!
+//! Each shell is written as a part of the model. Faces that are only available
+//! as triangles are written as planar triangular faces. Those don't share edges
+//! with exact faces, so the shells they are part of usually don't close up. If
+//! any part isn't a closed shell, the model is written as a surface model,
+//! instead of a set of solids.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::SystemTime,
+};
+
+use fj_kernel::{
+    algorithms::{
+        adjacency::Adjacency,
+        validate::{Validated, ValidationConfig},
+    },
+    objects::{CurveKind, Cycle, Edge, Face, GlobalEdge, Shell, Surface},
+};
+use fj_math::{Circle, Point, Scalar, Triangle, Vector};
+
+use crate::{Error, ExportOptions, Metadata};
+
+pub fn export(
+    shells: &[Validated<Shell>],
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let shells = shells.iter().map(|shell| &**shell).collect::<Vec<_>>();
+    let time_stamp = time_stamp(SystemTime::now());
+
+    let mut file = BufWriter::new(File::create(path)?);
+    write_step(&shells, options.metadata.as_ref(), &time_stamp, &mut file)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+fn write_step(
+    shells: &[&Shell],
+    metadata: Option<&Metadata>,
+    time_stamp: &str,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let name = metadata
+        .map(|metadata| string(&metadata.name))
+        .unwrap_or_else(|| String::from("''"));
+
+    let mut description = Vec::new();
+    if let Some(metadata) = metadata {
+        if let Some(text) = &metadata.description {
+            description.push(string(text));
+        }
+        if let Some(license) = &metadata.license {
+            description.push(string(&format!("License: {license}")));
+        }
+    }
+    if description.is_empty() {
+        description.push(String::from("''"));
+    }
+
+    let mut entities = Entities::default();
+
+    let context = write_context(&mut entities);
+    let origin =
+        entities.placement(Point::origin(), Vector::unit_z(), Vector::unit_x());
+
+    let shells = shells
+        .iter()
+        .filter(|shell| shell.faces().next().is_some())
+        .map(|shell| ShellWriter::new(&mut entities).write(shell))
+        .collect::<Vec<_>>();
+
+    let closed = shells.iter().all(|&(_, closed)| closed);
+
+    let mut items = vec![origin];
+    for (i, &(shell, _)) in shells.iter().enumerate() {
+        let part = i + 1;
+        let item = if closed {
+            format!("MANIFOLD_SOLID_BREP('Part {part}',#{shell})")
+        } else {
+            format!("SHELL_BASED_SURFACE_MODEL('Part {part}',(#{shell}))")
+        };
+        items.push(entities.add(item));
+    }
+
+    let representation = if closed {
+        "ADVANCED_BREP_SHAPE_REPRESENTATION"
+    } else {
+        "MANIFOLD_SURFACE_SHAPE_REPRESENTATION"
+    };
+    let representation = entities.add(format!(
+        "{representation}({name},({}),#{context})",
+        refs(&items)
+    ));
+
+    write_product(&mut entities, &name, representation);
+
+    writeln!(writer, "ISO-10303-21;")?;
+    writeln!(writer, "HEADER;")?;
+    writeln!(
+        writer,
+        "FILE_DESCRIPTION(({}),'2;1');",
+        description.join(",")
+    )?;
+    writeln!(
+        writer,
+        "FILE_NAME({name},{},(''),(''),'Fornjot','Fornjot','');",
+        string(time_stamp)
+    )?;
+    writeln!(
+        writer,
+        "FILE_SCHEMA(('AUTOMOTIVE_DESIGN {{ 1 0 10303 214 1 1 1 1 }}'));"
+    )?;
+    writeln!(writer, "ENDSEC;")?;
+
+    writeln!(writer, "DATA;")?;
+    for (i, entity) in entities.entities.iter().enumerate() {
+        writeln!(writer, "#{}={entity};", i + 1)?;
+    }
+    writeln!(writer, "ENDSEC;")?;
+    writeln!(writer, "END-ISO-10303-21;")?;
+
+    Ok(())
+}
+
+/// Write the units and the uncertainty of the geometry
+///
+/// Returns the ID of the representation context.
+fn write_context(entities: &mut Entities) -> usize {
+    // Fornjot doesn't define units, but models are conventionally designed in
+    // millimeters.
+    let length =
+        entities.add("(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.))");
+    let angle =
+        entities.add("(NAMED_UNIT(*) PLANE_ANGLE_UNIT() SI_UNIT($,.RADIAN.))");
+    let solid_angle = entities
+        .add("(NAMED_UNIT(*) SI_UNIT($,.STERADIAN.) SOLID_ANGLE_UNIT())");
+
+    // Vertices that are closer than this are considered identical by the
+    // kernel, so that's the accuracy that the geometry can be relied on to
+    // have.
+    let accuracy = ValidationConfig::default().distinct_min_distance;
+    let uncertainty = entities.add(format!(
+        "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{length},\
+        'distance_accuracy_value','confusion accuracy')",
+        real(accuracy)
+    ));
+
+    entities.add(format!(
+        "(GEOMETRIC_REPRESENTATION_CONTEXT(3) \
+        GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{uncertainty})) \
+        GLOBAL_UNIT_ASSIGNED_CONTEXT((#{length},#{angle},#{solid_angle})) \
+        REPRESENTATION_CONTEXT('','3D'))"
+    ))
+}
+
+/// Write the product that the shape representation belongs to
+fn write_product(entities: &mut Entities, name: &str, representation: usize) {
+    let application = entities.add("APPLICATION_CONTEXT('automotive design')");
+    entities.add(format!(
+        "APPLICATION_PROTOCOL_DEFINITION('international standard',\
+        'automotive_design',2000,#{application})"
+    ));
+    let product_context = entities
+        .add(format!("PRODUCT_CONTEXT('',#{application},'mechanical')"));
+    let product =
+        entities.add(format!("PRODUCT({name},{name},'',(#{product_context}))"));
+    entities.add(format!(
+        "PRODUCT_RELATED_PRODUCT_CATEGORY('part',$,(#{product}))"
+    ));
+    let formation =
+        entities.add(format!("PRODUCT_DEFINITION_FORMATION('','',#{product})"));
+    let definition_context = entities.add(format!(
+        "PRODUCT_DEFINITION_CONTEXT('part definition',#{application},\
+        'design')"
+    ));
+    let definition = entities.add(format!(
+        "PRODUCT_DEFINITION('design','',#{formation},#{definition_context})"
+    ));
+    let shape =
+        entities.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{definition})"));
+    entities.add(format!(
+        "SHAPE_DEFINITION_REPRESENTATION(#{shape},#{representation})"
+    ));
+}
+
+/// Writes the faces of a shell, sharing vertices and edges between them
+struct ShellWriter<'r> {
+    entities: &'r mut Entities,
+    vertices: BTreeMap<Point<3>, usize>,
+    edges: BTreeMap<EdgeKey, usize>,
+    uses: BTreeMap<EdgeKey, usize>,
+}
+
+impl<'r> ShellWriter<'r> {
+    fn new(entities: &'r mut Entities) -> Self {
+        Self {
+            entities,
+            vertices: BTreeMap::new(),
+            edges: BTreeMap::new(),
+            uses: BTreeMap::new(),
+        }
+    }
+
+    /// Write the shell
+    ///
+    /// Returns the ID of the shell, and whether it is closed.
+    fn write(mut self, shell: &Shell) -> (usize, bool) {
+        let mut advanced_faces = Vec::new();
+
+        for face in shell.faces() {
+            match face.triangles() {
+                Some(triangles) => {
+                    for (triangle, _) in triangles {
+                        advanced_faces.push(self.triangle(triangle));
+                    }
+                }
+                None => advanced_faces.push(self.face(face)),
+            }
+        }
+
+        // Every edge of a closed shell is shared by exactly two faces.
+        let closed =
+            !self.uses.is_empty() && self.uses.values().all(|&uses| uses == 2);
+
+        let shell = if closed { "CLOSED_SHELL" } else { "OPEN_SHELL" };
+        let shell = self
+            .entities
+            .add(format!("{shell}('',({}))", refs(&advanced_faces)));
+
+        (shell, closed)
+    }
+
+    fn face(&mut self, face: &Face) -> usize {
+        let (surface, same_sense) = self.surface(face.surface());
+
+        // The cycles of a face can be oriented either way. STEP expects outer
+        // bounds to be counter-clockwise with respect to the face normal, and
+        // inner bounds clockwise, so orient the bounds based on the area that
+        // each cycle encloses.
+        let mut bounds = Vec::new();
+        for (i, cycle) in face.exteriors().enumerate() {
+            let kind = if i == 0 {
+                "FACE_OUTER_BOUND"
+            } else {
+                "FACE_BOUND"
+            };
+            let orientation = signed_area(cycle) > Scalar::ZERO;
+            bounds.push(self.bound(kind, cycle, orientation));
+        }
+        for cycle in face.interiors() {
+            let orientation = signed_area(cycle) < Scalar::ZERO;
+            bounds.push(self.bound("FACE_BOUND", cycle, orientation));
+        }
+
+        self.entities.add(format!(
+            "ADVANCED_FACE('',({}),#{surface},{})",
+            refs(&bounds),
+            logical(same_sense)
+        ))
+    }
+
+    fn bound(&mut self, kind: &str, cycle: &Cycle, orientation: bool) -> usize {
+        let edges = cycle
+            .edges()
+            .map(|edge| self.oriented_edge(edge))
+            .collect::<Vec<_>>();
+        let edge_loop = self
+            .entities
+            .add(format!("EDGE_LOOP('',({}))", refs(&edges)));
+
+        self.entities
+            .add(format!("{kind}('',#{edge_loop},{})", logical(orientation)))
+    }
+
+    /// Write the surface of a face
+    ///
+    /// Returns the ID of the surface, and whether its normal points in the
+    /// same direction as the normal of the face.
+    fn surface(&mut self, surface: &Surface) -> (usize, bool) {
+        let Surface::SweptCurve(surface) = surface;
+
+        match surface.curve {
+            CurveKind::Line(line) => {
+                let normal = line.direction().cross(&surface.path);
+                let placement = self.entities.placement(
+                    line.origin(),
+                    normal,
+                    line.direction(),
+                );
+
+                (self.entities.add(format!("PLANE('',#{placement})")), true)
+            }
+            CurveKind::Circle(circle) => {
+                let axis = circle.a().cross(&circle.b());
+
+                // Paths along the axis of the circle result in a cylinder.
+                // Allow for some floating point inaccuracy when checking for
+                // that, as transformed models rarely line up exactly.
+                let sine = surface
+                    .path
+                    .normalize()
+                    .cross(&axis.normalize())
+                    .magnitude();
+
+                if sine < Scalar::from_f64(1e-9) {
+                    let placement = self.entities.placement(
+                        circle.center(),
+                        axis,
+                        circle.a(),
+                    );
+                    let cylinder = self.entities.add(format!(
+                        "CYLINDRICAL_SURFACE('',#{placement},{})",
+                        real(circle.a().magnitude())
+                    ));
+
+                    // The normal of a cylindrical surface always points
+                    // outward, while the normal of the face depends on the
+                    // direction of the circle relative to the path.
+                    (cylinder, surface.path.dot(&axis) > Scalar::ZERO)
+                } else {
+                    let circle = self.circle(&circle);
+                    let extrusion = self.entities.vector(surface.path);
+                    let surface = self.entities.add(format!(
+                        "SURFACE_OF_LINEAR_EXTRUSION('',#{circle},#{extrusion})"
+                    ));
+
+                    (surface, true)
+                }
+            }
+        }
+    }
+
+    fn oriented_edge(&mut self, edge: &Edge) -> usize {
+        let global = Adjacency::normalize_edge(edge.global());
+        let curve = *global.curve().kind();
+
+        // Vertices are defined in terms of the edge's own global curve.
+        // Neighboring faces might refer to that curve in opposite directions,
+        // so figure out how the edge runs along the normalized curve, which
+        // is shared between them.
+        let vertices = edge.vertices().get();
+        let forward = match (vertices, curve) {
+            (Some([a, b]), CurveKind::Line(_)) => a.global() < b.global(),
+            (Some([a, b]), CurveKind::Circle(_)) => {
+                (a.position() < b.position())
+                    == (edge.global().curve().kind() == &curve)
+            }
+            (None, _) => edge.global().curve().kind() == &curve,
+        };
+
+        let key = EdgeKey::Edge(global);
+        let edge_curve = match self.edges.get(&key) {
+            Some(&edge_curve) => edge_curve,
+            None => {
+                let [start, end] = match vertices {
+                    Some([a, b]) => {
+                        let [a, b] = [a, b].map(|v| v.global().position());
+                        if forward {
+                            [a, b]
+                        } else {
+                            [b, a]
+                        }
+                    }
+                    None => {
+                        let point = curve.point_from_curve_coords([0.]);
+                        [point, point]
+                    }
+                };
+
+                let geometry = match curve {
+                    CurveKind::Circle(circle) => self.circle(&circle),
+                    CurveKind::Line(line) => {
+                        self.line(line.origin(), line.direction())
+                    }
+                };
+
+                let edge_curve = self.edge_curve(start, end, geometry);
+                self.edges.insert(key, edge_curve);
+                edge_curve
+            }
+        };
+
+        *self.uses.entry(key).or_default() += 1;
+
+        self.entities.add(format!(
+            "ORIENTED_EDGE('',*,*,#{edge_curve},{})",
+            logical(forward)
+        ))
+    }
+
+    /// Write a triangle as a planar face
+    fn triangle(&mut self, triangle: &Triangle<3>) -> usize {
+        let [a, b, c] = triangle.points();
+        let normal = (b - a).cross(&(c - a));
+
+        let placement = self.entities.placement(a, normal, b - a);
+        let plane = self.entities.add(format!("PLANE('',#{placement})"));
+
+        let edges = [[a, b], [b, c], [c, a]].map(|[start, end]| {
+            let forward = start < end;
+            let segment = if forward { [start, end] } else { [end, start] };
+
+            let key = EdgeKey::Segment(segment);
+            let edge_curve = match self.edges.get(&key) {
+                Some(&edge_curve) => edge_curve,
+                None => {
+                    let [start, end] = segment;
+                    let line = self.line(start, end - start);
+                    let edge_curve = self.edge_curve(start, end, line);
+                    self.edges.insert(key, edge_curve);
+                    edge_curve
+                }
+            };
+
+            *self.uses.entry(key).or_default() += 1;
+
+            self.entities.add(format!(
+                "ORIENTED_EDGE('',*,*,#{edge_curve},{})",
+                logical(forward)
+            ))
+        });
+
+        let edge_loop = self
+            .entities
+            .add(format!("EDGE_LOOP('',({}))", refs(&edges)));
+        let bound = self
+            .entities
+            .add(format!("FACE_OUTER_BOUND('',#{edge_loop},.T.)"));
+
+        self.entities
+            .add(format!("ADVANCED_FACE('',(#{bound}),#{plane},.T.)"))
+    }
+
+    fn edge_curve(
+        &mut self,
+        start: Point<3>,
+        end: Point<3>,
+        geometry: usize,
+    ) -> usize {
+        let [start, end] = [start, end].map(|point| self.vertex(point));
+        self.entities
+            .add(format!("EDGE_CURVE('',#{start},#{end},#{geometry},.T.)"))
+    }
+
+    fn vertex(&mut self, point: Point<3>) -> usize {
+        if let Some(&vertex) = self.vertices.get(&point) {
+            return vertex;
+        }
+
+        let cartesian_point = self.entities.point(point);
+        let vertex = self
+            .entities
+            .add(format!("VERTEX_POINT('',#{cartesian_point})"));
+        self.vertices.insert(point, vertex);
+
+        vertex
+    }
+
+    fn line(&mut self, origin: Point<3>, direction: Vector<3>) -> usize {
+        let origin = self.entities.point(origin);
+        let direction = self.entities.vector(direction);
+        self.entities
+            .add(format!("LINE('',#{origin},#{direction})"))
+    }
+
+    fn circle(&mut self, circle: &Circle<3>) -> usize {
+        // STEP circles run counter-clockwise around their axis, starting at
+        // the reference direction. That's the same as Fornjot's circles, if
+        // the axis is chosen accordingly.
+        let axis = circle.a().cross(&circle.b());
+        let placement =
+            self.entities.placement(circle.center(), axis, circle.a());
+
+        self.entities.add(format!(
+            "CIRCLE('',#{placement},{})",
+            real(circle.a().magnitude())
+        ))
+    }
+}
+
+/// Identifies an edge within a shell
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum EdgeKey {
+    /// An edge of a face, in normalized form
+    Edge(GlobalEdge),
+
+    /// A segment of a triangle, from the smaller to the larger point
+    Segment([Point<3>; 2]),
+}
+
+/// The entities of the data section
+///
+/// Entities are numbered in the order they are added, starting at 1.
+#[derive(Default)]
+struct Entities {
+    entities: Vec<String>,
+}
+
+impl Entities {
+    fn add(&mut self, entity: impl Into<String>) -> usize {
+        self.entities.push(entity.into());
+        self.entities.len()
+    }
+
+    fn point(&mut self, point: Point<3>) -> usize {
+        let [x, y, z] = point.coords.components.map(real);
+        self.add(format!("CARTESIAN_POINT('',({x},{y},{z}))"))
+    }
+
+    fn direction(&mut self, direction: Vector<3>) -> usize {
+        let [x, y, z] = direction.normalize().components.map(real);
+        self.add(format!("DIRECTION('',({x},{y},{z}))"))
+    }
+
+    fn vector(&mut self, vector: Vector<3>) -> usize {
+        let direction = self.direction(vector);
+        self.add(format!(
+            "VECTOR('',#{direction},{})",
+            real(vector.magnitude())
+        ))
+    }
+
+    fn placement(
+        &mut self,
+        origin: Point<3>,
+        axis: Vector<3>,
+        ref_direction: Vector<3>,
+    ) -> usize {
+        let origin = self.point(origin);
+        let axis = self.direction(axis);
+        let ref_direction = self.direction(ref_direction);
+        self.add(format!(
+            "AXIS2_PLACEMENT_3D('',#{origin},#{axis},#{ref_direction})"
+        ))
+    }
+}
+
+/// Compute the signed area that a cycle encloses in surface coordinates
+///
+/// Circles are approximated by a polygon. That isn't accurate, but good enough
+/// to tell the orientation of the cycle.
+fn signed_area(cycle: &Cycle) -> Scalar {
+    let mut area = Scalar::ZERO;
+
+    for edge in cycle.edges() {
+        let range = match edge.vertices().get() {
+            Some([a, b]) => [a.position().t, b.position().t],
+            None => [Scalar::ZERO, Scalar::TAU],
+        };
+
+        let curve = edge.curve().kind();
+        let pieces: u64 = match curve {
+            CurveKind::Circle(_) => 16,
+            CurveKind::Line(_) => 1,
+        };
+
+        let points = (0..=pieces)
+            .map(|i| {
+                let t = range[0]
+                    + (range[1] - range[0]) * Scalar::from_u64(i)
+                        / Scalar::from_u64(pieces);
+                curve.point_from_curve_coords([t])
+            })
+            .collect::<Vec<_>>();
+
+        for segment in points.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            area += a.u * b.v - b.u * a.v;
+        }
+    }
+
+    area / 2.
+}
+
+/// Format a point in time as an ISO 8601 date and time, in UTC
+fn time_stamp(time: SystemTime) -> String {
+    const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let days = seconds / SECONDS_PER_DAY;
+    let seconds = seconds % SECONDS_PER_DAY;
+
+    // Convert the days since 1970-01-01 into a date of the proleptic Gregorian
+    // calendar. The calculation is based on eras of 400 years, which always
+    // have the same number of days, starting on March 1st. That puts leap days
+    // at the end of each year.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let (year, month) = if month < 10 {
+        (era * 400 + year_of_era, month + 3)
+    } else {
+        (era * 400 + year_of_era + 1, month - 9)
+    };
+
+    let hour = seconds / 3600;
+    let minute = seconds / 60 % 60;
+    let second = seconds % 60;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}+00:00"
+    )
+}
+
+fn refs(ids: &[usize]) -> String {
+    ids.iter()
+        .map(|id| format!("#{id}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn logical(value: bool) -> &'static str {
+    if value {
+        ".T."
+    } else {
+        ".F."
+    }
+}
+
+fn real(value: Scalar) -> String {
+    // STEP requires a decimal point in every real, and an upper-case exponent.
+    let value = format!("{:?}", value.into_f64());
+
+    match value.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') => {
+            format!("{mantissa}E{exponent}")
+        }
+        Some((mantissa, exponent)) => format!("{mantissa}.E{exponent}"),
+        None => value,
+    }
+}
+
+/// Encode text as a string literal
+///
+/// Apostrophes and backslashes are escaped, and anything that isn't printable
+/// ASCII is encoded as UTF-16.
+fn string(text: &str) -> String {
+    let mut encoded = String::from("'");
+
+    for c in text.chars() {
+        match c {
+            '\'' => encoded.push_str("''"),
+            '\\' => encoded.push_str("\\\\"),
+            ' '..='~' => encoded.push(c),
+            c => {
+                encoded.push_str("\\X2\\");
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    encoded.push_str(&format!("{unit:04X}"));
+                }
+                encoded.push_str("\\X0\\");
+            }
+        }
+    }
+
+    encoded.push('\'');
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use std::{
+        collections::BTreeMap,
+        time::{Duration, SystemTime},
+    };
+
+    use fj_kernel::{
+        algorithms::{
+            blend::{blend_edges, BlendKind},
+            reverse::Reverse,
+        },
+        iter::ObjectIters,
+        objects::{
+            CurveKind, Cycle, Edge, Face, GlobalVertex, Shell, Solid, Surface,
+            SweptCurve,
+        },
+    };
+    use fj_math::{Circle, Scalar, Triangle};
+
+    use crate::Metadata;
+
+    use super::{real, string, time_stamp, write_step};
+
+    #[test]
+    fn cube() -> anyhow::Result<()> {
+        let shell = Shell::build().cube_from_edge_length(2.);
+
+        let metadata = Metadata {
+            name: String::from("Cube's"),
+            description: None,
+            license: None,
+        };
+
+        let mut step = Vec::new();
+        write_step(
+            &[&shell],
+            Some(&metadata),
+            "2022-09-01T12:00:00+00:00",
+            &mut step,
+        )?;
+        let step = String::from_utf8(step)?;
+
+        let count = |entity: &str| step.matches(&format!("={entity}(")).count();
+
+        assert_eq!(count("ADVANCED_FACE"), 6);
+        assert_eq!(count("PLANE"), 6);
+        assert_eq!(count("EDGE_CURVE"), 12);
+        assert_eq!(count("VERTEX_POINT"), 8);
+        assert_eq!(count("ORIENTED_EDGE"), 24);
+        assert_eq!(count("CLOSED_SHELL"), 1);
+        assert_eq!(count("MANIFOLD_SOLID_BREP"), 1);
+        assert_eq!(count("ADVANCED_BREP_SHAPE_REPRESENTATION"), 1);
+        assert!(step.contains("PRODUCT('Cube''s','Cube''s'"));
+        assert!(
+            step.contains("FILE_NAME('Cube''s','2022-09-01T12:00:00+00:00',")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn triangles() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let color = Color([255, 0, 0, 255]);
+
+        let tetrahedron = [[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+            .map(|points| (Triangle::from(points), color));
+        let shell = Shell::new()
+            .with_faces([Face::from_triangles(tetrahedron.to_vec())]);
+
+        let mut step = Vec::new();
+        write_step(&[&shell], None, "", &mut step)?;
+        let step = String::from_utf8(step)?;
+
+        let count = |entity: &str| step.matches(&format!("={entity}(")).count();
+
+        assert_eq!(count("ADVANCED_FACE"), 4);
+        assert_eq!(count("EDGE_CURVE"), 6);
+        assert_eq!(count("VERTEX_POINT"), 4);
+        assert_eq!(count("CLOSED_SHELL"), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fillet() -> anyhow::Result<()> {
+        let solid = Solid::build().cube_from_edge_length(2.);
+        let vertices =
+            [[-1., 1., 1.], [1., 1., 1.]].map(GlobalVertex::from_position);
+        let edge = solid
+            .edge_iter()
+            .map(|edge| *edge.global())
+            .find(|edge| {
+                let [a, b] = edge.vertices().get_or_panic().map(|v| *v);
+                [a, b] == vertices || [b, a] == vertices
+            })
+            .expect("Edge not found");
+        let solid = blend_edges(
+            &solid,
+            [edge],
+            BlendKind::Fillet {
+                radius: Scalar::from(0.5),
+            },
+        )?;
+
+        let mut step = Vec::new();
+        write_step(&solid.shells().collect::<Vec<_>>(), None, "", &mut step)?;
+        let step = String::from_utf8(step)?;
+
+        let count = |entity: &str| step.matches(&format!("={entity}(")).count();
+
+        assert_eq!(count("ADVANCED_FACE"), 7);
+        assert_eq!(count("PLANE"), 6);
+        assert_eq!(count("CYLINDRICAL_SURFACE"), 1);
+        assert_eq!(count("CIRCLE"), 2);
+        assert_eq!(count("EDGE_CURVE"), 15);
+        assert_eq!(count("VERTEX_POINT"), 10);
+        assert_eq!(count("CLOSED_SHELL"), 1);
+        assert_eq!(count("MANIFOLD_SOLID_BREP"), 1);
+
+        // The fillet's surface normal points away from the axis, like that of
+        // a cylindrical surface, and its boundary is counter-clockwise.
+        let entities = entities(&step);
+        let cylinder = find(&entities, "CYLINDRICAL_SURFACE(", "");
+        let face = find(&entities, "ADVANCED_FACE(", &format!(",#{cylinder},"));
+        assert!(entities[&face].ends_with(",.T.)"));
+
+        let bound = bounds(&entities, face)[0];
+        assert!(entities[&bound].starts_with("FACE_OUTER_BOUND("));
+        assert!(entities[&bound].ends_with(",.T.)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plate_with_hole() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let exterior = Cycle::build(surface).polygon_from_points([
+            [-2., -2.],
+            [2., -2.],
+            [2., 2.],
+            [-2., 2.],
+        ]);
+        let interior = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+        );
+        let plate = Face::new(surface)
+            .with_exteriors([exterior])
+            .with_interiors([interior]);
+
+        // Reversing the plate mirrors its cycles in surface coordinates, which
+        // must flip the orientation of its bounds. The edges themselves stay
+        // the same.
+        let mut senses = Vec::new();
+        for (face, orientations) in [
+            (plate.clone(), [".T.", ".F."]),
+            (plate.reverse(), [".F.", ".T."]),
+        ] {
+            let shell = Shell::new().with_faces([face]);
+
+            let mut step = Vec::new();
+            write_step(&[&shell], None, "", &mut step)?;
+            let step = String::from_utf8(step)?;
+
+            let count =
+                |entity: &str| step.matches(&format!("={entity}(")).count();
+
+            assert_eq!(count("ADVANCED_FACE"), 1);
+            assert_eq!(count("FACE_OUTER_BOUND"), 1);
+            assert_eq!(count("FACE_BOUND"), 1);
+            assert_eq!(count("CIRCLE"), 1);
+            assert_eq!(count("EDGE_CURVE"), 5);
+            assert_eq!(count("OPEN_SHELL"), 1);
+            assert_eq!(count("SHELL_BASED_SURFACE_MODEL"), 1);
+
+            let entities = entities(&step);
+            let face = find(&entities, "ADVANCED_FACE(", "");
+            for (bound, orientation) in
+                bounds(&entities, face).into_iter().zip(orientations)
+            {
+                let orientation = format!(",{orientation})");
+                assert!(entities[&bound].ends_with(&orientation));
+            }
+
+            let circle = find(&entities, "CIRCLE(", "");
+            let edge_curve =
+                find(&entities, "EDGE_CURVE(", &format!(",#{circle},"));
+            let oriented_edge =
+                find(&entities, "ORIENTED_EDGE(", &format!(",#{edge_curve},"));
+            senses.push(entities[&oriented_edge].ends_with(",.T.)"));
+        }
+        assert_eq!(senses[0], senses[1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn swept_circles() -> anyhow::Result<()> {
+        let circle = CurveKind::Circle(Circle::new(
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [0., 1., 0.],
+        ));
+
+        for (path, entity, circles, same_sense) in [
+            ([0., 0., -1.], "CYLINDRICAL_SURFACE(", 0, ".F."),
+            ([1., 0., 1.], "SURFACE_OF_LINEAR_EXTRUSION(", 1, ".T."),
+        ] {
+            let surface = Surface::SweptCurve(SweptCurve {
+                curve: circle,
+                path: path.into(),
+            });
+            let face = Face::build(surface)
+                .polygon_from_points([[0., 0.], [1., 0.], [1., 1.], [0., 1.]])
+                .into_face();
+            let shell = Shell::new().with_faces([face]);
+
+            let mut step = Vec::new();
+            write_step(&[&shell], None, "", &mut step)?;
+            let step = String::from_utf8(step)?;
+
+            assert_eq!(step.matches("=CIRCLE(").count(), circles);
+
+            let entities = entities(&step);
+            let surface = find(&entities, entity, "");
+            let face =
+                find(&entities, "ADVANCED_FACE(", &format!(",#{surface},"));
+            assert!(entities[&face].ends_with(&format!(",{same_sense})")));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(real(1.0.into()), "1.0");
+        assert_eq!(real(1e-7.into()), "1.E-7");
+        assert_eq!(real((-2.5e21).into()), "-2.5E21");
+
+        assert_eq!(string("Fornjöt's"), "'Fornj\\X2\\00F6\\X0\\t''s'");
+    }
+
+    #[test]
+    fn time_stamps() {
+        let time =
+            |seconds| SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+
+        assert_eq!(time_stamp(time(0)), "1970-01-01T00:00:00+00:00");
+        assert_eq!(
+            time_stamp(time(1_000_000_000)),
+            "2001-09-09T01:46:40+00:00"
+        );
+        assert_eq!(
+            time_stamp(time(1_709_164_800)),
+            "2024-02-29T00:00:00+00:00"
+        );
+        assert_eq!(
+            time_stamp(time(1_709_251_199)),
+            "2024-02-29T23:59:59+00:00"
+        );
+    }
+
+    /// Parse the entities of the data section, by their ID
+    fn entities(step: &str) -> BTreeMap<usize, &str> {
+        step.lines()
+            .filter_map(|line| {
+                let (id, entity) = line.strip_prefix('#')?.split_once('=')?;
+                Some((id.parse::<usize>().ok()?, entity.strip_suffix(';')?))
+            })
+            .collect()
+    }
+
+    /// Find the ID of the only entity with the given prefix and content
+    fn find(
+        entities: &BTreeMap<usize, &str>,
+        prefix: &str,
+        content: &str,
+    ) -> usize {
+        let mut ids = entities
+            .iter()
+            .filter(|(_, entity)| {
+                entity.starts_with(prefix) && entity.contains(content)
+            })
+            .map(|(&id, _)| id);
+
+        let id = ids.next().expect("Entity not found");
+        assert!(ids.next().is_none(), "Entity is ambiguous");
+        id
+    }
+
+    /// Access the IDs of the bounds of a face
+    fn bounds(entities: &BTreeMap<usize, &str>, face: usize) -> Vec<usize> {
+        let bounds = entities[&face]
+            .strip_prefix("ADVANCED_FACE('',(")
+            .and_then(|rest| rest.split_once(')'))
+            .map(|(bounds, _)| bounds)
+            .expect("Unexpected face");
+
+        bounds
+            .split(',')
+            .map(|id| id.trim_start_matches('#').parse().expect("Invalid ID"))
+            .collect()
+    }
+}
@@ -0,0 +1,291 @@
+//! Export to the 3D Manufacturing Format (3MF)
+//!
+//! The colors of the mesh are written as base materials, which most slicers
+//! understand. Each part of the model becomes a separate object.
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, Write},
+    ops::Range,
+    path::Path,
+};
+
+use fj_interop::mesh::{Color, Mesh, Triangle};
+use fj_math::{Point, Scalar};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{Error, ExportOptions, Metadata};
+
+/// The unit of the model
+///
+/// Fornjot doesn't define units, but models are conventionally designed in
+/// millimeters. 3MF defaults to millimeters too, but stating it explicitly
+/// leaves no room for interpretation.
+const UNIT: &str = "millimeter";
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+pub fn export(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let file_options =
+        FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("[Content_Types].xml", file_options)?;
+    zip.write_all(CONTENT_TYPES.as_bytes())?;
+
+    zip.start_file("_rels/.rels", file_options)?;
+    zip.write_all(RELATIONSHIPS.as_bytes())?;
+
+    zip.start_file("3D/3dmodel.model", file_options)?;
+    write_model(mesh, &options.parts, options.metadata.as_ref(), &mut zip)?;
+
+    zip.finish()?;
+
+    Ok(())
+}
+
+fn write_model(
+    mesh: &Mesh<Point<3>>,
+    parts: &[Range<usize>],
+    metadata: Option<&Metadata>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<model unit="{UNIT}" xml:lang="en-US" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">"#
+    )?;
+
+    writeln!(
+        writer,
+        r#"  <metadata name="Application">Fornjot</metadata>"#
+    )?;
+    if let Some(metadata) = metadata {
+        let entries = [
+            ("Title", Some(&metadata.name)),
+            ("Description", metadata.description.as_ref()),
+            ("LicenseTerms", metadata.license.as_ref()),
+        ];
+
+        for (name, value) in entries {
+            if let Some(value) = value {
+                writeln!(
+                    writer,
+                    r#"  <metadata name="{name}">{}</metadata>"#,
+                    escape(value)
+                )?;
+            }
+        }
+    }
+
+    writeln!(writer, "  <resources>")?;
+
+    let mut colors = mesh
+        .triangles()
+        .map(|triangle| triangle.color)
+        .collect::<Vec<_>>();
+    colors.sort();
+    colors.dedup();
+
+    // Resource IDs start at 1. The base materials come first, followed by the
+    // objects.
+    let materials_id = 1;
+
+    writeln!(writer, r#"    <basematerials id="{materials_id}">"#)?;
+    for &Color([r, g, b, a]) in &colors {
+        writeln!(
+            writer,
+            r##"      <base name="Color #{r:02X}{g:02X}{b:02X}{a:02X}" displaycolor="#{r:02X}{g:02X}{b:02X}{a:02X}"/>"##
+        )?;
+    }
+    writeln!(writer, "    </basematerials>")?;
+
+    let objects = split_into_objects(mesh, parts);
+
+    for (i, triangles) in objects.iter().enumerate() {
+        let object_id = materials_id + 1 + i;
+        let material_index = |color| {
+            colors
+                .binary_search(&color)
+                .expect("Colors contain all colors of the mesh")
+        };
+
+        // Every object must have at least one triangle, so there is a first
+        // one, for the default material.
+        let default_material = material_index(triangles[0].color);
+
+        writeln!(
+            writer,
+            r#"    <object id="{object_id}" type="model" name="Part {}" pid="{materials_id}" pindex="{default_material}">"#,
+            i + 1
+        )?;
+        writeln!(writer, "      <mesh>")?;
+
+        let mut vertices = BTreeMap::new();
+        let mut vertex_list = Vec::new();
+        let triangle_indices = triangles
+            .iter()
+            .map(|triangle| {
+                triangle.inner.points().map(|point| {
+                    *vertices.entry(point).or_insert_with(|| {
+                        vertex_list.push(point);
+                        vertex_list.len() - 1
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(writer, "        <vertices>")?;
+        for vertex in vertex_list {
+            let [x, y, z] = vertex.coords.components.map(Scalar::into_f64);
+            writeln!(writer, r#"          <vertex x="{x}" y="{y}" z="{z}"/>"#)?;
+        }
+        writeln!(writer, "        </vertices>")?;
+
+        writeln!(writer, "        <triangles>")?;
+        for (triangle, [v1, v2, v3]) in triangles.iter().zip(triangle_indices) {
+            let p1 = material_index(triangle.color);
+            writeln!(
+                writer,
+                r#"          <triangle v1="{v1}" v2="{v2}" v3="{v3}" pid="{materials_id}" p1="{p1}"/>"#
+            )?;
+        }
+        writeln!(writer, "        </triangles>")?;
+
+        writeln!(writer, "      </mesh>")?;
+        writeln!(writer, "    </object>")?;
+    }
+
+    writeln!(writer, "  </resources>")?;
+
+    writeln!(writer, "  <build>")?;
+    for i in 0..objects.len() {
+        writeln!(writer, r#"    <item objectid="{}"/>"#, materials_id + 1 + i)?;
+    }
+    writeln!(writer, "  </build>")?;
+
+    writeln!(writer, "</model>")?;
+
+    Ok(())
+}
+
+/// Split the triangles of the mesh into one list per object
+///
+/// Triangles that don't belong to any of the parts are collected into an
+/// object of their own. Empty objects are omitted.
+fn split_into_objects(
+    mesh: &Mesh<Point<3>>,
+    parts: &[Range<usize>],
+) -> Vec<Vec<Triangle>> {
+    let mut objects = vec![Vec::new(); parts.len() + 1];
+
+    for triangle in mesh.triangles() {
+        let part = triangle.face.and_then(|face| {
+            parts.iter().position(|faces| faces.contains(&face.0))
+        });
+
+        objects[part.unwrap_or(parts.len())].push(triangle);
+    }
+
+    objects.retain(|triangles| !triangles.is_empty());
+    objects
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, FaceId, Mesh, Triangle};
+    use fj_math::{Point, Vector};
+
+    use crate::Metadata;
+
+    use super::write_model;
+
+    #[test]
+    fn objects_colors_and_metadata() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+        let offset = Vector::from([2., 0., 0.]);
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 255]);
+
+        let mut mesh = Mesh::new();
+        for (i, points) in [[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+            .into_iter()
+            .enumerate()
+        {
+            mesh.push(Triangle::new(points, red).with_face(FaceId(i)));
+        }
+        for (i, points) in [[a, c, b], [a, b, d], [b, c, d], [c, a, d]]
+            .into_iter()
+            .enumerate()
+        {
+            let points = points.map(|point| point + offset);
+            let color = if i == 0 { red } else { blue };
+            mesh.push(Triangle::new(points, color).with_face(FaceId(4 + i)));
+        }
+
+        let metadata = Metadata {
+            name: String::from("Spanner & Nut"),
+            description: None,
+            license: Some(String::from("0BSD")),
+        };
+
+        let mut model = Vec::new();
+        write_model(&mesh, &[0..4, 4..8], Some(&metadata), &mut model)?;
+        let model = String::from_utf8(model)?;
+
+        let count = |pattern: &str| model.matches(pattern).count();
+
+        assert_eq!(count("<object "), 2);
+        assert_eq!(count("<item "), 2);
+        assert_eq!(count("<vertex "), 8);
+        assert_eq!(count("<base "), 2);
+        // Colors are sorted, so blue comes before red.
+        assert_eq!(count(r#"p1="0""#), 3);
+        assert_eq!(count(r#"p1="1""#), 5);
+
+        assert!(model.contains(r#"unit="millimeter""#));
+        assert!(model.contains(
+            r#"<metadata name="Title">Spanner &amp; Nut</metadata>"#
+        ));
+        assert!(
+            model.contains(r#"<metadata name="LicenseTerms">0BSD</metadata>"#)
+        );
+
+        Ok(())
+    }
+}
@@ -85,6 +85,19 @@ impl Model {
         arguments: &Parameters,
         status: &mut StatusReport,
     ) -> Result<fj::Shape, Error> {
+        self.load_once_with_metadata(arguments, status)
+            .map(|(shape, _)| shape)
+    }
+
+    /// Load the model once, including the metadata of the model's package
+    ///
+    /// Works like [`Model::load_once`], but additionally returns the metadata
+    /// that the model provided when it was initialized.
+    pub fn load_once_with_metadata(
+        &self,
+        arguments: &Parameters,
+        status: &mut StatusReport,
+    ) -> Result<(fj::Shape, fj::models::Metadata), Error> {
         let manifest_path = self.manifest_path.display().to_string();
 
         let mut command_root = Command::new("cargo");
@@ -140,7 +153,7 @@ impl Model {
         // I don't know of a way to fix this. We should take this as motivation
         // to switch to a better technique:
         // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
+        let (shape, metadata) = unsafe {
             let lib = libloading::Library::new(&self.lib_path)?;
             let init: libloading::Symbol<abi::InitFunction> =
                 lib.get(abi::INIT_FUNCTION_NAME.as_bytes())?;
@@ -150,19 +163,19 @@ impl Model {
                 model: None,
             };
 
-            match init(&mut abi::Host::from(&mut host)) {
-                abi::ffi_safe::Result::Ok(_metadata) => {}
+            let metadata = match init(&mut abi::Host::from(&mut host)) {
+                abi::ffi_safe::Result::Ok(metadata) => metadata.into(),
                 abi::ffi_safe::Result::Err(e) => {
                     return Err(Error::InitializeModel(e.into()));
                 }
-            }
+            };
 
             let model = host.model.take().ok_or(Error::NoModelRegistered)?;
 
-            model.shape(&host).map_err(Error::Shape)?
+            (model.shape(&host).map_err(Error::Shape)?, metadata)
         };
 
-        Ok(shape)
+        Ok((shape, metadata))
     }
 
     /// Load the model, then watch it for changes
@@ -0,0 +1,376 @@
+//! Analysis of triangle meshes
+//!
+//! See [`analyze`].
+
+use std::collections::{BTreeMap, VecDeque};
+
+use fj_math::{Point, Scalar, Triangle};
+
+use crate::mesh::{Index, Mesh};
+
+/// Analyze whether a triangle mesh is a closed, consistently oriented manifold
+///
+/// Only a mesh that is closed, oriented, and free of self-intersections can be
+/// considered a valid solid, which is what slicers for 3D printing expect.
+///
+/// Edges are identified by the indices of their vertices, so vertices on
+/// shared edges must be identical. Weld the mesh first, using
+/// [`Mesh::weld`], if that is not the case.
+pub fn analyze(mesh: &Mesh<Point<3>>) -> MeshAnalysis {
+    let vertices = mesh.vertices().collect::<Vec<_>>();
+    let indices = mesh.indices().collect::<Vec<_>>();
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect::<Vec<_>>();
+
+    let points_of =
+        |triangle: [Index; 3]| triangle.map(|index| vertices[index as usize]);
+
+    // For each undirected edge, the triangles it belongs to, and whether they
+    // traverse it from the lower to the higher index.
+    let mut edges: BTreeMap<[Index; 2], Vec<(usize, bool)>> = BTreeMap::new();
+    for (i, &[a, b, c]) in triangles.iter().enumerate() {
+        for [from, to] in [[a, b], [b, c], [c, a]] {
+            let edge = if from < to { [from, to] } else { [to, from] };
+            edges.entry(edge).or_default().push((i, from < to));
+        }
+    }
+
+    let mut analysis = MeshAnalysis::default();
+
+    // The neighbors of each triangle that share an edge with it, and whether
+    // they're oriented consistently with it.
+    let mut neighbors = vec![Vec::new(); triangles.len()];
+
+    for (&[a, b], users) in &edges {
+        let points = [a, b].map(|index| vertices[index as usize]);
+
+        match users.as_slice() {
+            [_] => analysis.boundary_edges.push(points),
+            &[(t0, forward0), (t1, forward1)] => {
+                // Triangles that are oriented consistently traverse their
+                // shared edge in opposite directions.
+                let consistent = forward0 != forward1;
+
+                neighbors[t0].push((t1, consistent));
+                neighbors[t1].push((t0, consistent));
+            }
+            _ => {
+                analysis.non_manifold_edges.push(points);
+
+                for &(t0, _) in users {
+                    for &(t1, _) in users {
+                        if t0 != t1 {
+                            neighbors[t0].push((t1, true));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Find the connected components, and the orientation of each triangle
+    // relative to the first triangle of its component.
+    let mut component_of = vec![None; triangles.len()];
+    let mut flipped = vec![false; triangles.len()];
+
+    for start in 0..triangles.len() {
+        if component_of[start].is_some() {
+            continue;
+        }
+
+        let component = analysis.num_components;
+        analysis.num_components += 1;
+
+        let mut members = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        component_of[start] = Some(component);
+
+        while let Some(triangle) = queue.pop_front() {
+            members.push(triangle);
+
+            for &(neighbor, consistent) in &neighbors[triangle] {
+                if component_of[neighbor].is_none() {
+                    component_of[neighbor] = Some(component);
+                    flipped[neighbor] = if consistent {
+                        flipped[triangle]
+                    } else {
+                        !flipped[triangle]
+                    };
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        // There's no way to tell which orientation is the right one, just by
+        // looking at the connectivity. Assume it's that of the majority.
+        let num_flipped = members.iter().filter(|&&t| flipped[t]).count();
+        let minority = num_flipped * 2 <= members.len();
+
+        for &triangle in &members {
+            if flipped[triangle] == minority {
+                let points = points_of(triangles[triangle]);
+                if let Ok(triangle) = Triangle::from_points(points) {
+                    analysis.flipped_triangles.push(triangle);
+                }
+            }
+        }
+    }
+
+    analysis.intersecting_triangles = find_intersections(&triangles, points_of);
+
+    analysis
+}
+
+/// The result of [`analyze`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshAnalysis {
+    /// Edges that belong to only one triangle
+    ///
+    /// These edges border on holes in the mesh.
+    pub boundary_edges: Vec<[Point<3>; 2]>,
+
+    /// Edges that belong to more than two triangles
+    pub non_manifold_edges: Vec<[Point<3>; 2]>,
+
+    /// Triangles whose winding is inconsistent with their neighbors
+    ///
+    /// The orientation of most of the triangles in a connected component is
+    /// considered to be the correct one.
+    pub flipped_triangles: Vec<Triangle<3>>,
+
+    /// Pairs of triangles that intersect each other
+    ///
+    /// Triangles that share a vertex are not checked against each other.
+    /// Neither are triangles that are coplanar.
+    pub intersecting_triangles: Vec<[Triangle<3>; 2]>,
+
+    /// The number of connected components
+    ///
+    /// Triangles are connected, if they share an edge.
+    pub num_components: usize,
+}
+
+impl MeshAnalysis {
+    /// Indicate whether the mesh is closed and manifold
+    pub fn is_closed_manifold(&self) -> bool {
+        self.boundary_edges.is_empty() && self.non_manifold_edges.is_empty()
+    }
+
+    /// Indicate whether the mesh is free of all detected problems
+    pub fn is_valid(&self) -> bool {
+        self.is_closed_manifold()
+            && self.flipped_triangles.is_empty()
+            && self.intersecting_triangles.is_empty()
+    }
+}
+
+fn find_intersections(
+    triangles: &[[Index; 3]],
+    points_of: impl Fn([Index; 3]) -> [Point<3>; 3],
+) -> Vec<[Triangle<3>; 2]> {
+    let bounds = triangles
+        .iter()
+        .map(|&triangle| {
+            let points = points_of(triangle);
+
+            let mut min = points[0].coords.components;
+            let mut max = points[0].coords.components;
+            for point in points {
+                for (i, &coord) in point.coords.components.iter().enumerate() {
+                    min[i] = min[i].min(coord);
+                    max[i] = max[i].max(coord);
+                }
+            }
+
+            (min, max)
+        })
+        .collect::<Vec<_>>();
+
+    // Sweep along the x axis, to only check triangles whose bounding boxes
+    // overlap.
+    let mut order = (0..triangles.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&t| bounds[t].0[0]);
+
+    let mut intersections = Vec::new();
+
+    for (i, &t0) in order.iter().enumerate() {
+        for &t1 in &order[i + 1..] {
+            if bounds[t1].0[0] > bounds[t0].1[0] {
+                break;
+            }
+
+            let overlap = (1..3).all(|axis| {
+                bounds[t1].0[axis] <= bounds[t0].1[axis]
+                    && bounds[t0].0[axis] <= bounds[t1].1[axis]
+            });
+            if !overlap {
+                continue;
+            }
+
+            let shares_vertex = triangles[t0]
+                .iter()
+                .any(|index| triangles[t1].contains(index));
+            if shares_vertex {
+                continue;
+            }
+
+            let a = points_of(triangles[t0]);
+            let b = points_of(triangles[t1]);
+
+            if triangles_intersect(a, b) {
+                if let (Ok(a), Ok(b)) =
+                    (Triangle::from_points(a), Triangle::from_points(b))
+                {
+                    intersections.push([a, b]);
+                }
+            }
+        }
+    }
+
+    intersections
+}
+
+fn triangles_intersect(a: [Point<3>; 3], b: [Point<3>; 3]) -> bool {
+    // If two triangles that aren't coplanar intersect, an edge of one of them
+    // must cross the other.
+    let edges_cross = |[p0, p1, p2]: [Point<3>; 3], triangle| {
+        [[p0, p1], [p1, p2], [p2, p0]]
+            .into_iter()
+            .any(|segment| segment_crosses_triangle(segment, triangle))
+    };
+
+    edges_cross(a, b) || edges_cross(b, a)
+}
+
+fn segment_crosses_triangle(
+    [p, q]: [Point<3>; 2],
+    [a, b, c]: [Point<3>; 3],
+) -> bool {
+    let direction = q - p;
+    let e1 = b - a;
+    let e2 = c - a;
+
+    let h = direction.cross(&e2);
+    let det = e1.dot(&h);
+    if det == Scalar::ZERO {
+        // The segment is parallel to the triangle.
+        return false;
+    }
+
+    let s = p - a;
+    let u = s.dot(&h) / det;
+    if u <= Scalar::ZERO || u >= Scalar::ONE {
+        return false;
+    }
+
+    let r = s.cross(&e1);
+    let v = direction.dot(&r) / det;
+    if v <= Scalar::ZERO || u + v >= Scalar::ONE {
+        return false;
+    }
+
+    let t = e2.dot(&r) / det;
+    t > Scalar::ZERO && t < Scalar::ONE
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::mesh::{Color, Mesh};
+
+    use super::analyze;
+
+    #[test]
+    fn closed_mesh() {
+        let mesh = tetrahedron();
+        let analysis = analyze(&mesh);
+
+        assert!(analysis.is_valid());
+        assert_eq!(analysis.num_components, 1);
+    }
+
+    #[test]
+    fn open_mesh() {
+        let [a, b, c, _] = tetrahedron_points();
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], Color::default());
+
+        let analysis = analyze(&mesh);
+
+        assert!(!analysis.is_closed_manifold());
+        assert_eq!(analysis.boundary_edges.len(), 3);
+        assert!(analysis.non_manifold_edges.is_empty());
+    }
+
+    #[test]
+    fn flipped_triangle() {
+        let [a, b, c, d] = tetrahedron_points();
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, c, b], Color::default());
+        mesh.push_triangle([a, b, d], Color::default());
+        mesh.push_triangle([b, c, d], Color::default());
+        mesh.push_triangle([c, d, a], Color::default());
+
+        let analysis = analyze(&mesh);
+
+        assert!(analysis.is_closed_manifold());
+        assert_eq!(analysis.flipped_triangles.len(), 1);
+        assert!(!analysis.is_valid());
+    }
+
+    #[test]
+    fn non_manifold_edge() {
+        let [a, b, c, d] = tetrahedron_points();
+        let e = Point::from([0., -1., 0.]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([b, a, d], Color::default());
+        mesh.push_triangle([b, a, e], Color::default());
+
+        let analysis = analyze(&mesh);
+
+        assert_eq!(analysis.non_manifold_edges, [[a, b]]);
+    }
+
+    #[test]
+    fn intersecting_components() {
+        let mut mesh = tetrahedron();
+
+        let offset = fj_math::Vector::from([0.25, 0.25, 0.25]);
+        let [a, b, c, d] = tetrahedron_points().map(|point| point + offset);
+        mesh.push_triangle([a, c, b], Color::default());
+        mesh.push_triangle([a, b, d], Color::default());
+        mesh.push_triangle([b, c, d], Color::default());
+        mesh.push_triangle([c, a, d], Color::default());
+
+        let analysis = analyze(&mesh);
+
+        assert!(analysis.is_closed_manifold());
+        assert!(analysis.flipped_triangles.is_empty());
+        assert_eq!(analysis.num_components, 2);
+        assert!(!analysis.intersecting_triangles.is_empty());
+    }
+
+    fn tetrahedron() -> Mesh<Point<3>> {
+        let [a, b, c, d] = tetrahedron_points();
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, c, b], Color::default());
+        mesh.push_triangle([a, b, d], Color::default());
+        mesh.push_triangle([b, c, d], Color::default());
+        mesh.push_triangle([c, a, d], Color::default());
+
+        mesh
+    }
+
+    fn tetrahedron_points() -> [Point<3>; 4] {
+        [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+            .map(Point::from)
+    }
+}
@@ -4,13 +4,16 @@
 //! ecosystem. The types in here aren't very useful in themselves, but they
 //! define an interface that other crates use to communicate between each other.
 
-use fj_math::{Point, Segment};
+use fj_math::{Point, Scalar, Segment};
 
 /// Debug info from the CAD kernel that can be visualized
 #[derive(Default)]
 pub struct DebugInfo {
     /// Rays being used during face triangulation
     pub triangle_edge_checks: Vec<TriangleEdgeCheck>,
+
+    /// Points where the mesh deviates the most from the faces it approximates
+    pub deviation_checks: Vec<DeviationCheck>,
 }
 
 impl DebugInfo {
@@ -26,6 +29,17 @@ impl DebugInfo {
     /// allocations.
     pub fn clear(&mut self) {
         self.triangle_edge_checks.clear();
+        self.deviation_checks.clear();
+    }
+
+    /// Move all information from another instance into this one
+    ///
+    /// Used to combine the debug info of operations that ran independently,
+    /// for example on different threads.
+    pub fn append(&mut self, mut other: Self) {
+        self.triangle_edge_checks
+            .append(&mut other.triangle_edge_checks);
+        self.deviation_checks.append(&mut other.deviation_checks);
     }
 }
 
@@ -47,3 +61,22 @@ impl TriangleEdgeCheck {
         }
     }
 }
+
+/// Record of the point where a face's triangles deviate the most from it
+pub struct DeviationCheck {
+    /// The point on the triangle mesh
+    pub point: Point<3>,
+
+    /// The distance between the point and the face
+    pub deviation: Scalar,
+
+    /// The tolerance that the face was approximated with
+    pub tolerance: Scalar,
+}
+
+impl DeviationCheck {
+    /// Indicate whether the deviation exceeds the tolerance
+    pub fn exceeds_tolerance(&self) -> bool {
+        self.deviation > self.tolerance
+    }
+}
@@ -0,0 +1,583 @@
+//! Simplification of triangle meshes
+//!
+//! See [`decimate`].
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet},
+    ops::Add,
+};
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::mesh::{Color, FaceId, Mesh, Triangle};
+
+/// The targets of a decimation
+///
+/// If neither target is set, the mesh is not simplified at all. If both are
+/// set, the simplification stops as soon as one of them is reached.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Decimation {
+    /// The number of triangles to reduce the mesh to
+    pub max_triangles: Option<usize>,
+
+    /// The maximum error that a single simplification step may introduce
+    ///
+    /// The error is estimated as the distance of the moved vertex from the
+    /// planes of the original triangles around it.
+    pub max_error: Option<Scalar>,
+}
+
+/// Simplify a triangle mesh, by collapsing its edges
+///
+/// Uses quadric error metrics to decide which edges to collapse first. Edges
+/// are collapsed into one of their vertices, so the simplified mesh only
+/// contains vertices of the original mesh.
+///
+/// Sharp edges of the original shape are preserved. Triangles that were
+/// created from different faces, or have different colors, are never merged.
+/// Vertices on the boundaries between them can only move along those
+/// boundaries, and vertices where more than two boundaries meet don't move at
+/// all. The same applies to holes in the mesh.
+///
+/// This is meant for previews and visualization. The result is not accurate
+/// enough for manufacturing.
+pub fn decimate(
+    mesh: &Mesh<Point<3>>,
+    decimation: &Decimation,
+) -> Mesh<Point<3>> {
+    let mut decimator = Decimator::new(mesh);
+
+    loop {
+        match decimation.max_triangles {
+            Some(max_triangles) if decimator.num_triangles <= max_triangles => {
+                break;
+            }
+            None if decimation.max_error.is_none() => break,
+            _ => {}
+        }
+
+        let Reverse((cost, from, to, versions)) = match decimator.queue.pop() {
+            Some(candidate) => candidate,
+            None => break,
+        };
+
+        let is_current = decimator.alive[from]
+            && decimator.alive[to]
+            && versions == [decimator.versions[from], decimator.versions[to]];
+        if !is_current {
+            continue;
+        }
+
+        // The queue is sorted by cost, so all remaining candidates would
+        // exceed the limit too.
+        if let Some(max_error) = decimation.max_error {
+            if cost > max_error * max_error {
+                break;
+            }
+        }
+
+        if decimator.can_collapse(from, to) {
+            decimator.collapse(from, to);
+        }
+    }
+
+    decimator.into_mesh()
+}
+
+struct Decimator {
+    positions: Vec<Point<3>>,
+    alive: Vec<bool>,
+    versions: Vec<u64>,
+    quadrics: Vec<Quadric>,
+    classes: Vec<VertexClass>,
+
+    triangles: Vec<Option<[usize; 3]>>,
+    attributes: Vec<Triangle>,
+    triangles_of: Vec<Vec<usize>>,
+    num_triangles: usize,
+
+    feature_edges: HashSet<[usize; 2]>,
+
+    queue: BinaryHeap<Reverse<(Scalar, usize, usize, [u64; 2])>>,
+}
+
+impl Decimator {
+    fn new(mesh: &Mesh<Point<3>>) -> Self {
+        let positions = mesh.vertices().collect::<Vec<_>>();
+        let attributes = mesh.triangles().collect::<Vec<_>>();
+        let indices = mesh.indices().collect::<Vec<_>>();
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                Some(
+                    [triangle[0], triangle[1], triangle[2]].map(|i| i as usize),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut triangles_of = vec![Vec::new(); positions.len()];
+        for (t, triangle) in triangles.iter().enumerate() {
+            for &vertex in triangle.iter().flatten() {
+                triangles_of[vertex].push(t);
+            }
+        }
+
+        let region = |t: usize| (attributes[t].face, attributes[t].color);
+
+        let mut edges: BTreeMap<[usize; 2], Vec<usize>> = BTreeMap::new();
+        for (t, triangle) in triangles.iter().enumerate() {
+            let [a, b, c] = triangle.expect("All triangles are alive");
+            for edge in [[a, b], [b, c], [c, a]] {
+                edges.entry(sorted(edge)).or_default().push(t);
+            }
+        }
+
+        let mut quadrics = vec![Quadric::default(); positions.len()];
+        let mut feature_edges = HashSet::new();
+        let mut num_feature_edges = vec![0; positions.len()];
+        let mut is_corner = vec![false; positions.len()];
+
+        for (t, triangle) in triangles.iter().enumerate() {
+            let triangle = triangle.expect("All triangles are alive");
+            let plane = Quadric::from_plane(
+                attributes[t].inner.normal(),
+                positions[triangle[0]],
+            );
+
+            for vertex in triangle {
+                quadrics[vertex] = quadrics[vertex] + plane;
+            }
+        }
+
+        for (&[a, b], users) in &edges {
+            let is_feature = match users.as_slice() {
+                &[t0, t1] => region(t0) != region(t1),
+                _ => true,
+            };
+            if !is_feature {
+                continue;
+            }
+
+            if users.len() > 2 {
+                is_corner[a] = true;
+                is_corner[b] = true;
+            }
+
+            feature_edges.insert([a, b]);
+            num_feature_edges[a] += 1;
+            num_feature_edges[b] += 1;
+
+            // Penalize moving the vertices away from the feature, by adding
+            // planes that contain the edge and are perpendicular to the
+            // triangles next to it.
+            let direction = positions[b] - positions[a];
+            for &t in users {
+                let normal = direction.cross(&attributes[t].inner.normal());
+                if normal.magnitude() == Scalar::ZERO {
+                    continue;
+                }
+
+                let plane =
+                    Quadric::from_plane(normal.normalize(), positions[a]);
+                quadrics[a] = quadrics[a] + plane;
+                quadrics[b] = quadrics[b] + plane;
+            }
+        }
+
+        let classes = num_feature_edges
+            .into_iter()
+            .zip(is_corner)
+            .map(|(num_feature_edges, is_corner)| {
+                match (num_feature_edges, is_corner) {
+                    (0, false) => VertexClass::Interior,
+                    (2, false) => VertexClass::Feature,
+                    _ => VertexClass::Corner,
+                }
+            })
+            .collect();
+
+        let num_triangles = triangles.len();
+        let mut self_ = Self {
+            alive: vec![true; positions.len()],
+            versions: vec![0; positions.len()],
+            positions,
+            quadrics,
+            classes,
+            triangles,
+            attributes,
+            triangles_of,
+            num_triangles,
+            feature_edges,
+            queue: BinaryHeap::new(),
+        };
+
+        for [a, b] in edges.into_keys() {
+            self_.push_candidate(a, b);
+            self_.push_candidate(b, a);
+        }
+
+        self_
+    }
+
+    fn push_candidate(&mut self, from: usize, to: usize) {
+        let allowed = match self.classes[from] {
+            VertexClass::Interior => true,
+            VertexClass::Feature => {
+                self.feature_edges.contains(&sorted([from, to]))
+            }
+            VertexClass::Corner => false,
+        };
+        if !allowed {
+            return;
+        }
+
+        let quadric = self.quadrics[from] + self.quadrics[to];
+        let cost = quadric.error(self.positions[to]).max(Scalar::ZERO);
+
+        self.queue.push(Reverse((
+            cost,
+            from,
+            to,
+            [self.versions[from], self.versions[to]],
+        )));
+    }
+
+    fn neighbors(&self, vertex: usize) -> BTreeSet<usize> {
+        self.triangles_of[vertex]
+            .iter()
+            .filter_map(|&t| self.triangles[t])
+            .flatten()
+            .filter(|&other| other != vertex)
+            .collect()
+    }
+
+    fn can_collapse(&self, from: usize, to: usize) -> bool {
+        // Collapsing an edge must not change the topology of the mesh. That's
+        // the case, if the only vertices that are connected to both ends of
+        // the edge are those of the triangles that share the edge.
+        let shared = self
+            .neighbors(from)
+            .intersection(&self.neighbors(to))
+            .count();
+        let num_shared_triangles = self.triangles_of[from]
+            .iter()
+            .filter_map(|&t| self.triangles[t])
+            .filter(|triangle| triangle.contains(&to))
+            .count();
+        if num_shared_triangles == 0 || shared != num_shared_triangles {
+            return false;
+        }
+
+        // The remaining triangles must not degenerate or flip over.
+        for &t in &self.triangles_of[from] {
+            let triangle = match self.triangles[t] {
+                Some(triangle) if !triangle.contains(&to) => triangle,
+                _ => continue,
+            };
+
+            let before = normal_of(triangle.map(|v| self.positions[v]));
+            let after = normal_of(
+                triangle
+                    .map(|v| if v == from { to } else { v })
+                    .map(|v| self.positions[v]),
+            );
+
+            if after.magnitude() == Scalar::ZERO
+                || before.dot(&after) <= Scalar::ZERO
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn collapse(&mut self, from: usize, to: usize) {
+        let neighbors = self.neighbors(from);
+
+        for t in self.triangles_of[from].clone() {
+            let mut triangle = match self.triangles[t] {
+                Some(triangle) => triangle,
+                None => continue,
+            };
+
+            if triangle.contains(&to) {
+                self.triangles[t] = None;
+                self.num_triangles -= 1;
+
+                for vertex in triangle {
+                    if vertex != from {
+                        self.triangles_of[vertex].retain(|&other| other != t);
+                    }
+                }
+
+                continue;
+            }
+
+            let corner = triangle
+                .iter()
+                .position(|&vertex| vertex == from)
+                .expect("Triangle of vertex must contain vertex");
+            triangle[corner] = to;
+
+            let region = (self.attributes[t].face, self.attributes[t].color);
+            let normal = self.normal_at(to, region);
+            if let (Some(normals), Some(normal)) =
+                (&mut self.attributes[t].normals, normal)
+            {
+                normals[corner] = normal;
+            }
+
+            self.triangles[t] = Some(triangle);
+            self.triangles_of[to].push(t);
+        }
+
+        self.triangles_of[from].clear();
+        self.alive[from] = false;
+        self.quadrics[to] = self.quadrics[to] + self.quadrics[from];
+        self.versions[to] += 1;
+
+        for neighbor in neighbors {
+            if self.feature_edges.remove(&sorted([from, neighbor]))
+                && neighbor != to
+            {
+                self.feature_edges.insert(sorted([to, neighbor]));
+            }
+        }
+
+        for neighbor in self.neighbors(to) {
+            self.push_candidate(to, neighbor);
+            self.push_candidate(neighbor, to);
+        }
+    }
+
+    /// Find the normal at a vertex, in the triangles of the given region
+    fn normal_at(
+        &self,
+        vertex: usize,
+        region: (Option<FaceId>, Color),
+    ) -> Option<Vector<3>> {
+        self.triangles_of[vertex].iter().find_map(|&t| {
+            let attributes = &self.attributes[t];
+            if (attributes.face, attributes.color) != region {
+                return None;
+            }
+
+            let corner = self.triangles[t]?
+                .iter()
+                .position(|&other| other == vertex)?;
+            attributes.normals.map(|normals| normals[corner])
+        })
+    }
+
+    fn into_mesh(self) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+
+        for (triangle, attributes) in
+            self.triangles.into_iter().zip(self.attributes)
+        {
+            let triangle = match triangle {
+                Some(triangle) => triangle,
+                None => continue,
+            };
+
+            let points = triangle.map(|vertex| self.positions[vertex]);
+            if let Ok(inner) = fj_math::Triangle::from_points(points) {
+                mesh.push(Triangle {
+                    inner,
+                    ..attributes
+                });
+            }
+        }
+
+        mesh
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum VertexClass {
+    /// The vertex can move freely
+    Interior,
+
+    /// The vertex can only move along the feature edges it's connected to
+    Feature,
+
+    /// The vertex can't move
+    Corner,
+}
+
+/// A quadric that measures the squared distance of points from planes
+#[derive(Clone, Copy, Debug, Default)]
+struct Quadric([Scalar; 10]);
+
+impl Quadric {
+    fn from_plane(normal: Vector<3>, point: Point<3>) -> Self {
+        let [a, b, c] = normal.components;
+        let d = -normal.dot(&point.coords);
+
+        Self([
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ])
+    }
+
+    fn error(&self, point: Point<3>) -> Scalar {
+        let [x, y, z] = point.coords.components;
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+
+        aa * x * x
+            + bb * y * y
+            + cc * z * z
+            + (ab * x * y + ac * x * z + ad * x + bc * y * z + bd * y + cd * z)
+                * 2.
+            + dd
+    }
+}
+
+impl Add for Quadric {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        let mut sum = self.0;
+        for (a, b) in sum.iter_mut().zip(other.0) {
+            *a = *a + b;
+        }
+        Self(sum)
+    }
+}
+
+fn sorted([a, b]: [usize; 2]) -> [usize; 2] {
+    if a < b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+fn normal_of([a, b, c]: [Point<3>; 3]) -> Vector<3> {
+    (b - a).cross(&(c - a))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::mesh::{Color, Mesh};
+
+    use super::{decimate, Decimation};
+
+    #[test]
+    fn flat_grid() {
+        let mut mesh = Mesh::new();
+        push_grid(&mut mesh, Color::default(), |u, v| [u, v, 0.]);
+
+        let num_triangles = mesh.triangles().count();
+        let decimated = decimate(
+            &mesh,
+            &Decimation {
+                max_error: Some(Scalar::from_f64(1e-9)),
+                ..Decimation::default()
+            },
+        );
+
+        assert!(decimated.triangles().count() < num_triangles / 2);
+        for triangle in decimated.triangles() {
+            for point in triangle.inner.points() {
+                assert_eq!(point.z, Scalar::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_budget() {
+        let mut mesh = Mesh::new();
+        push_grid(&mut mesh, Color::default(), |u, v| [u, v, u * v]);
+
+        let decimated = decimate(
+            &mesh,
+            &Decimation {
+                max_triangles: Some(64),
+                ..Decimation::default()
+            },
+        );
+
+        assert!(decimated.triangles().count() <= 64);
+    }
+
+    #[test]
+    fn no_targets() {
+        let mut mesh = Mesh::new();
+        push_grid(&mut mesh, Color::default(), |u, v| [u, v, 0.]);
+
+        let decimated = decimate(&mesh, &Decimation::default());
+
+        assert_eq!(
+            decimated.triangles().collect::<Vec<_>>(),
+            mesh.triangles().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn sharp_edges() {
+        let a = Color([255, 0, 0, 255]);
+        let b = Color([0, 0, 255, 255]);
+
+        // Two regions that meet at a right angle.
+        let mut mesh = Mesh::new();
+        push_grid(&mut mesh, a, |u, v| [u, v, 0.]);
+        push_grid(&mut mesh, b, |u, v| [u, 0., v]);
+
+        let decimated = decimate(
+            &mesh,
+            &Decimation {
+                max_triangles: Some(4),
+                ..Decimation::default()
+            },
+        );
+
+        for triangle in decimated.triangles() {
+            for point in triangle.inner.points() {
+                if triangle.color == a {
+                    assert_eq!(point.z, Scalar::ZERO);
+                } else {
+                    assert_eq!(point.y, Scalar::ZERO);
+                }
+            }
+        }
+    }
+
+    fn push_grid(
+        mesh: &mut Mesh<Point<3>>,
+        color: Color,
+        f: impl Fn(f64, f64) -> [f64; 3],
+    ) {
+        const N: usize = 8;
+
+        let point = |i: usize, j: usize| {
+            Point::from(f(i as f64 / N as f64, j as f64 / N as f64))
+        };
+
+        for i in 0..N {
+            for j in 0..N {
+                let [a, b, c, d] = [
+                    point(i, j),
+                    point(i + 1, j),
+                    point(i + 1, j + 1),
+                    point(i, j + 1),
+                ];
+
+                mesh.push_triangle([a, b, c], color);
+                mesh.push_triangle([a, c, d], color);
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Deviation of a triangle mesh from the shape it approximates
+
+use fj_math::{Point, Scalar};
+
+/// The deviation of a triangle mesh from the shape it approximates
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshDeviation {
+    /// The deviation of each face that could be analyzed
+    pub faces: Vec<FaceDeviation>,
+}
+
+impl MeshDeviation {
+    /// The maximum deviation of all faces
+    ///
+    /// Returns zero, if no face could be analyzed.
+    pub fn max(&self) -> Scalar {
+        self.faces
+            .iter()
+            .map(|face| face.max)
+            .fold(Scalar::ZERO, Scalar::max)
+    }
+
+    /// The mean deviation of all samples of all faces
+    ///
+    /// Returns zero, if no face could be analyzed.
+    pub fn mean(&self) -> Scalar {
+        let num_samples =
+            self.faces.iter().map(|face| face.num_samples).sum::<u64>();
+        if num_samples == 0 {
+            return Scalar::ZERO;
+        }
+
+        let sum = self
+            .faces
+            .iter()
+            .map(|face| face.mean * Scalar::from_u64(face.num_samples))
+            .fold(Scalar::ZERO, |a, b| a + b);
+
+        sum / Scalar::from_u64(num_samples)
+    }
+}
+
+/// The deviation of the triangles of a single face from that face
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FaceDeviation {
+    /// The index of the face within the shape
+    pub face: usize,
+
+    /// The maximum deviation of any sample
+    pub max: Scalar,
+
+    /// The mean deviation of all samples
+    pub mean: Scalar,
+
+    /// The sample with the maximum deviation
+    pub worst_point: Point<3>,
+
+    /// The number of points that were sampled
+    pub num_samples: u64,
+}
@@ -0,0 +1,68 @@
+//! 2-dimensional drawings of a shape
+
+use fj_math::Point;
+
+/// An orthographic drawing of a shape
+///
+/// Consists of polylines in the coordinates of the drawing plane, each of
+/// which is classified by what it represents, and whether it is visible.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Drawing {
+    /// The lines that make up the drawing
+    pub lines: Vec<DrawingLine>,
+}
+
+impl Drawing {
+    /// Construct an empty instance of `Drawing`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the lines with the given visibility
+    pub fn lines_with_visibility(
+        &self,
+        visibility: Visibility,
+    ) -> impl Iterator<Item = &DrawingLine> + '_ {
+        self.lines
+            .iter()
+            .filter(move |line| line.visibility == visibility)
+    }
+}
+
+/// A polyline in a [`Drawing`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawingLine {
+    /// The points of the polyline
+    pub points: Vec<Point<2>>,
+
+    /// What the line represents
+    pub kind: LineKind,
+
+    /// Whether the line is visible
+    pub visibility: Visibility,
+}
+
+/// What a [`DrawingLine`] represents
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum LineKind {
+    /// The line is the projection of an edge
+    Edge,
+
+    /// The line is the silhouette of a curved face
+    ///
+    /// Where a curved face turns away from the viewer, there's no edge, but
+    /// the outline of the face still needs to be drawn.
+    Silhouette,
+}
+
+/// The visibility of a [`DrawingLine`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Visibility {
+    /// The line is visible
+    Visible,
+
+    /// The line is hidden behind a face
+    ///
+    /// Technical drawings usually show these as dashed lines.
+    Hidden,
+}
@@ -14,7 +14,12 @@
 
 #![warn(missing_docs)]
 
+pub mod analysis;
 pub mod debug;
+pub mod decimation;
+pub mod deviation;
+pub mod drawing;
+pub mod mass_properties;
 pub mod mesh;
 pub mod processed_shape;
 pub mod status_report;
@@ -0,0 +1,28 @@
+//! Mass properties of a shape
+
+use fj_math::{Point, Scalar};
+
+/// The mass properties of a shape
+///
+/// All values assume a uniform density of `1`. Multiply `volume` and `inertia`
+/// with the actual density to get mass and moment of inertia.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    /// The volume enclosed by the shape
+    pub volume: Scalar,
+
+    /// The area of the shape's surface
+    pub area: Scalar,
+
+    /// The center of mass
+    ///
+    /// If the shape doesn't enclose any volume, this is the origin.
+    pub centroid: Point<3>,
+
+    /// The inertia tensor, relative to the center of mass
+    ///
+    /// The tensor is stored in row-major order. The diagonal elements are the
+    /// moments of inertia around the x-, y-, and z-axis; the other elements
+    /// are the products of inertia.
+    pub inertia: [[Scalar; 3]; 3],
+}
@@ -2,9 +2,10 @@
 
 use std::{collections::HashMap, hash::Hash};
 
-use fj_math::Point;
+use fj_math::{Point, Scalar, Vector};
 
 /// A triangle mesh
+#[derive(Clone)]
 pub struct Mesh<V> {
     vertices: Vec<V>,
     indices: Vec<Index>,
@@ -77,16 +78,108 @@ impl Mesh<Point<3>> {
         triangle: impl Into<fj_math::Triangle<3>>,
         color: Color,
     ) {
-        let triangle = triangle.into();
+        self.push(Triangle::new(triangle, color));
+    }
 
-        for point in triangle.points() {
+    /// Add a triangle, including all of its attributes, to the mesh
+    pub fn push(&mut self, triangle: Triangle) {
+        for point in triangle.inner.points() {
             self.push_vertex(point);
         }
 
-        self.triangles.push(Triangle {
-            inner: triangle,
-            color,
-        });
+        self.triangles.push(triangle);
+    }
+
+    /// Merge vertices that are closer to each other than `distance`
+    ///
+    /// Vertices are only merged, if they are exactly equal, when they are
+    /// pushed. Faces that are triangulated separately can end up with slightly
+    /// different vertices where they meet, leaving cracks in the mesh. Welding
+    /// closes those cracks.
+    ///
+    /// Of each group of merged vertices, the one that was pushed first is kept.
+    /// Triangles that become degenerate are removed from the mesh.
+    pub fn weld(&mut self, distance: Scalar) {
+        if distance <= Scalar::ZERO {
+            return;
+        }
+
+        let cell_of = |point: Point<3>| {
+            point.coords.components.map(|coord| {
+                (coord.into_f64() / distance.into_f64()).floor() as i64
+            })
+        };
+
+        // Vertices are sorted into a grid, with cells as large as the welding
+        // distance. Any vertex that is close enough to be welded must then be
+        // in the same or a neighboring cell.
+        let mut grid: HashMap<[i64; 3], Vec<Index>> = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut new_indices = Vec::with_capacity(self.vertices.len());
+
+        for &vertex in &self.vertices {
+            let [x, y, z] = cell_of(vertex);
+
+            let mut existing = None;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let cell = [
+                            x.saturating_add(dx),
+                            y.saturating_add(dy),
+                            z.saturating_add(dz),
+                        ];
+                        for &index in grid.get(&cell).into_iter().flatten() {
+                            let other: Point<3> = vertices[index as usize];
+                            if (other - vertex).magnitude() <= distance {
+                                existing = Some(index);
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let index = existing.unwrap_or_else(|| {
+                let index = vertices.len() as Index;
+                vertices.push(vertex);
+                grid.entry([x, y, z]).or_default().push(index);
+                index
+            });
+
+            new_indices.push(index);
+        }
+
+        let mut indices = Vec::with_capacity(self.indices.len());
+        let mut triangles = Vec::with_capacity(self.triangles.len());
+
+        for (triangle, old) in
+            self.triangles.iter().zip(self.indices.chunks_exact(3))
+        {
+            let [a, b, c] = [old[0], old[1], old[2]]
+                .map(|index| new_indices[index as usize]);
+            if a == b || b == c || c == a {
+                continue;
+            }
+
+            let points = [a, b, c].map(|index| vertices[index as usize]);
+            let inner = match fj_math::Triangle::from_points(points) {
+                Ok(inner) => inner,
+                Err(_) => continue,
+            };
+
+            indices.extend([a, b, c]);
+            triangles.push(Triangle { inner, ..*triangle });
+        }
+
+        self.indices_by_vertex = vertices
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex)| (vertex, index as Index))
+            .collect();
+        self.vertices = vertices;
+        self.indices = indices;
+        self.triangles = triangles;
     }
 }
 
@@ -108,7 +201,8 @@ pub type Index = u32;
 
 /// A triangle
 ///
-/// Extension of [`fj_math::Triangle`] that also includes a color.
+/// Extension of [`fj_math::Triangle`] that also includes a color, and optional
+/// attributes that depend on the shape the triangle was created from.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Triangle {
     /// The points of the triangle
@@ -116,8 +210,63 @@ pub struct Triangle {
 
     /// The color of the triangle
     pub color: Color,
+
+    /// The normals at the points of the triangle
+    ///
+    /// These are the normals of the surface that the triangle approximates,
+    /// which allow curved surfaces to be shaded smoothly. If they are not
+    /// available, the normal of the triangle itself can be used instead. See
+    /// [`Triangle::vertex_normals`].
+    pub normals: Option<[Vector<3>; 3]>,
+
+    /// The face that the triangle was created from
+    pub face: Option<FaceId>,
 }
 
+impl Triangle {
+    /// Construct a `Triangle` without any of the optional attributes
+    pub fn new(
+        triangle: impl Into<fj_math::Triangle<3>>,
+        color: Color,
+    ) -> Self {
+        Self {
+            inner: triangle.into(),
+            color,
+            normals: None,
+            face: None,
+        }
+    }
+
+    /// Set the normals at the points of the triangle
+    pub fn with_normals(mut self, normals: [Vector<3>; 3]) -> Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    /// Set the face that the triangle was created from
+    pub fn with_face(mut self, face: FaceId) -> Self {
+        self.face = Some(face);
+        self
+    }
+
+    /// Access the normals at the points of the triangle
+    ///
+    /// Falls back to the normal of the triangle itself, for all points, if no
+    /// normals are available.
+    pub fn vertex_normals(&self) -> [Vector<3>; 3] {
+        self.normals.unwrap_or_else(|| {
+            let normal = self.inner.normal();
+            [normal; 3]
+        })
+    }
+}
+
+/// Identifies the face that a triangle was created from
+///
+/// This is the index of the face within the shape that was triangulated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct FaceId(pub usize);
+
 /// RGBA color
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Color(pub [u8; 4]);
@@ -128,3 +277,56 @@ impl Default for Color {
         Self([255, 0, 0, 255])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use super::{Color, Mesh};
+
+    #[test]
+    fn weld() {
+        let mut mesh = Mesh::new();
+
+        // Two triangles that share an edge, except that the points on the
+        // shared edge don't match up exactly.
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Color::default(),
+        );
+        mesh.push_triangle(
+            [[1., 1e-9, 0.], [1., 1., 0.], [1e-9, 1., 0.]],
+            Color::default(),
+        );
+        assert_eq!(mesh.vertices().count(), 6);
+
+        mesh.weld(Scalar::from_f64(1e-6));
+
+        assert_eq!(mesh.vertices().count(), 4);
+        assert_eq!(mesh.triangles().count(), 2);
+        assert!(mesh.contains_triangle([
+            [1., 0., 0.],
+            [1., 1., 0.],
+            [0., 1., 0.]
+        ]));
+    }
+
+    #[test]
+    fn weld_removes_degenerate_triangles() {
+        let mut mesh = Mesh::new();
+
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Color::default(),
+        );
+        mesh.push_triangle(
+            [[0., 0., 0.], [1e-9, 0., 0.], [0., 0., 1.]],
+            Color::default(),
+        );
+
+        mesh.weld(Scalar::from_f64(1e-6));
+
+        assert_eq!(mesh.triangles().count(), 1);
+        assert_eq!(mesh.indices().count(), 3);
+    }
+}
@@ -1,8 +1,13 @@
 //! A processed shape
 
+use std::ops::Range;
+
 use fj_math::{Aabb, Point};
 
-use crate::{debug::DebugInfo, mesh::Mesh};
+use crate::{
+    analysis::MeshAnalysis, debug::DebugInfo, deviation::MeshDeviation,
+    mass_properties::MassProperties, mesh::Mesh,
+};
 
 /// A processed shape
 pub struct ProcessedShape {
@@ -12,6 +17,33 @@ pub struct ProcessedShape {
     /// The triangle mesh that approximates the original shape
     pub mesh: Mesh<Point<3>>,
 
+    /// The faces that make up each part of the shape
+    ///
+    /// Each range refers to the [`FaceId`]s of the mesh's triangles. Every
+    /// shape of a top-level group is a separate part, and nested groups are
+    /// flattened. Any other shape consists of a single part.
+    ///
+    /// [`FaceId`]: crate::mesh::FaceId
+    pub parts: Vec<Range<usize>>,
+
+    /// The mass properties of the shape
+    ///
+    /// These are computed from the boundary representation of the shape, not
+    /// from the triangle mesh, and therefore don't depend on the tolerance.
+    pub mass_properties: MassProperties,
+
+    /// The deviation of the triangle mesh from the original shape
+    ///
+    /// Only available, if the analysis was requested, as it is expensive.
+    pub deviation: Option<MeshDeviation>,
+
+    /// The analysis of the triangle mesh
+    ///
+    /// Reports whether the mesh is a closed manifold, without
+    /// self-intersections. Only available, if the analysis was requested, as
+    /// it is expensive for big meshes.
+    pub analysis: Option<MeshAnalysis>,
+
     /// The debug info generated while processing the shape
     pub debug_info: DebugInfo,
 }
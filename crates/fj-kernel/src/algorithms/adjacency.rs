@@ -0,0 +1,319 @@
+//! Adjacency queries for the faces, edges, and vertices of a shape
+//!
+//! [`ObjectIters`] can only walk a shape downward, from faces to the edges and
+//! vertices that bound them. [`Adjacency`] provides the opposite direction: It
+//! answers which faces meet at an edge, which edges meet at a vertex, and which
+//! face lies across an edge from another.
+//!
+//! [`ObjectIters`]: crate::iter::ObjectIters
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::objects::{
+    CurveKind, Face, GlobalCurve, GlobalEdge, GlobalVertex, Shell, Solid,
+    VerticesOfEdge,
+};
+
+/// An index of the adjacency relationships within a shell or solid
+///
+/// Two edges are considered identical, if they are bounded by the same global
+/// vertices and lie on the same global curve, regardless of the direction of
+/// that curve. This is necessary, as neighboring faces usually reference the
+/// shared edge in opposite directions.
+///
+/// Please note that this comparison is exact. Faces that don't share global
+/// vertices exactly are not considered adjacent.
+///
+/// Faces that use triangle representation don't have any edges, and are
+/// ignored.
+#[derive(Clone, Debug, Default)]
+pub struct Adjacency<'r> {
+    faces: Vec<&'r Face>,
+    faces_by_edge: BTreeMap<GlobalEdge, Vec<usize>>,
+    edges_by_vertex: BTreeMap<GlobalVertex, BTreeSet<GlobalEdge>>,
+}
+
+impl<'r> Adjacency<'r> {
+    /// Build the adjacency index of a shell
+    pub fn from_shell(shell: &'r Shell) -> Self {
+        Self::from_faces(shell.faces())
+    }
+
+    /// Build the adjacency index of a solid
+    ///
+    /// The faces of all of the solid's shells are indexed together.
+    pub fn from_solid(solid: &'r Solid) -> Self {
+        Self::from_faces(solid.shells().flat_map(|shell| shell.faces()))
+    }
+
+    /// Build the adjacency index of a list of faces
+    pub fn from_faces(faces: impl IntoIterator<Item = &'r Face>) -> Self {
+        let mut self_ = Self::default();
+
+        for face in faces {
+            if face.triangles().is_some() {
+                continue;
+            }
+
+            let index = self_.faces.len();
+            self_.faces.push(face);
+
+            for cycle in face.all_cycles() {
+                for edge in cycle.edges() {
+                    let edge = normalize_edge(edge.global());
+
+                    let faces = self_.faces_by_edge.entry(edge).or_default();
+                    if !faces.contains(&index) {
+                        faces.push(index);
+                    }
+
+                    for vertex in edge.vertices().iter() {
+                        self_
+                            .edges_by_vertex
+                            .entry(*vertex)
+                            .or_default()
+                            .insert(edge);
+                    }
+                }
+            }
+        }
+
+        self_
+    }
+
+    /// Access all faces in the index
+    pub fn faces(&self) -> impl Iterator<Item = &'r Face> + '_ {
+        self.faces.iter().copied()
+    }
+
+    /// Access all edges in the index
+    ///
+    /// Each edge is returned once, in its normalized form. See
+    /// [`Adjacency::normalize_edge`].
+    pub fn edges(&self) -> impl Iterator<Item = &GlobalEdge> + '_ {
+        self.faces_by_edge.keys()
+    }
+
+    /// Access all vertices in the index
+    pub fn vertices(&self) -> impl Iterator<Item = &GlobalVertex> + '_ {
+        self.edges_by_vertex.keys()
+    }
+
+    /// Access the faces that share the provided edge
+    pub fn faces_of_edge(
+        &self,
+        edge: &GlobalEdge,
+    ) -> impl Iterator<Item = &'r Face> + '_ {
+        self.faces_by_edge
+            .get(&normalize_edge(edge))
+            .into_iter()
+            .flatten()
+            .map(|&index| self.faces[index])
+    }
+
+    /// Access the edges that meet at the provided vertex
+    pub fn edges_of_vertex(
+        &self,
+        vertex: &GlobalVertex,
+    ) -> impl Iterator<Item = &GlobalEdge> + '_ {
+        self.edges_by_vertex.get(vertex).into_iter().flatten()
+    }
+
+    /// Access the faces that meet at the provided vertex
+    pub fn faces_of_vertex(&self, vertex: &GlobalVertex) -> Vec<&'r Face> {
+        let mut indices = BTreeSet::new();
+
+        for edge in self.edges_of_vertex(vertex) {
+            if let Some(faces) = self.faces_by_edge.get(edge) {
+                indices.extend(faces.iter().copied());
+            }
+        }
+
+        indices.into_iter().map(|index| self.faces[index]).collect()
+    }
+
+    /// Access the faces that are bounded by the provided face's edges
+    ///
+    /// The provided face itself is not included.
+    pub fn neighbors_of_face(&self, face: &Face) -> Vec<&'r Face> {
+        let mut neighbors = Vec::new();
+
+        for cycle in face.all_cycles() {
+            for edge in cycle.edges() {
+                if let Some(neighbor) =
+                    self.face_across_edge(face, edge.global())
+                {
+                    if !neighbors.contains(&neighbor) {
+                        neighbors.push(neighbor);
+                    }
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    /// Access the face that lies across the provided edge from `face`
+    ///
+    /// Returns `None`, if `face` is the only face bounded by the edge, or if
+    /// the edge is shared by more than two faces, which means the face across
+    /// the edge is ambiguous.
+    pub fn face_across_edge(
+        &self,
+        face: &Face,
+        edge: &GlobalEdge,
+    ) -> Option<&'r Face> {
+        let faces = self.faces_by_edge.get(&normalize_edge(edge))?;
+
+        if faces.len() != 2 {
+            return None;
+        }
+
+        let [a, b] = [faces[0], faces[1]].map(|index| self.faces[index]);
+
+        if a == face {
+            Some(b)
+        } else if b == face {
+            Some(a)
+        } else {
+            None
+        }
+    }
+
+    /// Access the edges that bound only a single face
+    ///
+    /// If a shell has any of those, it is not closed.
+    pub fn boundary_edges(&self) -> impl Iterator<Item = &GlobalEdge> + '_ {
+        self.faces_by_edge
+            .iter()
+            .filter(|(_, faces)| faces.len() == 1)
+            .map(|(edge, _)| edge)
+    }
+
+    /// Access the edges that are shared by more than two faces
+    ///
+    /// If a shell has any of those, it is not a manifold.
+    pub fn non_manifold_edges(&self) -> impl Iterator<Item = &GlobalEdge> + '_ {
+        self.faces_by_edge
+            .iter()
+            .filter(|(_, faces)| faces.len() > 2)
+            .map(|(edge, _)| edge)
+    }
+
+    /// Indicate whether the indexed faces form a closed manifold
+    ///
+    /// This is the case, if every edge is shared by exactly two faces.
+    pub fn is_closed_manifold(&self) -> bool {
+        self.faces_by_edge.values().all(|faces| faces.len() == 2)
+    }
+
+    /// Normalize an edge, so it can be compared to its reversed form
+    ///
+    /// This is the form that edges are stored in by the index.
+    pub fn normalize_edge(edge: &GlobalEdge) -> GlobalEdge {
+        normalize_edge(edge)
+    }
+}
+
+fn normalize_edge(edge: &GlobalEdge) -> GlobalEdge {
+    match edge.vertices().get() {
+        Some([&a, &b]) => {
+            let [a, b] = [a.min(b), a.max(b)];
+
+            // Lines keep their origin when reversed, and neighboring faces
+            // might have constructed the same line from different points. The
+            // vertices define the line unambiguously though.
+            let curve = match edge.curve().kind() {
+                CurveKind::Line(_) => {
+                    CurveKind::line_from_points([a.position(), b.position()])
+                }
+                kind => normalize_curve(*kind),
+            };
+
+            GlobalEdge::new(
+                GlobalCurve::from_kind(curve),
+                VerticesOfEdge::from_vertices([a, b]),
+            )
+        }
+        None => GlobalEdge::new(
+            GlobalCurve::from_kind(normalize_curve(*edge.curve().kind())),
+            VerticesOfEdge::none(),
+        ),
+    }
+}
+
+fn normalize_curve(kind: CurveKind<3>) -> CurveKind<3> {
+    kind.min(kind.reverse())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::reverse::Reverse,
+        iter::ObjectIters,
+        objects::{Face, GlobalVertex, Shell, Surface},
+    };
+
+    use super::Adjacency;
+
+    #[test]
+    fn cube() {
+        let shell = Shell::build().cube_from_edge_length(2.);
+        let adjacency = Adjacency::from_shell(&shell);
+
+        assert_eq!(adjacency.faces().count(), 6);
+        assert_eq!(adjacency.edges().count(), 12);
+        assert_eq!(adjacency.vertices().count(), 8);
+        assert!(adjacency.is_closed_manifold());
+
+        for edge in adjacency.edges() {
+            assert_eq!(adjacency.faces_of_edge(edge).count(), 2);
+        }
+
+        let corner = GlobalVertex::from_position([1., 1., 1.]);
+        assert_eq!(adjacency.edges_of_vertex(&corner).count(), 3);
+        assert_eq!(adjacency.faces_of_vertex(&corner).len(), 3);
+
+        for face in shell.faces() {
+            assert_eq!(adjacency.neighbors_of_face(face).len(), 4);
+        }
+    }
+
+    #[test]
+    fn face_across_edge() {
+        let shell = Shell::build().cube_from_edge_length(2.);
+        let adjacency = Adjacency::from_shell(&shell);
+
+        for face in shell.faces() {
+            for edge in face.edge_iter() {
+                let other = adjacency
+                    .face_across_edge(face, edge.global())
+                    .expect("Every cube edge has a face on either side");
+                assert_ne!(other, face);
+
+                // The relationship must be symmetric, regardless of the
+                // direction the edge is referenced in.
+                let reversed = edge.reverse();
+                assert_eq!(
+                    adjacency.face_across_edge(other, reversed.global()),
+                    Some(face)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn open_shell() {
+        let surface = Surface::xy_plane();
+        let face = Face::build(surface)
+            .polygon_from_points([[0., 0.], [1., 0.], [0., 1.]])
+            .into_face();
+        let shell = Shell::new().with_faces([face]);
+
+        let adjacency = Adjacency::from_shell(&shell);
+
+        assert_eq!(adjacency.boundary_edges().count(), 3);
+        assert_eq!(adjacency.non_manifold_edges().count(), 0);
+        assert!(!adjacency.is_closed_manifold());
+    }
+}
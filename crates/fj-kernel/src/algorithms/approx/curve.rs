@@ -1,6 +1,6 @@
 use std::cmp::max;
 
-use fj_math::{Circle, Point, Scalar};
+use fj_math::{Circle, Line, Point, Scalar};
 
 use crate::objects::{Curve, CurveKind, GlobalCurve};
 
@@ -38,7 +38,7 @@ impl Approx for GlobalCurve {
     ) -> Self::Approximation {
         match self.kind() {
             CurveKind::Circle(curve) => approx_circle(curve, range, tolerance),
-            CurveKind::Line(_) => vec![range.start()],
+            CurveKind::Line(line) => approx_line(line, range, tolerance),
         }
     }
 }
@@ -68,7 +68,7 @@ fn approx_circle(
 
     for i in 1..n {
         let angle = range.start().0.t
-            + (Scalar::TAU / n as f64 * i as f64) * range.direction();
+            + (range.length() / n as f64 * i as f64) * range.direction();
 
         let point_curve = Point::from([angle]);
         let point_global = circle.point_from_circle_coords(point_curve);
@@ -84,13 +84,71 @@ fn number_of_vertices_for_circle(
     radius: Scalar,
     range: Scalar,
 ) -> u64 {
-    let n = (range / (Scalar::ONE - (tolerance.inner() / radius)).acos() / 2.)
-        .ceil()
-        .into_u64();
+    let mut n =
+        (range / (Scalar::ONE - (tolerance.inner() / radius)).acos() / 2.)
+            .ceil()
+            .into_u64();
+
+    // Each vertex changes the direction of the approximation by the angle
+    // between two vertices, as seen from the center.
+    if let Some(angular_deviation) = tolerance.angular_deviation() {
+        n = max(n, (range / angular_deviation).ceil().into_u64());
+    }
+
+    // A segment that spans the angle `a` has the length `2 * r * sin(a / 2)`.
+    // No segment is longer than the diameter, so a limit above that doesn't
+    // have any effect.
+    if let Some(max_edge_length) = tolerance.max_edge_length() {
+        let ratio = max_edge_length / (radius * 2.);
+        if ratio < Scalar::ONE {
+            let max_angle = ratio.into_f64().asin() * 2.;
+            n = max(n, (range / max_angle).ceil().into_u64());
+        }
+    }
+
+    if let Some(min_circle_segments) = tolerance.min_circle_segments() {
+        let min = Scalar::from_u64(min_circle_segments) * range / Scalar::TAU;
+        n = max(n, min.ceil().into_u64());
+    }
 
     max(n, 3)
 }
 
+/// Approximate a line
+///
+/// Lines don't deviate from their approximation, so only the start of the
+/// range is needed, unless the tolerance limits the length of the segments.
+fn approx_line(
+    line: &Line<3>,
+    range: impl Into<RangeOnCurve>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    let range = range.into();
+
+    let n = match tolerance.max_edge_length() {
+        Some(max_edge_length) => {
+            let length = (range.end().1 - range.start().1).magnitude();
+            max((length / max_edge_length).ceil().into_u64(), 1)
+        }
+        None => 1,
+    };
+
+    let mut points = Vec::new();
+    points.push(range.start());
+
+    for i in 1..n {
+        let t = range.start().0.t
+            + (range.length() / n as f64 * i as f64) * range.direction();
+
+        let point_curve = Point::from([t]);
+        let point_global = line.point_from_line_coords(point_curve);
+
+        points.push((point_curve, point_global));
+    }
+
+    points
+}
+
 pub struct RangeOnCurve {
     pub boundary: [(Point<1>, Point<3>); 2],
 }
@@ -119,10 +177,40 @@ impl RangeOnCurve {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Scalar;
+    use fj_math::{Circle, Point, Scalar};
 
     use crate::algorithms::approx::Tolerance;
 
+    use super::RangeOnCurve;
+
+    #[test]
+    fn approx_circle_within_range() -> anyhow::Result<()> {
+        let circle = Circle::new([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        for range in [[0., 1.], [1., 0.], [Scalar::PI.into_f64(), 0.5]] {
+            let boundary = range.map(|t| {
+                let point_curve = Point::from([t]);
+                (point_curve, circle.point_from_circle_coords(point_curve))
+            });
+            let [min, max] = [range[0].min(range[1]), range[0].max(range[1])];
+
+            let points = super::approx_circle(
+                &circle,
+                RangeOnCurve { boundary },
+                tolerance,
+            );
+
+            assert!(points.len() > 1);
+            for (point_curve, _) in points {
+                assert!(point_curve.t >= Scalar::from(min));
+                assert!(point_curve.t <= Scalar::from(max));
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn number_of_vertices_for_circle() {
         verify_result(50., 100., Scalar::TAU, 3);
@@ -159,4 +247,44 @@ mod tests {
             radius - radius * (range / Scalar::from_u64(n) / 2.).cos()
         }
     }
+
+    #[test]
+    fn number_of_vertices_for_circle_with_limits() -> anyhow::Result<()> {
+        let radius = Scalar::from(100.);
+        let tolerance = Tolerance::from_scalar(50.)?;
+
+        let angular = tolerance.with_angular_deviation(Scalar::PI / 8.)?;
+        assert_eq!(
+            super::number_of_vertices_for_circle(angular, radius, Scalar::TAU),
+            16
+        );
+        assert_eq!(
+            super::number_of_vertices_for_circle(angular, radius, Scalar::PI),
+            8
+        );
+
+        // The edges of a hexagon are as long as the radius. A slightly shorter
+        // limit requires one more segment.
+        let edge_length = tolerance.with_max_edge_length(90.)?;
+        assert_eq!(
+            super::number_of_vertices_for_circle(
+                edge_length,
+                radius,
+                Scalar::TAU
+            ),
+            7
+        );
+
+        let segments = tolerance.with_min_circle_segments(32);
+        assert_eq!(
+            super::number_of_vertices_for_circle(segments, radius, Scalar::TAU),
+            32
+        );
+        assert_eq!(
+            super::number_of_vertices_for_circle(segments, radius, Scalar::PI),
+            16
+        );
+
+        Ok(())
+    }
 }
@@ -2,7 +2,7 @@ use fj_math::{Point, Segment};
 
 use crate::objects::Cycle;
 
-use super::{Approx, Tolerance};
+use super::{Approx, EdgeApproxCache, Tolerance};
 
 impl Approx for Cycle {
     type Approximation = CycleApprox;
@@ -13,18 +13,11 @@ impl Approx for Cycle {
         tolerance: Tolerance,
         (): Self::Params,
     ) -> Self::Approximation {
-        let mut points = Vec::new();
-
-        for edge in self.edges() {
-            let edge_points = edge.approx(tolerance, ());
-            points.extend(edge_points);
-        }
-
-        if let Some(&point) = points.first() {
-            points.push(point);
-        }
-
-        CycleApprox { points }
+        CycleApprox::with_edge_cache(
+            self,
+            tolerance,
+            &EdgeApproxCache::default(),
+        )
     }
 }
 
@@ -36,6 +29,28 @@ pub struct CycleApprox {
 }
 
 impl CycleApprox {
+    /// Approximate a cycle, sharing the approximations of its edges
+    ///
+    /// See [`EdgeApproxCache`].
+    pub fn with_edge_cache(
+        cycle: &Cycle,
+        tolerance: Tolerance,
+        cache: &EdgeApproxCache,
+    ) -> Self {
+        let mut points = Vec::new();
+
+        for edge in cycle.edges() {
+            let edge_points = cache.approx(edge, tolerance);
+            points.extend(edge_points);
+        }
+
+        if let Some(&point) = points.first() {
+            points.push(point);
+        }
+
+        Self { points }
+    }
+
     /// Construct the segments that approximate the cycle
     pub fn segments(&self) -> Vec<Segment<3>> {
         let mut segments = Vec::new();
@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
 use fj_math::{Point, Scalar};
 
-use crate::objects::Edge;
+use crate::{
+    algorithms::adjacency::Adjacency,
+    objects::{Edge, Face, GlobalEdge},
+};
 
-use super::{curve::RangeOnCurve, Approx};
+use super::{curve::RangeOnCurve, Approx, Tolerance};
 
 impl Approx for Edge {
     type Approximation = Vec<(Point<2>, Point<3>)>;
@@ -13,28 +18,159 @@ impl Approx for Edge {
         tolerance: super::Tolerance,
         (): Self::Params,
     ) -> Self::Approximation {
-        // The range is only used for circles right now.
-        let boundary = match self.vertices().get() {
-            Some(vertices) => vertices
-                .map(|vertex| (vertex.position(), vertex.global().position())),
-            None => {
-                let start_curve = Point::from([Scalar::ZERO]);
-                let end_curve = Point::from([Scalar::TAU]);
-
-                // We're dealing with a circle here. Start and end are identical
-                // points, in global coordinates.
-                let point_global = self
-                    .global()
-                    .curve()
-                    .kind()
-                    .point_from_curve_coords(start_curve);
-
-                [(start_curve, point_global), (end_curve, point_global)]
+        let points = approx_global_edge(self, tolerance);
+        points_on_edge(self, points)
+    }
+}
+
+/// Approximate the global form of an edge
+///
+/// All faces that share the global edge must end up with exactly the same
+/// points on it, or there will be cracks in the mesh. Neighboring faces
+/// traverse the edge in opposite directions though, and approximating the
+/// reversed range doesn't result in bit-identical points. This function always
+/// approximates the edge in the same direction. [`points_on_edge`] reverses the
+/// result, if necessary.
+fn approx_global_edge(
+    edge: &Edge,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<3>)> {
+    let [a, b] = boundary(edge);
+    let boundary = if a.1 > b.1 { [b, a] } else { [a, b] };
+
+    edge.global()
+        .curve()
+        .approx(tolerance, RangeOnCurve { boundary })
+}
+
+/// Convert the approximation of a global edge into one of the provided edge
+fn points_on_edge(
+    edge: &Edge,
+    mut points: Vec<(Point<1>, Point<3>)>,
+) -> Vec<(Point<2>, Point<3>)> {
+    let [a, b] = boundary(edge);
+
+    if a.1 > b.1 {
+        // The approximation contains the start of the range, but not its
+        // end. After reversing it, it has to start at the end instead.
+        points.push(a);
+        points.reverse();
+        points.pop();
+    }
+
+    points
+        .into_iter()
+        .map(|(point_curve, point_global)| {
+            let point_surface =
+                edge.curve().kind().point_from_curve_coords(point_curve);
+            (point_surface, point_global)
+        })
+        .collect()
+}
+
+fn boundary(edge: &Edge) -> [(Point<1>, Point<3>); 2] {
+    // The range is only used for circles right now.
+    match edge.vertices().get() {
+        Some(vertices) => vertices
+            .map(|vertex| (vertex.position(), vertex.global().position())),
+        None => {
+            let start_curve = Point::from([Scalar::ZERO]);
+            let end_curve = Point::from([Scalar::TAU]);
+
+            // We're dealing with a circle here. Start and end are identical
+            // points, in global coordinates.
+            let point_global = edge
+                .global()
+                .curve()
+                .kind()
+                .point_from_curve_coords(start_curve);
+
+            [(start_curve, point_global), (end_curve, point_global)]
+        }
+    }
+}
+
+/// Approximations of edges, shared between faces
+///
+/// All faces that share an edge must approximate it in the same way, or there
+/// will be cracks in the mesh. If those faces have different tolerances, the
+/// shared edge is approximated using the finer one. Each global edge is then
+/// approximated only once, and all faces reuse that approximation.
+#[derive(Debug, Default)]
+pub struct EdgeApproxCache {
+    tolerances: HashMap<GlobalEdge, Tolerance>,
+    approximations: HashMap<GlobalEdge, Vec<(Point<1>, Point<3>)>>,
+}
+
+impl EdgeApproxCache {
+    /// Create a cache for the edges of the provided faces
+    ///
+    /// `tolerance` applies to all faces that don't have their own.
+    pub fn new<'r>(
+        faces: impl IntoIterator<Item = &'r Face>,
+        tolerance: Tolerance,
+    ) -> Self {
+        let edges = faces
+            .into_iter()
+            .filter(|face| face.triangles().is_none())
+            .flat_map(|face| {
+                let tolerance = face.tolerance().unwrap_or(tolerance);
+                face.all_cycles()
+                    .flat_map(|cycle| cycle.edges())
+                    .map(move |edge| (edge, tolerance))
+            })
+            .collect::<Vec<_>>();
+
+        // Edges might have been constructed differently by neighboring faces,
+        // in which case their global forms aren't identical. The normalized
+        // form is used to find the tolerance for them anyway.
+        let mut tolerances = HashMap::new();
+        for &(edge, face_tolerance) in &edges {
+            tolerances
+                .entry(Adjacency::normalize_edge(edge.global()))
+                .and_modify(|tolerance: &mut Tolerance| {
+                    *tolerance = tolerance.finer(&face_tolerance);
+                })
+                .or_insert(face_tolerance);
+        }
+
+        let mut cache = Self {
+            tolerances,
+            approximations: HashMap::new(),
+        };
+        for (edge, tolerance) in edges {
+            if !cache.approximations.contains_key(edge.global()) {
+                let tolerance = cache.tolerance(edge, tolerance);
+                let points = approx_global_edge(edge, tolerance);
+                cache.approximations.insert(*edge.global(), points);
             }
+        }
+
+        cache
+    }
+
+    /// Approximate an edge
+    ///
+    /// Returns the cached approximation, if the edge is part of any of the
+    /// faces that the cache was created for. Otherwise, the edge is
+    /// approximated using `tolerance`.
+    pub fn approx(
+        &self,
+        edge: &Edge,
+        tolerance: Tolerance,
+    ) -> Vec<(Point<2>, Point<3>)> {
+        let points = match self.approximations.get(edge.global()) {
+            Some(points) => points.clone(),
+            None => approx_global_edge(edge, self.tolerance(edge, tolerance)),
         };
 
-        let range = RangeOnCurve { boundary };
+        points_on_edge(edge, points)
+    }
 
-        self.curve().approx(tolerance, range)
+    fn tolerance(&self, edge: &Edge, tolerance: Tolerance) -> Tolerance {
+        self.tolerances
+            .get(&Adjacency::normalize_edge(edge.global()))
+            .copied()
+            .unwrap_or(tolerance)
     }
 }
@@ -4,7 +4,7 @@ use fj_math::Point;
 
 use crate::objects::Face;
 
-use super::{Approx, CycleApprox, Tolerance};
+use super::{Approx, CycleApprox, EdgeApproxCache, Tolerance};
 
 impl Approx for Face {
     type Approximation = FaceApprox;
@@ -15,6 +15,39 @@ impl Approx for Face {
         tolerance: Tolerance,
         (): Self::Params,
     ) -> Self::Approximation {
+        FaceApprox::with_edge_cache(
+            self,
+            tolerance,
+            &EdgeApproxCache::default(),
+        )
+    }
+}
+
+/// An approximation of a [`Face`]
+#[derive(Debug, Eq, PartialEq)]
+pub struct FaceApprox {
+    /// All points that make up the approximation
+    ///
+    /// These could be actual vertices from the model, points that approximate
+    /// an edge, or points that approximate a face.
+    pub points: HashSet<(Point<2>, Point<3>)>,
+
+    /// Approximation of the exterior cycle
+    pub exterior: CycleApprox,
+
+    /// Approximations of the interior cycles
+    pub interiors: HashSet<CycleApprox>,
+}
+
+impl FaceApprox {
+    /// Approximate a face, sharing the approximations of its edges
+    ///
+    /// See [`EdgeApproxCache`].
+    pub fn with_edge_cache(
+        face: &Face,
+        tolerance: Tolerance,
+        cache: &EdgeApproxCache,
+    ) -> Self {
         // Curved faces whose curvature is not fully defined by their edges
         // are not supported yet. For that reason, we can fully ignore `face`'s
         // `surface` field and just pass the edges to `Self::for_edges`.
@@ -28,18 +61,23 @@ impl Approx for Face {
         // would need to provide its own approximation, as the edges that bound
         // it have nothing to do with its curvature.
 
+        // A tolerance that was set on the face takes precedence. Edges that
+        // are shared with neighboring faces might be approximated more finely,
+        // if the cache knows about those.
+        let tolerance = face.tolerance().unwrap_or(tolerance);
+
         let mut points = HashSet::new();
         let mut exteriors = Vec::new();
         let mut interiors = HashSet::new();
 
-        for cycle in self.exteriors() {
-            let cycle = cycle.approx(tolerance, ());
+        for cycle in face.exteriors() {
+            let cycle = CycleApprox::with_edge_cache(cycle, tolerance, cache);
 
             points.extend(cycle.points.iter().copied());
             exteriors.push(cycle);
         }
-        for cycle in self.interiors() {
-            let cycle = cycle.approx(tolerance, ());
+        for cycle in face.interiors() {
+            let cycle = CycleApprox::with_edge_cache(cycle, tolerance, cache);
 
             points.extend(cycle.points.iter().copied());
             interiors.insert(cycle);
@@ -57,7 +95,7 @@ impl Approx for Face {
             "Approximation only supports faces with one exterior cycle",
         );
 
-        FaceApprox {
+        Self {
             points,
             exterior,
             interiors,
@@ -65,30 +103,14 @@ impl Approx for Face {
     }
 }
 
-/// An approximation of a [`Face`]
-#[derive(Debug, Eq, PartialEq)]
-pub struct FaceApprox {
-    /// All points that make up the approximation
-    ///
-    /// These could be actual vertices from the model, points that approximate
-    /// an edge, or points that approximate a face.
-    pub points: HashSet<(Point<2>, Point<3>)>,
-
-    /// Approximation of the exterior cycle
-    pub exterior: CycleApprox,
-
-    /// Approximations of the interior cycles
-    pub interiors: HashSet<CycleApprox>,
-}
-
 #[cfg(test)]
 mod tests {
     use fj_math::{Point, Scalar};
     use map_macro::set;
 
     use crate::{
-        algorithms::approx::Approx,
-        objects::{Face, Surface},
+        algorithms::approx::{Approx, EdgeApproxCache},
+        objects::{Cycle, Edge, Face, Surface},
     };
 
     use super::{CycleApprox, FaceApprox, Tolerance};
@@ -138,4 +160,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn for_face_with_tolerance() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let cycle = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+        );
+        let face = Face::new(surface).with_exteriors([cycle]);
+
+        let coarse = Tolerance::from_scalar(0.1)?;
+        let fine = Tolerance::from_scalar(0.001)?;
+
+        let num_points = |face: &Face| face.approx(coarse, ()).points.len();
+
+        assert!(
+            num_points(&face.clone().with_tolerance(Some(fine)))
+                > num_points(&face)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn shared_edge_with_different_tolerances() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let cycle = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+        );
+
+        let coarse = Tolerance::from_scalar(0.1)?;
+        let fine = Tolerance::from_scalar(0.001)?;
+
+        let a = Face::new(surface).with_exteriors([cycle.clone()]);
+        let b = Face::new(surface)
+            .with_exteriors([cycle])
+            .with_tolerance(Some(fine));
+
+        assert_ne!(
+            a.approx(coarse, ()).exterior,
+            b.approx(coarse, ()).exterior
+        );
+
+        let cache = EdgeApproxCache::new([&a, &b], coarse);
+        assert_eq!(
+            FaceApprox::with_edge_cache(&a, coarse, &cache).exterior,
+            FaceApprox::with_edge_cache(&b, coarse, &cache).exterior,
+        );
+
+        Ok(())
+    }
 }
@@ -8,6 +8,7 @@ mod tolerance;
 
 pub use self::{
     cycle::CycleApprox,
+    edge::EdgeApproxCache,
     face::FaceApprox,
     tolerance::{InvalidTolerance, Tolerance},
 };
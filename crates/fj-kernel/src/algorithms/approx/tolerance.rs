@@ -5,6 +5,11 @@ use fj_math::Scalar;
 /// A tolerance value is used during approximation. It defines the maximum
 /// allowed deviation of the approximation from the actual shape.
 ///
+/// In addition to that distance, a tolerance can define further limits for
+/// the approximation of curves. Those are useful for shapes that have small
+/// features, whose approximation would be too coarse, if it were only based on
+/// the distance. The approximation satisfies all limits at once.
+///
 /// The `Tolerance` type enforces that the tolerance value is always larger than
 /// zero, which is an attribute that the approximation code relies on.
 ///
@@ -18,7 +23,12 @@ use fj_math::Scalar;
 /// documentation doesn't provide any actual reasoning for this requirement, I'm
 /// feeling free to just ignore it.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct Tolerance(Scalar);
+pub struct Tolerance {
+    distance: Scalar,
+    angular_deviation: Option<Scalar>,
+    max_edge_length: Option<Scalar>,
+    min_circle_segments: Option<u64>,
+}
 
 impl Tolerance {
     /// Construct a `Tolerance` from a [`Scalar`]
@@ -33,12 +43,113 @@ impl Tolerance {
             return Err(InvalidTolerance(scalar));
         }
 
-        Ok(Self(scalar))
+        Ok(Self {
+            distance: scalar,
+            angular_deviation: None,
+            max_edge_length: None,
+            min_circle_segments: None,
+        })
+    }
+
+    /// Limit the angle between neighboring segments of an approximated curve
+    ///
+    /// The angle is in radians. Returns an error, if it is not larger than
+    /// zero.
+    pub fn with_angular_deviation(
+        mut self,
+        angle: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        let angle = angle.into();
+
+        if angle <= Scalar::ZERO {
+            return Err(InvalidTolerance(angle));
+        }
+
+        self.angular_deviation = Some(angle);
+        Ok(self)
+    }
+
+    /// Limit the length of the segments of an approximated edge
+    ///
+    /// Returns an error, if the length is not larger than zero.
+    pub fn with_max_edge_length(
+        mut self,
+        length: impl Into<Scalar>,
+    ) -> Result<Self, InvalidTolerance> {
+        let length = length.into();
+
+        if length <= Scalar::ZERO {
+            return Err(InvalidTolerance(length));
+        }
+
+        self.max_edge_length = Some(length);
+        Ok(self)
+    }
+
+    /// Require a minimum number of segments for the approximation of a circle
+    ///
+    /// Arcs get a number of segments that is proportional to the angle they
+    /// cover. Circles are always approximated with at least 3 segments, so
+    /// values below that don't have any effect.
+    pub fn with_min_circle_segments(mut self, segments: u64) -> Self {
+        self.min_circle_segments = Some(segments);
+        self
     }
 
     /// Return the [`Scalar`] that defines the tolerance
     pub fn inner(&self) -> Scalar {
-        self.0
+        self.distance
+    }
+
+    /// Return the maximum angle between neighboring segments, if defined
+    pub fn angular_deviation(&self) -> Option<Scalar> {
+        self.angular_deviation
+    }
+
+    /// Return the maximum length of edge segments, if defined
+    pub fn max_edge_length(&self) -> Option<Scalar> {
+        self.max_edge_length
+    }
+
+    /// Return the minimum number of segments per circle, if defined
+    pub fn min_circle_segments(&self) -> Option<u64> {
+        self.min_circle_segments
+    }
+
+    /// Combine this tolerance with another one
+    ///
+    /// The result satisfies both tolerances. It has the smaller distance, and
+    /// the stricter of each limit that is defined by either of them.
+    pub fn finer(&self, other: &Self) -> Self {
+        fn stricter<T: Ord>(
+            a: Option<T>,
+            b: Option<T>,
+            pick: fn(T, T) -> T,
+        ) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(pick(a, b)),
+                (a, b) => a.or(b),
+            }
+        }
+
+        Self {
+            distance: self.distance.min(other.distance),
+            angular_deviation: stricter(
+                self.angular_deviation,
+                other.angular_deviation,
+                Ord::min,
+            ),
+            max_edge_length: stricter(
+                self.max_edge_length,
+                other.max_edge_length,
+                Ord::min,
+            ),
+            min_circle_segments: stricter(
+                self.min_circle_segments,
+                other.min_circle_segments,
+                Ord::max,
+            ),
+        }
     }
 }
 
@@ -0,0 +1,984 @@
+//! Rounding and chamfering of edges
+//!
+//! Blending an edge replaces it with a new face that connects the two faces
+//! next to the edge. Those faces, as well as the faces at either end of the
+//! edge, are trimmed to make room for the new face.
+
+use fj_interop::mesh::Color;
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::objects::{
+    Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalEdge, GlobalVertex,
+    Shell, Solid, Surface, SweptCurve, Vertex, VerticesOfEdge,
+};
+
+use super::{adjacency::Adjacency, approx::Tolerance, intersect::Intersect};
+
+/// Round or chamfer the provided edges of a solid
+///
+/// The edges are blended one after the other. Each one must be a straight
+/// edge, that is shared by two planar faces and whose vertices are each shared
+/// by exactly one more planar face. Blended edges must not share vertices
+/// with each other.
+///
+/// # Limitations
+///
+/// Only convex edges can be blended. Concave edges, which would require adding
+/// material instead of removing it, are rejected.
+///
+/// All blends are cylindrical or planar. Toroidal or spherical blends, which
+/// would be required at vertices where blended edges meet, are not supported.
+/// That is why blended edges must not share vertices. For the same reason,
+/// fillets require the faces at either end of the edge to be perpendicular to
+/// it, as they are bounded by circular arcs.
+pub fn blend_edges(
+    solid: &Solid,
+    edges: impl IntoIterator<Item = GlobalEdge>,
+    kind: BlendKind,
+) -> Result<Solid, BlendError> {
+    if kind.size() <= Scalar::ZERO {
+        return Err(BlendError::InvalidSize);
+    }
+    if solid
+        .shells()
+        .any(|shell| shell.faces().any(|face| face.triangles().is_some()))
+    {
+        return Err(BlendError::TriangleRepresentation);
+    }
+
+    let mut edges = edges
+        .into_iter()
+        .map(|edge| Adjacency::normalize_edge(&edge))
+        .collect::<Vec<_>>();
+    edges.sort();
+    edges.dedup();
+
+    let mut vertices = Vec::new();
+    for edge in &edges {
+        for vertex in edge.vertices().iter() {
+            if vertices.contains(vertex) {
+                return Err(BlendError::SharedVertex(*vertex));
+            }
+            vertices.push(*vertex);
+        }
+    }
+
+    let mut shells = solid
+        .shells()
+        .map(|shell| shell.faces().cloned().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    for edge in edges {
+        let current = Solid::new().with_shells(
+            shells
+                .iter()
+                .map(|faces| Shell::new().with_faces(faces.iter().cloned())),
+        );
+
+        let faces = shells
+            .iter_mut()
+            .find(|faces| {
+                Adjacency::from_faces(faces.iter())
+                    .faces_of_edge(&edge)
+                    .next()
+                    .is_some()
+            })
+            .ok_or(BlendError::EdgeNotFound(edge))?;
+
+        blend_edge(&current, faces, &edge, kind)?;
+    }
+
+    let shells = shells
+        .into_iter()
+        .map(|faces| Shell::new().with_faces(faces));
+    Ok(Solid::new().with_shells(shells))
+}
+
+/// The kind of blend that [`blend_edges`] creates
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum BlendKind {
+    /// Round the edges, using a cylindrical face
+    Fillet {
+        /// The radius of the cylindrical face
+        radius: Scalar,
+    },
+
+    /// Bevel the edges, using a planar face
+    Chamfer {
+        /// The distance between the edge and the chamfer face's boundaries
+        ///
+        /// This is measured within the faces next to the edge, perpendicular
+        /// to the edge.
+        distance: Scalar,
+    },
+}
+
+impl BlendKind {
+    fn size(&self) -> Scalar {
+        match self {
+            Self::Fillet { radius } => *radius,
+            Self::Chamfer { distance } => *distance,
+        }
+    }
+}
+
+/// An error that can occur while blending edges
+#[derive(Debug, thiserror::Error)]
+pub enum BlendError {
+    /// The radius or distance of the blend is not positive
+    #[error("Blend size must be positive")]
+    InvalidSize,
+
+    /// The solid contains faces in triangle representation
+    ///
+    /// Those don't have any edges that could be blended. They are the result
+    /// of boolean operations that were computed on triangle meshes, for
+    /// example.
+    #[error("Can't blend solid with faces in triangle representation")]
+    TriangleRepresentation,
+
+    /// An edge is not part of the solid
+    #[error("Edge is not part of the solid: {0:?}")]
+    EdgeNotFound(GlobalEdge),
+
+    /// An edge is not shared by exactly two faces
+    #[error("Edge is not shared by exactly two faces: {0:?}")]
+    NonManifoldEdge(GlobalEdge),
+
+    /// Multiple blended edges meet at a vertex
+    #[error("Blended edges must not share vertices: {0:?}")]
+    SharedVertex(GlobalVertex),
+
+    /// The edge is concave
+    #[error("Can't blend concave edge: {0:?}")]
+    ConcaveEdge(GlobalEdge),
+
+    /// The blend does not fit onto the faces next to the edge
+    #[error("Blend is too large for edge: {0:?}")]
+    TooLarge(GlobalEdge),
+
+    /// The geometry around an edge is not supported
+    #[error("Can't blend edge {edge:?}: {reason}")]
+    Unsupported {
+        /// The edge that could not be blended
+        edge: GlobalEdge,
+
+        /// The reason why the edge could not be blended
+        reason: &'static str,
+    },
+}
+
+fn blend_edge(
+    solid: &Solid,
+    faces: &mut Vec<Face>,
+    edge: &GlobalEdge,
+    kind: BlendKind,
+) -> Result<(), BlendError> {
+    let [v0, v1] = match edge.vertices().get() {
+        Some([&a, &b]) => [a, b],
+        None => return Err(unsupported(edge, "edge has no vertices")),
+    };
+    if !matches!(edge.curve().kind(), CurveKind::Line(_)) {
+        return Err(unsupported(edge, "edge is not straight"));
+    }
+
+    // Find the faces next to the edge, and at either end of it.
+    let (indices, [mut f1, mut f2, mut g0, mut g1]) = {
+        let adjacency = Adjacency::from_faces(faces.iter());
+
+        let [f1, f2] = match adjacency.faces_of_edge(edge).collect::<Vec<_>>() {
+            neighbors if neighbors.len() == 2 => [neighbors[0], neighbors[1]],
+            _ => return Err(BlendError::NonManifoldEdge(*edge)),
+        };
+
+        let mut end_faces = Vec::new();
+        for vertex in [v0, v1] {
+            let others = adjacency
+                .faces_of_vertex(&vertex)
+                .into_iter()
+                .filter(|&face| face != f1 && face != f2)
+                .collect::<Vec<_>>();
+
+            if others.len() != 1
+                || adjacency.edges_of_vertex(&vertex).count() != 3
+            {
+                return Err(unsupported(
+                    edge,
+                    "vertices must be shared by exactly three faces",
+                ));
+            }
+
+            end_faces.push(others[0]);
+        }
+        let [g0, g1] = [end_faces[0], end_faces[1]];
+        if g0 == g1 {
+            return Err(unsupported(edge, "edge ends at the same face"));
+        }
+
+        let neighbors = [f1, f2, g0, g1];
+        let indices = neighbors.map(|face| {
+            faces
+                .iter()
+                .position(|f| f == face)
+                .expect("Face from adjacency index must be in list")
+        });
+
+        let mut planar = Vec::new();
+        for face in neighbors {
+            planar.push(PlanarFace::from_face(face).ok_or_else(|| {
+                unsupported(edge, "faces must be bounded by lines or arcs")
+            })?);
+        }
+        let [f1, f2, g0, g1]: [PlanarFace; 4] = match planar.try_into() {
+            Ok(planar) => planar,
+            Err(_) => unreachable!("Converted exactly four faces"),
+        };
+
+        (indices, [f1, f2, g0, g1])
+    };
+
+    let p = [v0, v1].map(|vertex| vertex.position());
+    let e = (p[1] - p[0]).normalize();
+
+    // The vertices at the far end of the edges that continue from the blended
+    // edge's vertices, within the faces next to it.
+    let q1 = f1
+        .far_ends([v0, v1])
+        .ok_or_else(|| unsupported(edge, "unexpected face boundary"))?;
+    let q2 = f2
+        .far_ends([v0, v1])
+        .ok_or_else(|| unsupported(edge, "unexpected face boundary"))?;
+
+    // Directions that point from the edge into the faces next to it,
+    // perpendicular to the edge.
+    let d1 = perpendicular(q1[0].position() - p[0], e)
+        .ok_or_else(|| unsupported(edge, "degenerate face"))?;
+    let d2 = perpendicular(q2[0].position() - p[0], e)
+        .ok_or_else(|| unsupported(edge, "degenerate face"))?;
+
+    let cos = d1.dot(&d2);
+    let m1 = d2 - d1 * cos;
+    let sin = m1.magnitude();
+    if sin <= Scalar::from_f64(1e-9) {
+        return Err(unsupported(edge, "faces next to edge are parallel"));
+    }
+
+    // The distance between the edge and the boundary of the blend, within the
+    // faces next to the edge.
+    let offset = match kind {
+        BlendKind::Chamfer { distance } => distance,
+        BlendKind::Fillet { radius } => radius * (Scalar::ONE + cos) / sin,
+    };
+    let is_fillet = matches!(kind, BlendKind::Fillet { .. });
+
+    let trim_points = |q: [GlobalVertex; 2], d: Vector<3>| {
+        let [a, b] = [0, 1].map(|k| {
+            trim(edge, p[k], q[k].position(), d, offset, e, is_fillet)
+        });
+        Ok::<_, BlendError>([a?, b?])
+    };
+    let p1 = trim_points(q1, d1)?;
+    let p2 = trim_points(q2, d2)?;
+
+    // The material next to a convex edge is between the faces next to it, so
+    // the center of the area removed by a chamfer is within the solid. Next to
+    // a concave edge, that area is outside of it.
+    let center = [p1[0], p1[1], p2[0], p2[1]]
+        .into_iter()
+        .fold(Vector::from([0., 0., 0.]), |sum, vertex| {
+            sum + vertex.position().coords
+        })
+        / 4.;
+    if (solid, &Point { coords: center }).intersect().is_none() {
+        return Err(BlendError::ConcaveEdge(*edge));
+    }
+
+    let (blend, boundaries) = match kind {
+        BlendKind::Chamfer { .. } => {
+            let outward = -(d1 + d2);
+            let face = chamfer_face(p1, p2, outward, f1.color)
+                .with_tolerance(f1.tolerance);
+            (face, [Boundary::Line; 2])
+        }
+        BlendKind::Fillet { radius } => {
+            // The center of the fillet is at `radius` distance from both faces
+            // next to the edge. Move there from the boundary on the first face.
+            let m1 = m1 / sin;
+            let centers = [0, 1].map(|k| p1[k].position() + m1 * radius);
+
+            // Choose the start of the arcs such, that the fillet's surface
+            // normal points away from its center.
+            let [starts, ends] = {
+                let a = p1[0].position() - centers[0];
+                let b = p2[0].position() - centers[0];
+
+                if e.cross(&a).dot(&b) > Scalar::ZERO {
+                    [p1, p2]
+                } else {
+                    [p2, p1]
+                }
+            };
+
+            let circles = [0, 1].map(|k| {
+                let a = starts[k].position() - centers[k];
+                Circle::new(centers[k], a, e.cross(&a))
+            });
+
+            let face = fillet_face(circles, starts, ends, f1.color)
+                .with_tolerance(f1.tolerance);
+            let boundaries = circles.map(|circle| Boundary::Arc { circle });
+            (face, boundaries)
+        }
+    };
+
+    for ((vertex, a), b) in [v0, v1].into_iter().zip(p1).zip(p2) {
+        f1.replace_vertex(vertex, a);
+        f2.replace_vertex(vertex, b);
+    }
+
+    let trims = [0, 1].map(|k| [(q1[k], p1[k]), (q2[k], p2[k])]);
+    if !g0.split_corner(v0, trims[0], boundaries[0])
+        || !g1.split_corner(v1, trims[1], boundaries[1])
+    {
+        return Err(unsupported(edge, "unexpected face at end of edge"));
+    }
+
+    for (index, face) in indices.into_iter().zip([f1, f2, g0, g1]) {
+        faces[index] = face.into_face();
+    }
+    faces.push(blend);
+
+    Ok(())
+}
+
+/// Compute the point where a face next to the blended edge is trimmed
+///
+/// The point lies on the edge from `vertex` to `far_end`, at `offset` distance
+/// from the blended edge.
+fn trim(
+    edge: &GlobalEdge,
+    vertex: Point<3>,
+    far_end: Point<3>,
+    into_face: Vector<3>,
+    offset: Scalar,
+    along_edge: Vector<3>,
+    is_fillet: bool,
+) -> Result<GlobalVertex, BlendError> {
+    let along = far_end - vertex;
+    let length = along.magnitude();
+    let direction = along / length;
+
+    if is_fillet && direction.dot(&along_edge).abs() > Scalar::from_f64(1e-9) {
+        return Err(unsupported(
+            edge,
+            "faces at the ends of a fillet must be perpendicular to the edge",
+        ));
+    }
+
+    let cos = direction.dot(&into_face);
+    if cos <= Scalar::ZERO {
+        return Err(unsupported(edge, "unexpected face boundary"));
+    }
+
+    let distance = offset / cos;
+    if distance >= length {
+        return Err(BlendError::TooLarge(*edge));
+    }
+
+    Ok(GlobalVertex::from_position(vertex + direction * distance))
+}
+
+fn chamfer_face(
+    p1: [GlobalVertex; 2],
+    p2: [GlobalVertex; 2],
+    outward: Vector<3>,
+    color: Color,
+) -> Face {
+    let origin = p1[0].position();
+    let u = (p1[1].position() - origin).normalize();
+    let mut v = perpendicular(p2[0].position() - origin, u)
+        .expect("Chamfer boundaries must not coincide");
+    if u.cross(&v).dot(&outward) < Scalar::ZERO {
+        v = -v;
+    }
+    let plane = Plane { origin, u, v };
+
+    let mut corners = [p1[0], p1[1], p2[1], p2[0]]
+        .map(|vertex| Corner {
+            vertex,
+            next: Boundary::Line,
+        })
+        .to_vec();
+
+    // Make sure the cycle is counter-clockwise in surface coordinates, same as
+    // the ones created by the builders.
+    let points = corners
+        .iter()
+        .map(|corner| plane.point_to_surface_coords(corner.vertex.position()))
+        .collect::<Vec<_>>();
+    let mut area = Scalar::ZERO;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area = area + a.u * b.v - b.u * a.v;
+    }
+    if area < Scalar::ZERO {
+        corners.reverse();
+    }
+
+    PlanarFace {
+        plane,
+        color,
+        exteriors: vec![corners],
+        interiors: Vec::new(),
+    }
+    .into_face()
+}
+
+fn fillet_face(
+    circles: [Circle<3>; 2],
+    starts: [GlobalVertex; 2],
+    ends: [GlobalVertex; 2],
+    color: Color,
+) -> Face {
+    // The surface coordinates of the fillet are the angle around the circle,
+    // and the position along the edge. As the arcs start at `u == 0`, the
+    // circle coordinates are identical to `u`.
+    let surface = Surface::SweptCurve(SweptCurve {
+        curve: CurveKind::Circle(circles[0]),
+        path: circles[1].center() - circles[0].center(),
+    });
+
+    let [end0, end1] =
+        [0, 1].map(|k| arc_coords(&circles[k], [starts[k], ends[k]])[1]);
+    let start = Point::from([0.]);
+
+    let arc =
+        |circle: Circle<3>, v: f64, [a, b]: [(Point<1>, GlobalVertex); 2]| {
+            let curve = Curve::new(
+                surface,
+                CurveKind::Line(Line::from_origin_and_direction(
+                    Point::from([0., v]),
+                    Vector::from([1., 0.]),
+                )),
+                GlobalCurve::from_kind(CurveKind::Circle(circle)),
+            );
+            let vertices = VerticesOfEdge::from_vertices([
+                Vertex::new(a.0, curve, a.1),
+                Vertex::new(b.0, curve, b.1),
+            ]);
+
+            Edge::from_curve_and_vertices(curve, vertices)
+        };
+
+    let edges = [
+        arc(circles[0], 0., [(start, starts[0]), (end0, ends[0])]),
+        Edge::build(surface).line_segment_from_vertices([
+            (Point::from([end0.t, Scalar::ZERO]), ends[0]),
+            (Point::from([end1.t, Scalar::ONE]), ends[1]),
+        ]),
+        arc(circles[1], 1., [(end1, ends[1]), (start, starts[1])]),
+        Edge::build(surface).line_segment_from_vertices([
+            (Point::from([0., 1.]), starts[1]),
+            (Point::from([0., 0.]), starts[0]),
+        ]),
+    ];
+
+    Face::new(surface)
+        .with_exteriors([Cycle::new(surface, edges)])
+        .with_color(color)
+}
+
+/// Compute the component of `vector` that is perpendicular to `axis`
+///
+/// Returns a normalized vector, or `None`, if `vector` is parallel to `axis`.
+fn perpendicular(vector: Vector<3>, axis: Vector<3>) -> Option<Vector<3>> {
+    let perpendicular = vector - axis * vector.dot(&axis);
+    if perpendicular.magnitude() == Scalar::ZERO {
+        return None;
+    }
+
+    Some(perpendicular.normalize())
+}
+
+/// Compute the circle coordinates of the ends of an arc
+///
+/// Assumes that the arc is shorter than half of the circle.
+fn arc_coords(
+    circle: &Circle<3>,
+    vertices: [GlobalVertex; 2],
+) -> [Point<1>; 2] {
+    let [a, b] = vertices.map(|vertex| {
+        let vector = vertex.position() - circle.center();
+        vector.dot(&circle.b()).atan2(vector.dot(&circle.a()))
+    });
+
+    let b = if b - a > Scalar::PI {
+        b - Scalar::TAU
+    } else if a - b > Scalar::PI {
+        b + Scalar::TAU
+    } else {
+        b
+    };
+
+    [Point::from([a]), Point::from([b])]
+}
+
+fn unsupported(edge: &GlobalEdge, reason: &'static str) -> BlendError {
+    BlendError::Unsupported {
+        edge: *edge,
+        reason,
+    }
+}
+
+/// A planar face, defined by the global vertices at its corners
+struct PlanarFace {
+    plane: Plane,
+    color: Color,
+    tolerance: Option<Tolerance>,
+    exteriors: Vec<Vec<Corner>>,
+    interiors: Vec<Vec<Corner>>,
+}
+
+impl PlanarFace {
+    fn from_face(face: &Face) -> Option<Self> {
+        if face.triangles().is_some() {
+            return None;
+        }
+
+        let corners = |cycle: &Cycle| {
+            cycle
+                .edges()
+                .map(|edge| {
+                    let [a, b] = edge.vertices().get()?;
+                    let next = match edge.curve().global().kind() {
+                        CurveKind::Line(_) => Boundary::Line,
+                        CurveKind::Circle(circle) => {
+                            // Arcs are reconstructed from their circle and
+                            // vertices, which is only unambiguous if they are
+                            // shorter than half of the circle.
+                            if (b.position() - a.position()).t.abs()
+                                >= Scalar::PI
+                            {
+                                return None;
+                            }
+
+                            Boundary::Arc { circle: *circle }
+                        }
+                    };
+
+                    Some(Corner {
+                        vertex: *a.global(),
+                        next,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()
+        };
+
+        Some(Self {
+            plane: Plane::from_surface(face.surface())?,
+            color: face.color(),
+            tolerance: face.tolerance(),
+            exteriors: face.exteriors().map(corners).collect::<Option<_>>()?,
+            interiors: face.interiors().map(corners).collect::<Option<_>>()?,
+        })
+    }
+
+    fn cycles(&self) -> impl Iterator<Item = &Vec<Corner>> + '_ {
+        self.exteriors.iter().chain(self.interiors.iter())
+    }
+
+    fn cycles_mut(&mut self) -> impl Iterator<Item = &mut Vec<Corner>> + '_ {
+        self.exteriors.iter_mut().chain(self.interiors.iter_mut())
+    }
+
+    /// Find the far ends of the straight boundaries that continue from an edge
+    ///
+    /// `edge` are the vertices of an edge of this face. For each of them, the
+    /// vertex at the far end of the other boundary that starts there is
+    /// returned.
+    fn far_ends(&self, edge: [GlobalVertex; 2]) -> Option<[GlobalVertex; 2]> {
+        let [a, b] = edge;
+        let far_ends = [self.neighbor(a, b)?, self.neighbor(b, a)?];
+
+        match far_ends {
+            [(a, Boundary::Line), (b, Boundary::Line)] => Some([a, b]),
+            _ => None,
+        }
+    }
+
+    /// Find the neighbor of `vertex` that is not `other`
+    ///
+    /// Also returns the boundary that connects `vertex` to that neighbor.
+    fn neighbor(
+        &self,
+        vertex: GlobalVertex,
+        other: GlobalVertex,
+    ) -> Option<(GlobalVertex, Boundary)> {
+        for cycle in self.cycles() {
+            let n = cycle.len();
+
+            if let Some(i) = cycle.iter().position(|c| c.vertex == vertex) {
+                let prev = &cycle[(i + n - 1) % n];
+                let next = &cycle[(i + 1) % n];
+
+                if next.vertex == other {
+                    return Some((prev.vertex, prev.next));
+                }
+                if prev.vertex == other {
+                    return Some((next.vertex, cycle[i].next));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn replace_vertex(&mut self, vertex: GlobalVertex, new: GlobalVertex) {
+        for cycle in self.cycles_mut() {
+            for corner in cycle {
+                if corner.vertex == vertex {
+                    corner.vertex = new;
+                }
+            }
+        }
+    }
+
+    /// Replace a corner with two corners, connected by `boundary`
+    ///
+    /// Each of the new corners is provided along with the neighbor of the
+    /// original corner whose boundary it lies on.
+    fn split_corner(
+        &mut self,
+        vertex: GlobalVertex,
+        new: [(GlobalVertex, GlobalVertex); 2],
+        boundary: Boundary,
+    ) -> bool {
+        for cycle in self.cycles_mut() {
+            let n = cycle.len();
+
+            if let Some(i) = cycle.iter().position(|c| c.vertex == vertex) {
+                let prev = cycle[(i + n - 1) % n].vertex;
+                let next = cycle[(i + 1) % n].vertex;
+
+                let [a, b] = match new {
+                    [(p, a), (q, b)] if p == prev && q == next => [a, b],
+                    [(p, b), (q, a)] if p == next && q == prev => [a, b],
+                    _ => return false,
+                };
+
+                let corner = cycle[i];
+                cycle[i] = Corner {
+                    vertex: a,
+                    next: boundary,
+                };
+                cycle.insert(
+                    i + 1,
+                    Corner {
+                        vertex: b,
+                        next: corner.next,
+                    },
+                );
+
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn into_face(self) -> Face {
+        let surface = self.plane.surface();
+
+        let exteriors = self
+            .exteriors
+            .iter()
+            .map(|corners| self.plane.cycle(corners))
+            .collect::<Vec<_>>();
+        let interiors = self
+            .interiors
+            .iter()
+            .map(|corners| self.plane.cycle(corners))
+            .collect::<Vec<_>>();
+
+        Face::new(surface)
+            .with_exteriors(exteriors)
+            .with_interiors(interiors)
+            .with_color(self.color)
+            .with_tolerance(self.tolerance)
+    }
+}
+
+/// A corner of a [`PlanarFace`]
+#[derive(Clone, Copy)]
+struct Corner {
+    vertex: GlobalVertex,
+
+    /// The boundary that connects this corner to the next one
+    next: Boundary,
+}
+
+#[derive(Clone, Copy)]
+enum Boundary {
+    Line,
+
+    /// An arc around the circle, shorter than half of it
+    Arc {
+        circle: Circle<3>,
+    },
+}
+
+/// A plane with an orthonormal coordinate system
+///
+/// Using an orthonormal coordinate system allows for arcs to be defined as
+/// circles in surface coordinates.
+#[derive(Clone, Copy)]
+pub(super) struct Plane {
+    origin: Point<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+}
+
+impl Plane {
+    /// Construct a plane that is equivalent to the provided surface
+    ///
+    /// The normal of the returned plane points into the same direction as the
+    /// surface's. Returns `None`, if the surface is not a plane.
+    pub(super) fn from_surface(surface: &Surface) -> Option<Self> {
+        let surface = match surface {
+            Surface::SweptCurve(surface) => surface,
+        };
+        let line = match surface.curve {
+            CurveKind::Line(line) => line,
+            CurveKind::Circle(_) => return None,
+        };
+
+        let u = line.direction().normalize();
+        let v = perpendicular(surface.path, u)?;
+
+        Some(Self {
+            origin: line.origin(),
+            u,
+            v,
+        })
+    }
+
+    /// Compute the normal of the plane
+    pub(super) fn normal(&self) -> Vector<3> {
+        self.u.cross(&self.v)
+    }
+
+    pub(super) fn surface(&self) -> Surface {
+        Surface::SweptCurve(SweptCurve {
+            curve: CurveKind::Line(Line::from_origin_and_direction(
+                self.origin,
+                self.u,
+            )),
+            path: self.v,
+        })
+    }
+
+    pub(super) fn point_to_surface_coords(&self, point: Point<3>) -> Point<2> {
+        let vector = point - self.origin;
+        Point::from([vector.dot(&self.u), vector.dot(&self.v)])
+    }
+
+    fn vector_to_surface_coords(&self, vector: Vector<3>) -> Vector<2> {
+        Vector::from([vector.dot(&self.u), vector.dot(&self.v)])
+    }
+
+    fn cycle(&self, corners: &[Corner]) -> Cycle {
+        let surface = self.surface();
+
+        let edges = corners.iter().enumerate().map(|(i, corner)| {
+            let next = corners[(i + 1) % corners.len()];
+            let vertices = [corner.vertex, next.vertex].map(|vertex| {
+                (self.point_to_surface_coords(vertex.position()), vertex)
+            });
+
+            match corner.next {
+                Boundary::Line => {
+                    Edge::build(surface).line_segment_from_vertices(vertices)
+                }
+                Boundary::Arc { circle } => self.arc(circle, vertices),
+            }
+        });
+
+        Cycle::new(surface, edges)
+    }
+
+    fn arc(
+        &self,
+        circle: Circle<3>,
+        [a, b]: [(Point<2>, GlobalVertex); 2],
+    ) -> Edge {
+        let circle_local = Circle::new(
+            self.point_to_surface_coords(circle.center()),
+            self.vector_to_surface_coords(circle.a()),
+            self.vector_to_surface_coords(circle.b()),
+        );
+        let curve = Curve::new(
+            self.surface(),
+            CurveKind::Circle(circle_local),
+            GlobalCurve::from_kind(CurveKind::Circle(circle)),
+        );
+
+        let [t_a, t_b] = arc_coords(&circle, [a.1, b.1]);
+        let vertices = VerticesOfEdge::from_vertices([
+            Vertex::new(t_a, curve, a.1),
+            Vertex::new(t_b, curve, b.1),
+        ]);
+
+        Edge::from_curve_and_vertices(curve, vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Scalar, Triangle};
+
+    use crate::{
+        algorithms::{adjacency::Adjacency, approx::Tolerance, triangulate},
+        builder::fixtures::prism,
+        iter::ObjectIters,
+        objects::{Face, GlobalEdge, GlobalVertex, Shell, Solid},
+    };
+
+    use super::{blend_edges, BlendError, BlendKind};
+
+    #[test]
+    fn chamfer() -> anyhow::Result<()> {
+        let solid = Solid::build().cube_from_edge_length(2.);
+        let edge = find_edge(&solid, [[-1., 1., 1.], [1., 1., 1.]]);
+
+        let chamfered = blend_edges(
+            &solid,
+            [edge],
+            BlendKind::Chamfer {
+                distance: Scalar::from(0.5),
+            },
+        )?;
+
+        assert_eq!(chamfered.face_iter().count(), 7);
+        assert_eq!(chamfered.global_vertex_iter().count(), 10);
+        assert!(chamfered
+            .global_vertex_iter()
+            .any(|vertex| vertex.position() == [1., 0.5, 1.].into()));
+        assert!(chamfered
+            .global_vertex_iter()
+            .any(|vertex| vertex.position() == [1., 1., 0.5].into()));
+
+        let adjacency = Adjacency::from_solid(&chamfered);
+        assert_eq!(adjacency.edges().count(), 15);
+        assert!(adjacency.is_closed_manifold());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fillet() -> anyhow::Result<()> {
+        let solid = Solid::build().cube_from_edge_length(2.);
+        let edge = find_edge(&solid, [[-1., 1., 1.], [1., 1., 1.]]);
+
+        let filleted = blend_edges(
+            &solid,
+            [edge],
+            BlendKind::Fillet {
+                radius: Scalar::from(0.5),
+            },
+        )?;
+
+        assert_eq!(filleted.face_iter().count(), 7);
+        assert_eq!(filleted.global_vertex_iter().count(), 10);
+
+        let adjacency = Adjacency::from_solid(&filleted);
+        assert_eq!(adjacency.edges().count(), 15);
+        assert!(adjacency.is_closed_manifold());
+
+        // The fillet must be approximated by more than the two triangles that
+        // a chamfer would get.
+        let faces = filleted.face_iter().cloned().collect();
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let mesh = triangulate(faces, tolerance);
+        assert!(mesh.triangles().count() > 7 * 2 + 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let solid = Solid::build().cube_from_edge_length(2.);
+        let a = find_edge(&solid, [[-1., 1., 1.], [1., 1., 1.]]);
+        let b = find_edge(&solid, [[1., 1., 1.], [1., -1., 1.]]);
+
+        let chamfer = BlendKind::Chamfer {
+            distance: Scalar::from(0.5),
+        };
+
+        assert!(matches!(
+            blend_edges(&solid, [a, b], chamfer),
+            Err(BlendError::SharedVertex(_))
+        ));
+        assert!(matches!(
+            blend_edges(
+                &solid,
+                [a],
+                BlendKind::Chamfer {
+                    distance: Scalar::from(3.),
+                }
+            ),
+            Err(BlendError::TooLarge(_))
+        ));
+
+        let triangles = Face::from_triangles(vec![(
+            Triangle::from([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]]),
+            Color([255, 0, 0, 255]),
+        )]);
+        let solid = Solid::new().with_shells([Shell::new()
+            .with_faces(solid.face_iter().cloned().chain([triangles]))]);
+        assert!(matches!(
+            blend_edges(&solid, [a], chamfer),
+            Err(BlendError::TriangleRepresentation)
+        ));
+    }
+
+    #[test]
+    fn concave_edge() -> anyhow::Result<()> {
+        let solid = prism(
+            [[0., 0.], [2., 0.], [2., 1.], [1., 1.], [1., 2.], [0., 2.]],
+            2.,
+        )?;
+
+        let chamfer = BlendKind::Chamfer {
+            distance: Scalar::from(0.25),
+        };
+
+        let concave = find_edge(&solid, [[1., 1., 0.], [1., 1., 2.]]);
+        assert!(matches!(
+            blend_edges(&solid, [concave], chamfer),
+            Err(BlendError::ConcaveEdge(_))
+        ));
+
+        let convex = find_edge(&solid, [[2., 0., 0.], [2., 0., 2.]]);
+        let chamfered = blend_edges(&solid, [convex], chamfer)?;
+        assert!(Adjacency::from_solid(&chamfered).is_closed_manifold());
+
+        Ok(())
+    }
+
+    fn find_edge(solid: &Solid, vertices: [[f64; 3]; 2]) -> GlobalEdge {
+        let vertices = vertices.map(GlobalVertex::from_position);
+
+        solid
+            .edge_iter()
+            .map(|edge| *edge.global())
+            .find(|edge| {
+                let mut a = edge.vertices().get_or_panic().map(|v| *v);
+                let mut b = vertices;
+                a.sort();
+                b.sort();
+                a == b
+            })
+            .expect("Edge not found")
+    }
+}
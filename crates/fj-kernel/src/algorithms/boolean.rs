@@ -0,0 +1,1031 @@
+//! Boolean operations on solids and triangle meshes
+//!
+//! See [`brep_boolean`] and [`mesh_boolean`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::mesh::{FaceId, Mesh, Triangle};
+use fj_math::{Aabb, Point, Scalar, Vector};
+use spade::HasPosition;
+
+use crate::objects::{CurveKind, Face};
+
+/// The relative distance at which the sides of a triangle are classified
+///
+/// This is relative to the size of the bounding box of both meshes. It needs
+/// to be larger than the numerical error of coplanar triangles, but small
+/// enough to not step over thin features of the other mesh.
+const CLASSIFICATION_OFFSET: f64 = 1e-8;
+
+/// The kind of a boolean operation
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum BooleanOperation {
+    /// Everything that is in either mesh
+    Union,
+
+    /// Everything that is in the first mesh, but not in the second
+    Difference,
+
+    /// Everything that is in both meshes
+    Intersection,
+}
+
+/// Compute a boolean operation of two solids, using their exact geometry
+///
+/// The solids are given by their faces. Only solids that are apart from each
+/// other are supported right now, as their result consists of the original
+/// faces, without any intersections. For all other solids,
+/// [`BooleanError::Unsupported`] is returned, and [`mesh_boolean`] can be used
+/// as a fallback.
+pub fn brep_boolean(
+    a: &[Face],
+    b: &[Face],
+    operation: BooleanOperation,
+) -> Result<Vec<Face>, BooleanError> {
+    let is_apart = match (solid_bounds(a), solid_bounds(b)) {
+        (Some(a), Some(b)) => (0..3).any(|i| {
+            a.max.coords.components[i] < b.min.coords.components[i]
+                || b.max.coords.components[i] < a.min.coords.components[i]
+        }),
+        // One of the solids is empty.
+        _ => true,
+    };
+
+    if !is_apart {
+        return Err(BooleanError::Unsupported("solids are not apart"));
+    }
+
+    let faces = match operation {
+        BooleanOperation::Union => a.iter().chain(b).cloned().collect(),
+        BooleanOperation::Difference => a.to_vec(),
+        BooleanOperation::Intersection => Vec::new(),
+    };
+
+    Ok(faces)
+}
+
+/// An error that can occur while computing a boolean operation of solids
+#[derive(Debug, thiserror::Error)]
+pub enum BooleanError {
+    /// The geometry is not supported by the operation
+    #[error("Boolean operation not supported: {0}")]
+    Unsupported(&'static str),
+}
+
+/// Compute a box that contains all of the provided faces
+///
+/// Circles are bounded by the box around their full sphere, so the result
+/// might be larger than necessary. Returns `None`, if there are no faces.
+fn solid_bounds(faces: &[Face]) -> Option<Aabb<3>> {
+    let mut points = Vec::new();
+
+    for face in faces {
+        if let Some(triangles) = face.triangles() {
+            points.extend(
+                triangles.iter().flat_map(|(triangle, _)| triangle.points()),
+            );
+            continue;
+        }
+
+        for edge in face.all_cycles().flat_map(|cycle| cycle.edges()) {
+            match edge.global().curve().kind() {
+                CurveKind::Circle(circle) => {
+                    let radius = circle.a().magnitude();
+                    let extent = Vector::from([radius, radius, radius]);
+                    points.push(circle.center() - extent);
+                    points.push(circle.center() + extent);
+                }
+                CurveKind::Line(_) => {
+                    points.extend(
+                        edge.vertices()
+                            .iter()
+                            .map(|vertex| vertex.global().position()),
+                    );
+                }
+            }
+        }
+    }
+
+    if points.is_empty() {
+        return None;
+    }
+
+    Some(Aabb::<3>::from_points(points))
+}
+
+/// Compute a boolean operation of two triangle meshes
+///
+/// This is a fallback for shapes that the kernel can't combine exactly. Both
+/// meshes must be closed and consistently oriented, with their triangles
+/// facing outward. Edges are identified by the indices of their vertices, so
+/// vertices on shared edges must be identical. Weld the meshes first, using
+/// [`Mesh::weld`], if that is not the case.
+///
+/// Exact predicates decide which triangles intersect. Those triangles are
+/// split along the intersection, and their neighbors along the same edges, so
+/// the result has no cracks. Each resulting triangle is then classified as
+/// inside or outside of the other mesh, using the generalized winding number,
+/// which tolerates small defects in the other mesh.
+///
+/// Triangles keep their color, normals, and face. The faces of `b` are
+/// renumbered to follow those of `a`, so they remain distinct.
+pub fn mesh_boolean(
+    a: &Mesh<Point<3>>,
+    b: &Mesh<Point<3>>,
+    operation: BooleanOperation,
+) -> Mesh<Point<3>> {
+    let a = Input::new(a);
+    let b = Input::new(b);
+
+    let mut cuts_a = Cuts::new(a.triangles.len());
+    let mut cuts_b = Cuts::new(b.triangles.len());
+    find_intersections(&a, &b, &mut cuts_a, &mut cuts_b);
+
+    let offset = classification_offset(&a, &b);
+    let face_offset = a
+        .attributes
+        .iter()
+        .filter_map(|triangle| triangle.face)
+        .map(|FaceId(face)| face + 1)
+        .max()
+        .unwrap_or(0);
+
+    let mut mesh = Mesh::new();
+
+    for triangle in a.split(&cuts_a) {
+        let [front, back] = classify(&triangle, &b, offset);
+
+        let keep = match operation {
+            BooleanOperation::Union => !front,
+            BooleanOperation::Difference => !back,
+            BooleanOperation::Intersection => back,
+        };
+
+        if keep {
+            mesh.push(triangle);
+        }
+    }
+
+    for triangle in b.split(&cuts_b) {
+        let [front, back] = classify(&triangle, &a, offset);
+
+        let triangle = Triangle {
+            face: triangle.face.map(|FaceId(face)| FaceId(face + face_offset)),
+            ..triangle
+        };
+
+        // Triangles on the boundary of `a` are decided by the triangles of
+        // `a`. Only triangles that are entirely on one side of `a` are kept.
+        match operation {
+            BooleanOperation::Union => {
+                if !front && !back {
+                    mesh.push(triangle);
+                }
+            }
+            BooleanOperation::Difference => {
+                if front && back {
+                    if let Some(triangle) = reverse(triangle) {
+                        mesh.push(triangle);
+                    }
+                }
+            }
+            BooleanOperation::Intersection => {
+                if front && back {
+                    mesh.push(triangle);
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// A mesh, in a form that is suitable for finding intersections
+struct Input {
+    vertices: Vec<Point<3>>,
+    triangles: Vec<[usize; 3]>,
+    attributes: Vec<Triangle>,
+}
+
+impl Input {
+    fn new(mesh: &Mesh<Point<3>>) -> Self {
+        let vertices = mesh.vertices().collect();
+        let indices = mesh
+            .indices()
+            .map(|index| index as usize)
+            .collect::<Vec<_>>();
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect();
+        let attributes = mesh.triangles().collect();
+
+        Self {
+            vertices,
+            triangles,
+            attributes,
+        }
+    }
+
+    fn points(&self, triangle: usize) -> [Point<3>; 3] {
+        self.triangles[triangle].map(|index| self.vertices[index])
+    }
+
+    /// Split the triangles along the provided cuts
+    ///
+    /// Triangles that aren't affected by any cut are returned unchanged.
+    fn split(&self, cuts: &Cuts) -> Vec<Triangle> {
+        let mut triangles = Vec::new();
+
+        for (i, &attributes) in self.attributes.iter().enumerate() {
+            let indices = self.triangles[i];
+            let points = self.points(i);
+
+            let has_edge_points = [[0, 1], [1, 2], [2, 0]]
+                .into_iter()
+                .map(|[j, k]| edge(indices[j], indices[k]))
+                .any(|edge| cuts.edge_points.contains_key(&edge));
+
+            if cuts.segments[i].is_empty() && !has_edge_points {
+                triangles.push(attributes);
+                continue;
+            }
+
+            let pieces = split_triangle(
+                points,
+                indices,
+                &cuts.segments[i],
+                &cuts.edge_points,
+            );
+
+            for piece in pieces {
+                let inner = match fj_math::Triangle::from_points(piece) {
+                    Ok(inner) => inner,
+                    Err(_) => continue,
+                };
+                let normals = attributes.normals.map(|normals| {
+                    piece.map(|point| interpolate(points, normals, point))
+                });
+
+                triangles.push(Triangle {
+                    inner,
+                    normals,
+                    ..attributes
+                });
+            }
+        }
+
+        triangles
+    }
+}
+
+/// Where the triangles of a mesh need to be split
+struct Cuts {
+    /// The segments along which each triangle is split
+    segments: Vec<Vec<[Point<3>; 2]>>,
+
+    /// The points at which edges are split, by the vertices of the edge
+    ///
+    /// Edges are shared between triangles, so all of them are split at the
+    /// same points.
+    edge_points: BTreeMap<[usize; 2], Vec<Point<3>>>,
+}
+
+impl Cuts {
+    fn new(num_triangles: usize) -> Self {
+        Self {
+            segments: vec![Vec::new(); num_triangles],
+            edge_points: BTreeMap::new(),
+        }
+    }
+
+    fn push_edge_point(&mut self, edge: [usize; 2], point: Point<3>) {
+        self.edge_points.entry(edge).or_default().push(point);
+    }
+}
+
+/// A point where a triangle crosses the plane of another triangle
+#[derive(Clone, Copy)]
+struct Crossing {
+    point: Point<3>,
+
+    /// The edge that crosses the plane, if it's not a vertex on the plane
+    edge: Option<[usize; 2]>,
+}
+
+fn find_intersections(
+    a: &Input,
+    b: &Input,
+    cuts_a: &mut Cuts,
+    cuts_b: &mut Cuts,
+) {
+    let bounds_a = (0..a.triangles.len())
+        .map(|i| bounds(a.points(i)))
+        .collect::<Vec<_>>();
+    let bounds_b = (0..b.triangles.len())
+        .map(|i| bounds(b.points(i)))
+        .collect::<Vec<_>>();
+
+    for (ia, (min_a, max_a)) in bounds_a.iter().enumerate() {
+        for (ib, (min_b, max_b)) in bounds_b.iter().enumerate() {
+            let overlap = (0..3).all(|axis| {
+                min_a[axis] <= max_b[axis] && min_b[axis] <= max_a[axis]
+            });
+
+            if overlap {
+                intersect_triangles(a, ia, b, ib, cuts_a, cuts_b);
+            }
+        }
+    }
+}
+
+fn intersect_triangles(
+    a: &Input,
+    ia: usize,
+    b: &Input,
+    ib: usize,
+    cuts_a: &mut Cuts,
+    cuts_b: &mut Cuts,
+) {
+    let points_a = a.points(ia);
+    let points_b = b.points(ib);
+
+    let signs_a = points_a.map(|point| orient(points_b, point));
+    let signs_b = points_b.map(|point| orient(points_a, point));
+
+    if on_one_side(signs_a) || on_one_side(signs_b) {
+        return;
+    }
+    if signs_a.iter().all(|&sign| sign == 0.) {
+        intersect_coplanar_triangles(a, ia, b, ib, cuts_a, cuts_b);
+        return;
+    }
+
+    let crossings_a =
+        plane_crossings(points_a, a.triangles[ia], signs_a, points_b);
+    let crossings_b =
+        plane_crossings(points_b, b.triangles[ib], signs_b, points_a);
+
+    let (crossings_a, crossings_b) = match (crossings_a, crossings_b) {
+        (Some(crossings_a), Some(crossings_b)) => (crossings_a, crossings_b),
+        _ => return,
+    };
+
+    // Both pairs of crossings are on the line where the planes intersect.
+    // The triangles intersect where the segments between them overlap.
+    let direction = normal(points_a).cross(&normal(points_b));
+    let position = |crossing: &Crossing| direction.dot(&crossing.point.coords);
+
+    let [start_a, end_a] = sorted_along(crossings_a, position);
+    let [start_b, end_b] = sorted_along(crossings_b, position);
+
+    let start = if position(&start_a) >= position(&start_b) {
+        (start_a, true)
+    } else {
+        (start_b, false)
+    };
+    let end = if position(&end_a) <= position(&end_b) {
+        (end_a, true)
+    } else {
+        (end_b, false)
+    };
+
+    if position(&start.0) >= position(&end.0) {
+        return;
+    }
+
+    let segment = [start.0.point, end.0.point];
+    cuts_a.segments[ia].push(segment);
+    cuts_b.segments[ib].push(segment);
+
+    // If an edge of one triangle lies in the plane of the other, the
+    // crossings of the other triangle can end up on that edge.
+    let edge_in_plane_a = edge_in_plane(a.triangles[ia], signs_a);
+    let edge_in_plane_b = edge_in_plane(b.triangles[ib], signs_b);
+
+    for (crossing, is_from_a) in [start, end] {
+        let (own, other, edge_in_other_plane) = if is_from_a {
+            (&mut *cuts_a, &mut *cuts_b, edge_in_plane_b)
+        } else {
+            (&mut *cuts_b, &mut *cuts_a, edge_in_plane_a)
+        };
+
+        if let Some(edge) = crossing.edge {
+            own.push_edge_point(edge, crossing.point);
+        }
+        if let Some(edge) = edge_in_other_plane {
+            other.push_edge_point(edge, crossing.point);
+        }
+    }
+}
+
+/// Find the edge of a triangle that lies in the plane of another, if any
+fn edge_in_plane(indices: [usize; 3], signs: [f64; 3]) -> Option<[usize; 2]> {
+    let on_plane = (0..3).filter(|&i| signs[i] == 0.).collect::<Vec<_>>();
+
+    match on_plane.as_slice() {
+        &[i, j] => Some(edge(indices[i], indices[j])),
+        _ => None,
+    }
+}
+
+fn intersect_coplanar_triangles(
+    a: &Input,
+    ia: usize,
+    b: &Input,
+    ib: usize,
+    cuts_a: &mut Cuts,
+    cuts_b: &mut Cuts,
+) {
+    let points_a = a.points(ia);
+    let points_b = b.points(ib);
+    let normal = normal(points_a);
+
+    let edges_a = triangle_edges(points_a, a.triangles[ia]);
+    let edges_b = triangle_edges(points_b, b.triangles[ib]);
+
+    // The points where the edges of both triangles cross. They are computed
+    // from the edges alone, so neighboring triangles compute the same ones.
+    let mut crossings_a = vec![Vec::new(); 3];
+    let mut crossings_b = vec![Vec::new(); 3];
+
+    for (i, &(key_a, [p, q])) in edges_a.iter().enumerate() {
+        for (j, &(key_b, [r, s])) in edges_b.iter().enumerate() {
+            let cross = opposite(side(p, q, r, normal), side(p, q, s, normal))
+                && opposite(side(r, s, p, normal), side(r, s, q, normal));
+            if !cross {
+                continue;
+            }
+
+            let point = line_intersection([p, q], [r, s]);
+
+            cuts_a.push_edge_point(key_a, point);
+            cuts_b.push_edge_point(key_b, point);
+            crossings_a[i].push(point);
+            crossings_b[j].push(point);
+        }
+    }
+
+    // The parts of each edge that are within the other triangle split the
+    // other triangle.
+    let parts_within = |edges: &[([usize; 2], [Point<3>; 2])],
+                        crossings: &[Vec<Point<3>>],
+                        triangle: [Point<3>; 3]| {
+        let mut segments = Vec::new();
+
+        for ((_, [p, q]), crossings) in edges.iter().zip(crossings) {
+            let mut points = crossings.clone();
+            for vertex in [*p, *q] {
+                if contains(triangle, vertex, normal) {
+                    points.push(vertex);
+                }
+            }
+
+            points.sort_by_key(|&point| (point - *p).magnitude());
+            if let (Some(&first), Some(&last)) = (points.first(), points.last())
+            {
+                if first != last {
+                    segments.push([first, last]);
+                }
+            }
+        }
+
+        segments
+    };
+
+    let segments_a =
+        parts_within(edges_b.as_slice(), crossings_b.as_slice(), points_a);
+    let segments_b =
+        parts_within(edges_a.as_slice(), crossings_a.as_slice(), points_b);
+
+    cuts_a.segments[ia].extend(segments_a);
+    cuts_b.segments[ib].extend(segments_b);
+}
+
+/// Compute where a triangle crosses the plane of another triangle
+///
+/// Returns `None`, if the triangle only touches the plane in a single point.
+fn plane_crossings(
+    points: [Point<3>; 3],
+    indices: [usize; 3],
+    signs: [f64; 3],
+    plane: [Point<3>; 3],
+) -> Option<[Crossing; 2]> {
+    let mut crossings = Vec::new();
+
+    for (&point, &sign) in points.iter().zip(&signs) {
+        if sign == 0. {
+            crossings.push(Crossing { point, edge: None });
+        }
+    }
+
+    for (i, (key, [p, q])) in
+        triangle_edges(points, indices).into_iter().enumerate()
+    {
+        if opposite(signs[i], signs[(i + 1) % 3]) {
+            crossings.push(Crossing {
+                point: line_plane_intersection([p, q], plane),
+                edge: Some(key),
+            });
+        }
+    }
+
+    match crossings.as_slice() {
+        &[a, b] => Some([a, b]),
+        _ => None,
+    }
+}
+
+/// Split a triangle along the provided segments
+///
+/// The points on the triangle's edges are inserted too, so the result matches
+/// up with the neighbors that share those edges.
+fn split_triangle(
+    points: [Point<3>; 3],
+    indices: [usize; 3],
+    segments: &[[Point<3>; 2]],
+    edge_points: &BTreeMap<[usize; 2], Vec<Point<3>>>,
+) -> Vec<[Point<3>; 3]> {
+    use spade::Triangulation as _;
+
+    let frame = Frame::new(points);
+    let mut triangulation = Cdt::new();
+
+    let mut boundary = Vec::new();
+    for (i, &p) in points.iter().enumerate() {
+        boundary.push(p);
+
+        let key = edge(indices[i], indices[(i + 1) % 3]);
+        if let Some(on_edge) = edge_points.get(&key) {
+            let mut on_edge = on_edge.clone();
+            on_edge.sort_by_key(|&point| (point - p).magnitude());
+            on_edge.dedup();
+
+            boundary.extend(on_edge);
+        }
+    }
+
+    let boundary = boundary
+        .into_iter()
+        .filter_map(|point| frame.insert(&mut triangulation, point))
+        .collect::<Vec<_>>();
+
+    let mut constraints = Vec::new();
+    for (i, &from) in boundary.iter().enumerate() {
+        constraints.push([from, boundary[(i + 1) % boundary.len()]]);
+    }
+    for &[p, q] in segments {
+        if let (Some(from), Some(to)) = (
+            frame.insert(&mut triangulation, p),
+            frame.insert(&mut triangulation, q),
+        ) {
+            constraints.push([from, to]);
+        }
+    }
+
+    for [from, to] in constraints {
+        // Segments that are nearly identical might cross each other, due to
+        // numerical error. Skip those, instead of failing the whole split.
+        if from == to || !triangulation.can_add_constraint(from, to) {
+            continue;
+        }
+
+        triangulation.add_constraint(from, to);
+    }
+
+    let outline = points.map(|point| frame.project(point));
+
+    triangulation
+        .inner_faces()
+        .map(|face| face.vertices().map(|vertex| *vertex.data()))
+        .filter(|triangle| {
+            // Points that are slightly outside of the triangle, due to
+            // numerical error, can lead to slivers outside of it.
+            let [a, b, c] = triangle.map(|vertex| vertex.position);
+            let center =
+                Point::from([(a.u + b.u + c.u) / 3., (a.v + b.v + c.v) / 3.]);
+
+            (0..3).all(|i| {
+                let p = outline[i];
+                let q = outline[(i + 1) % 3];
+                let [d, e] = [q - p, center - p];
+                d.u * e.v - d.v * e.u > Scalar::ZERO
+            })
+        })
+        .map(|triangle| triangle.map(|vertex| vertex.point))
+        .collect()
+}
+
+/// A 2-dimensional coordinate system in the plane of a triangle
+struct Frame {
+    origin: Point<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+}
+
+impl Frame {
+    fn new([a, b, c]: [Point<3>; 3]) -> Self {
+        let normal = (b - a).cross(&(c - a)).normalize();
+        let u = (b - a).normalize();
+        let v = normal.cross(&u);
+
+        Self { origin: a, u, v }
+    }
+
+    fn project(&self, point: Point<3>) -> Point<2> {
+        let offset = point - self.origin;
+        Point::from([offset.dot(&self.u), offset.dot(&self.v)])
+    }
+
+    fn insert(
+        &self,
+        triangulation: &mut Cdt,
+        point: Point<3>,
+    ) -> Option<spade::handles::FixedVertexHandle> {
+        use spade::Triangulation as _;
+
+        triangulation
+            .insert(Vertex {
+                position: self.project(point),
+                point,
+            })
+            .ok()
+    }
+}
+
+type Cdt = spade::ConstrainedDelaunayTriangulation<Vertex>;
+
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    position: Point<2>,
+    point: Point<3>,
+}
+
+impl HasPosition for Vertex {
+    type Scalar = Scalar;
+
+    fn position(&self) -> spade::Point2<Self::Scalar> {
+        spade::Point2 {
+            x: self.position.u,
+            y: self.position.v,
+        }
+    }
+}
+
+/// Decide whether the front and back of a triangle are inside of a mesh
+fn classify(triangle: &Triangle, mesh: &Input, offset: Scalar) -> [bool; 2] {
+    let [a, b, c] = triangle.inner.points();
+    let center = a + ((b - a) + (c - a)) / 3.;
+    let normal = triangle.inner.normal();
+
+    [center + normal * offset, center - normal * offset]
+        .map(|point| winding_number(point, mesh) >= 0.5)
+}
+
+/// Compute the generalized winding number of a point with respect to a mesh
+///
+/// This is the sum of the solid angles of all triangles, as seen from the
+/// point, divided by the full solid angle. It is 1 within a closed mesh, and 0
+/// outside of it.
+fn winding_number(point: Point<3>, mesh: &Input) -> f64 {
+    let mut total = 0.;
+
+    for i in 0..mesh.triangles.len() {
+        let [a, b, c] = mesh.points(i).map(|vertex| vertex - point);
+        let [la, lb, lc] = [a, b, c].map(|v| v.magnitude());
+
+        let det = a.dot(&b.cross(&c));
+        let div =
+            la * lb * lc + a.dot(&b) * lc + b.dot(&c) * la + c.dot(&a) * lb;
+
+        total += 2. * det.into_f64().atan2(div.into_f64());
+    }
+
+    total / (4. * std::f64::consts::PI)
+}
+
+fn classification_offset(a: &Input, b: &Input) -> Scalar {
+    let mut vertices = a.vertices.iter().chain(&b.vertices);
+
+    let first = match vertices.next() {
+        Some(vertex) => vertex.coords.components,
+        None => return Scalar::from_f64(CLASSIFICATION_OFFSET),
+    };
+
+    let (min, max) = vertices.fold((first, first), |(mut min, mut max), v| {
+        for (i, &coord) in v.coords.components.iter().enumerate() {
+            min[i] = min[i].min(coord);
+            max[i] = max[i].max(coord);
+        }
+        (min, max)
+    });
+
+    let size = Vector::from(max) - Vector::from(min);
+    size.magnitude().max(Scalar::ONE) * CLASSIFICATION_OFFSET
+}
+
+fn reverse(triangle: Triangle) -> Option<Triangle> {
+    let [a, b, c] = triangle.inner.points();
+    let inner = fj_math::Triangle::from_points([a, c, b]).ok()?;
+    let normals = triangle.normals.map(|[a, b, c]| [-a, -c, -b]);
+
+    Some(Triangle {
+        inner,
+        normals,
+        ..triangle
+    })
+}
+
+/// Interpolate the normals of a triangle at a point within it
+fn interpolate(
+    [a, b, c]: [Point<3>; 3],
+    normals: [Vector<3>; 3],
+    point: Point<3>,
+) -> Vector<3> {
+    let normal = (b - a).cross(&(c - a));
+    let area = normal.dot(&normal);
+
+    let weights = [[b, c], [c, a], [a, b]]
+        .map(|[p, q]| (q - p).cross(&(point - p)).dot(&normal) / area);
+
+    let interpolated = normals[0] * weights[0]
+        + normals[1] * weights[1]
+        + normals[2] * weights[2];
+
+    interpolated.normalize()
+}
+
+/// The edges of a triangle, with their keys and canonically ordered points
+///
+/// The points of each edge are ordered by the indices of its vertices, not by
+/// the triangle's winding, so triangles sharing an edge compute the same
+/// intersections with it. The edges are in order, starting at the first
+/// vertex.
+fn triangle_edges(
+    points: [Point<3>; 3],
+    indices: [usize; 3],
+) -> [([usize; 2], [Point<3>; 2]); 3] {
+    [0, 1, 2].map(|i| {
+        let j = (i + 1) % 3;
+
+        let (p, q) = if indices[i] <= indices[j] {
+            (points[i], points[j])
+        } else {
+            (points[j], points[i])
+        };
+
+        (edge(indices[i], indices[j]), [p, q])
+    })
+}
+
+fn edge(a: usize, b: usize) -> [usize; 2] {
+    if a <= b {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+fn orient([a, b, c]: [Point<3>; 3], point: Point<3>) -> f64 {
+    let [a, b, c, d] = [a, b, c, point]
+        .map(|point| point.coords.components.map(Scalar::into_f64));
+
+    robust_predicates::orient3d(&a, &b, &c, &d)
+}
+
+fn on_one_side(signs: [f64; 3]) -> bool {
+    signs.iter().all(|&sign| sign > 0.) || signs.iter().all(|&sign| sign < 0.)
+}
+
+fn opposite(a: f64, b: f64) -> bool {
+    (a > 0. && b < 0.) || (a < 0. && b > 0.)
+}
+
+fn side(p: Point<3>, q: Point<3>, point: Point<3>, normal: Vector<3>) -> f64 {
+    (q - p).cross(&(point - p)).dot(&normal).into_f64()
+}
+
+fn contains(
+    [a, b, c]: [Point<3>; 3],
+    point: Point<3>,
+    normal: Vector<3>,
+) -> bool {
+    // The triangle might be oriented opposite to the normal.
+    let sides =
+        [[a, b], [b, c], [c, a]].map(|[p, q]| side(p, q, point, normal));
+    on_one_side(sides)
+}
+
+fn normal([a, b, c]: [Point<3>; 3]) -> Vector<3> {
+    (b - a).cross(&(c - a))
+}
+
+fn sorted_along(
+    [a, b]: [Crossing; 2],
+    position: impl Fn(&Crossing) -> Scalar,
+) -> [Crossing; 2] {
+    if position(&a) <= position(&b) {
+        [a, b]
+    } else {
+        [b, a]
+    }
+}
+
+fn line_plane_intersection(
+    [p, q]: [Point<3>; 2],
+    [a, b, c]: [Point<3>; 3],
+) -> Point<3> {
+    let normal = (b - a).cross(&(c - a));
+    let distance_p = normal.dot(&(p - a));
+    let distance_q = normal.dot(&(q - a));
+
+    p + (q - p) * (distance_p / (distance_p - distance_q))
+}
+
+/// Compute the intersection of two coplanar lines
+fn line_intersection([p, q]: [Point<3>; 2], [r, s]: [Point<3>; 2]) -> Point<3> {
+    let d1 = q - p;
+    let d2 = s - r;
+    let w = p - r;
+
+    let a = d1.dot(&d1);
+    let b = d1.dot(&d2);
+    let c = d2.dot(&d2);
+    let d = d1.dot(&w);
+    let e = d2.dot(&w);
+
+    p + d1 * ((b * e - c * d) / (a * c - b * b))
+}
+
+fn bounds(points: [Point<3>; 3]) -> ([Scalar; 3], [Scalar; 3]) {
+    let mut min = points[0].coords.components;
+    let mut max = points[0].coords.components;
+
+    for point in points {
+        for (i, &coord) in point.coords.components.iter().enumerate() {
+            min[i] = min[i].min(coord);
+            max[i] = max[i].max(coord);
+        }
+    }
+
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        analysis::analyze,
+        mesh::{Color, Mesh},
+    };
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::transform::TransformObject,
+        iter::ObjectIters,
+        objects::{Face, Solid},
+    };
+
+    use super::{brep_boolean, mesh_boolean, BooleanError, BooleanOperation};
+
+    #[test]
+    fn brep_apart() -> anyhow::Result<()> {
+        let faces = |offset: [f64; 3]| {
+            Solid::build()
+                .cube_from_edge_length(1.)
+                .translate(offset)
+                .face_iter()
+                .cloned()
+                .collect::<Vec<Face>>()
+        };
+        let a = faces([0., 0., 0.]);
+        let b = faces([2., 0., 0.]);
+
+        let union = brep_boolean(&a, &b, BooleanOperation::Union)?;
+        assert_eq!(union.len(), 12);
+
+        let difference = brep_boolean(&a, &b, BooleanOperation::Difference)?;
+        assert_eq!(difference, a);
+
+        let intersection =
+            brep_boolean(&a, &b, BooleanOperation::Intersection)?;
+        assert!(intersection.is_empty());
+
+        // Solids that touch or overlap need the fallback.
+        let c = faces([1., 0., 0.]);
+        assert!(matches!(
+            brep_boolean(&a, &c, BooleanOperation::Union),
+            Err(BooleanError::Unsupported(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn disjoint_meshes() {
+        let a = cube([0., 0., 0.], 1.);
+        let b = cube([2., 0., 0.], 1.);
+
+        let union = mesh_boolean(&a, &b, BooleanOperation::Union);
+        assert_eq!(union.triangles().count(), 24);
+        assert_volume(&union, 2.);
+
+        let intersection = mesh_boolean(&a, &b, BooleanOperation::Intersection);
+        assert_eq!(intersection.triangles().count(), 0);
+
+        let difference = mesh_boolean(&a, &b, BooleanOperation::Difference);
+        assert_eq!(difference.triangles().count(), 12);
+        assert_volume(&difference, 1.);
+    }
+
+    #[test]
+    fn overlapping_meshes() {
+        let a = cube([0., 0., 0.], 1.);
+        let b = cube([0.5, 0.3, 0.4], 1.);
+
+        let overlap = 0.5 * 0.7 * 0.6;
+
+        for (operation, volume) in [
+            (BooleanOperation::Union, 2. - overlap),
+            (BooleanOperation::Intersection, overlap),
+            (BooleanOperation::Difference, 1. - overlap),
+        ] {
+            let mut mesh = mesh_boolean(&a, &b, operation);
+            assert_volume(&mesh, volume);
+
+            mesh.weld(Scalar::from_f64(1e-9));
+            let analysis = analyze(&mesh);
+            assert!(analysis.is_closed_manifold(), "{operation:?}");
+            assert!(analysis.flipped_triangles.is_empty(), "{operation:?}");
+        }
+    }
+
+    #[test]
+    fn contained_mesh() {
+        let a = cube([0., 0., 0.], 2.);
+        let b = cube([0.5, 0.5, 0.5], 1.);
+
+        let difference = mesh_boolean(&a, &b, BooleanOperation::Difference);
+        assert_eq!(difference.triangles().count(), 24);
+        assert_volume(&difference, 7.);
+
+        let union = mesh_boolean(&a, &b, BooleanOperation::Union);
+        assert_volume(&union, 8.);
+    }
+
+    #[test]
+    fn touching_meshes() {
+        let a = cube([0., 0., 0.], 1.);
+        let b = cube([1., 0.25, 0.25], 0.5);
+
+        // The faces where the cubes touch are removed.
+        let union = mesh_boolean(&a, &b, BooleanOperation::Union);
+        assert_volume(&union, 1.125);
+
+        let intersection = mesh_boolean(&a, &b, BooleanOperation::Intersection);
+        assert_volume(&intersection, 0.);
+    }
+
+    fn assert_volume(mesh: &Mesh<Point<3>>, expected: f64) {
+        let volume = mesh
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                a.coords.dot(&b.coords.cross(&c.coords)) / 6.
+            })
+            .fold(Scalar::ZERO, |a, b| a + b);
+
+        assert!(
+            (volume - Scalar::from_f64(expected)).abs()
+                < Scalar::from_f64(1e-9),
+            "expected volume {expected}, got {volume}"
+        );
+    }
+
+    fn cube(min: [f64; 3], size: f64) -> Mesh<Point<3>> {
+        let min = Vector::from(min);
+        let corner = |x: f64, y: f64, z: f64| {
+            Point::from([x * size, y * size, z * size]) + min
+        };
+
+        let [a, b, c, d] = [[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+        let bottom = [a, b, c, d].map(|[x, y]| corner(x, y, 0.));
+        let top = [a, b, c, d].map(|[x, y]| corner(x, y, 1.));
+
+        let mut quads = vec![
+            [bottom[0], bottom[3], bottom[2], bottom[1]],
+            [top[0], top[1], top[2], top[3]],
+        ];
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            quads.push([bottom[i], bottom[j], top[j], top[i]]);
+        }
+
+        let mut mesh = Mesh::new();
+        for [a, b, c, d] in quads {
+            mesh.push_triangle([a, b, c], Color::default());
+            mesh.push_triangle([a, c, d], Color::default());
+        }
+
+        mesh
+    }
+}
@@ -0,0 +1,210 @@
+//! Deviation of triangle meshes from the faces they approximate
+//!
+//! See [`mesh_deviation`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::{
+    debug::{DebugInfo, DeviationCheck},
+    deviation::{FaceDeviation, MeshDeviation},
+    mesh::{FaceId, Mesh},
+};
+use fj_math::{Point, Scalar};
+
+use crate::objects::Face;
+
+use super::{approx::Tolerance, distance::face_point_distance};
+
+/// The number of segments each triangle edge is divided into, for sampling
+///
+/// Samples are taken on a regular grid of barycentric coordinates, which
+/// includes the vertices, points on the edges, and points in the interior of
+/// each triangle.
+const SAMPLE_DIVISIONS: u64 = 4;
+
+/// Measure how far a triangle mesh deviates from the faces it was created from
+///
+/// The triangles of the mesh are matched to the faces using their [`FaceId`],
+/// which is the index of the face in `faces`, as assigned by
+/// [`triangulate()`]. Points are sampled on each triangle, and their distance
+/// to the face is computed, using the exact geometry of the face's surface and
+/// edges. Pass the final mesh, after any refinement or welding, to measure
+/// what is actually exported.
+///
+/// [`triangulate()`]: super::triangulate()
+///
+/// This measures how far the mesh strays from the faces, not the other way
+/// around. Parts of a face that are not covered by the mesh, for example
+/// between a circular edge and its approximation, don't count towards the
+/// deviation.
+///
+/// Faces in triangle representation are skipped, as their exact geometry is
+/// not known. For each face that is analyzed, the sample with the largest
+/// deviation is recorded in the debug info.
+pub fn mesh_deviation(
+    faces: &[Face],
+    mesh: &Mesh<Point<3>>,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> MeshDeviation {
+    let mut triangles_by_face: BTreeMap<usize, Vec<_>> = BTreeMap::new();
+    for triangle in mesh.triangles() {
+        if let Some(FaceId(index)) = triangle.face {
+            triangles_by_face
+                .entry(index)
+                .or_default()
+                .push(triangle.inner.points());
+        }
+    }
+
+    let mut deviation = MeshDeviation::default();
+
+    for (index, triangles) in triangles_by_face {
+        let face = match faces.get(index) {
+            Some(face) if face.triangles().is_none() => face,
+            _ => continue,
+        };
+        let tolerance = face.tolerance().unwrap_or(tolerance);
+
+        let mut max = Scalar::ZERO;
+        let mut sum = Scalar::ZERO;
+        let mut num_samples = 0;
+        let mut worst_point = None;
+
+        for triangle in triangles {
+            for point in sample_points(triangle) {
+                let distance = face_point_distance(face, point);
+
+                if worst_point.is_none() || distance > max {
+                    max = distance;
+                    worst_point = Some(point);
+                }
+                sum = sum + distance;
+                num_samples += 1;
+            }
+        }
+
+        let worst_point = match worst_point {
+            Some(point) => point,
+            None => continue,
+        };
+
+        debug_info.deviation_checks.push(DeviationCheck {
+            point: worst_point,
+            deviation: max,
+            tolerance: tolerance.inner(),
+        });
+        deviation.faces.push(FaceDeviation {
+            face: index,
+            max,
+            mean: sum / Scalar::from_u64(num_samples),
+            worst_point,
+            num_samples,
+        });
+    }
+
+    deviation
+}
+
+fn sample_points([a, b, c]: [Point<3>; 3]) -> impl Iterator<Item = Point<3>> {
+    (0..=SAMPLE_DIVISIONS).flat_map(move |i| {
+        (0..=SAMPLE_DIVISIONS - i).map(move |j| {
+            let s = i as f64 / SAMPLE_DIVISIONS as f64;
+            let t = j as f64 / SAMPLE_DIVISIONS as f64;
+
+            a + (b - a) * s + (c - a) * t
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        debug::DebugInfo,
+        mesh::{Color, FaceId, Mesh, Triangle},
+    };
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate},
+        objects::{Cycle, Edge, Face, Surface},
+    };
+
+    use super::mesh_deviation;
+
+    #[test]
+    fn planar_face() -> anyhow::Result<()> {
+        let face = Face::build(Surface::xy_plane())
+            .polygon_from_points([[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .into_face();
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let faces = [face];
+        let mesh = triangulate(faces.to_vec(), tolerance);
+        let deviation =
+            mesh_deviation(&faces, &mesh, tolerance, &mut DebugInfo::new());
+
+        assert_eq!(deviation.faces.len(), 1);
+        assert!(deviation.max() < Scalar::from_f64(1e-9));
+
+        Ok(())
+    }
+
+    #[test]
+    fn displaced_mesh() -> anyhow::Result<()> {
+        let face = Face::build(Surface::xy_plane())
+            .polygon_from_points([[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .into_face();
+
+        let [a, b, c] =
+            [[0., 0., 0.5], [2., 0., 0.5], [0., 2., 0.5]].map(Point::from);
+        let mut mesh = Mesh::new();
+        mesh.push(
+            Triangle::new([a, b, c], Color([255, 0, 0, 255]))
+                .with_face(FaceId(0)),
+        );
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let deviation =
+            mesh_deviation(&[face], &mesh, tolerance, &mut DebugInfo::new());
+
+        assert_eq!(deviation.faces.len(), 1);
+        assert!((deviation.max() - Scalar::from_f64(0.5)).abs() < 1e-9.into());
+
+        Ok(())
+    }
+
+    #[test]
+    fn face_with_circular_hole() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let face = Face::new(surface)
+            .with_exteriors([Cycle::build(surface).polygon_from_points([
+                [-2., -2.],
+                [2., -2.],
+                [2., 2.],
+                [-2., 2.],
+            ])])
+            .with_interiors([Cycle::new(
+                surface,
+                [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+            )]);
+
+        let tolerance = Tolerance::from_scalar(0.01)?;
+        let mut debug_info = DebugInfo::new();
+        let faces = [face];
+        let mesh = triangulate(faces.to_vec(), tolerance);
+        let deviation =
+            mesh_deviation(&faces, &mesh, tolerance, &mut debug_info);
+
+        // The triangles cover part of the hole, between the circle and its
+        // approximation.
+        assert!(deviation.max() > Scalar::ZERO);
+        assert!(deviation.max() <= tolerance.inner());
+        assert!(deviation.mean() < deviation.max());
+
+        assert_eq!(debug_info.deviation_checks.len(), 1);
+        assert!(!debug_info.deviation_checks[0].exceeds_tolerance());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,509 @@
+//! Minimum distance queries
+//!
+//! The distance between a point and a solid is computed from the solid's
+//! faces directly. The distance between two solids is computed from their
+//! triangle meshes, which makes its accuracy depend on the tolerance value
+//! used to create those.
+
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::{
+    iter::ObjectIters,
+    objects::{CurveKind, Edge, Face, Solid, Surface},
+};
+
+use super::{
+    approx::Tolerance,
+    intersect::{solid_point::face_contains_point, Intersect},
+    triangulate,
+};
+
+/// Compute the minimum distance between a point and a solid
+///
+/// Returns zero, if the point is inside the solid or on its boundary.
+pub fn distance_to_point(solid: &Solid, point: impl Into<Point<3>>) -> Scalar {
+    let point = point.into();
+
+    if (solid, &point).intersect().is_some() {
+        return Scalar::ZERO;
+    }
+
+    solid
+        .face_iter()
+        .map(|face| face_point_distance(face, point))
+        .fold(Scalar::MAX, Scalar::min)
+}
+
+/// Compute the minimum distance between two solids
+///
+/// Returns zero, if the solids touch or overlap, or if one contains the other.
+///
+/// Both solids are triangulated using the provided tolerance, and the distance
+/// is computed between the resulting triangle meshes. Curved faces are only
+/// accurate up to that tolerance, and so is the result.
+pub fn distance_between_solids(
+    a: &Solid,
+    b: &Solid,
+    tolerance: Tolerance,
+) -> Scalar {
+    let [mesh_a, mesh_b] = [a, b].map(|solid| {
+        let faces = solid.face_iter().cloned().collect();
+        triangulate(faces, tolerance)
+    });
+
+    // If the boundaries of the solids don't intersect, either one of them is
+    // completely contained in the other, or they are apart.
+    let contained = [(a, &mesh_b), (b, &mesh_a)].into_iter().any(
+        |(solid, mesh)| match mesh.vertices().next() {
+            Some(vertex) => (solid, &vertex).intersect().is_some(),
+            None => false,
+        },
+    );
+    if contained {
+        return Scalar::ZERO;
+    }
+
+    let triangles_b = mesh_b
+        .triangles()
+        .map(|triangle| {
+            let triangle = triangle.inner;
+            (triangle, Aabb::<3>::from_points(triangle.points()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut min_distance = Scalar::MAX;
+
+    for triangle_a in mesh_a.triangles() {
+        let triangle_a = triangle_a.inner;
+        let aabb_a = Aabb::<3>::from_points(triangle_a.points());
+
+        for (triangle_b, aabb_b) in &triangles_b {
+            // Skip triangles that can't be closer than what we already have.
+            if aabb_distance(&aabb_a, aabb_b) >= min_distance {
+                continue;
+            }
+
+            min_distance =
+                min_distance.min(triangle_distance(&triangle_a, triangle_b));
+        }
+    }
+
+    min_distance
+}
+
+/// Compute the minimum distance between a point and a face
+pub(super) fn face_point_distance(face: &Face, point: Point<3>) -> Scalar {
+    if let Some(triangles) = face.triangles() {
+        return triangles
+            .iter()
+            .map(|(triangle, _)| triangle_point_distance(triangle, point))
+            .fold(Scalar::MAX, Scalar::min);
+    }
+
+    let surface = match face.surface() {
+        Surface::SweptCurve(surface) => surface,
+    };
+
+    // If the point closest to the surface is within the face, that's it.
+    // Otherwise, the closest point is on the boundary of the face.
+    let (point_surface, distance) = match surface.curve {
+        CurveKind::Line(line) => {
+            let (u, v) = project_onto_plane(
+                point - line.origin(),
+                line.direction(),
+                surface.path,
+            );
+            let point_surface = Point::from([u, v]);
+            let closest =
+                face.surface().point_from_surface_coords(point_surface);
+
+            (point_surface, (point - closest).magnitude())
+        }
+        CurveKind::Circle(_) => {
+            let u = closest_angle_on_swept_circle(face.surface(), point);
+            let on_curve =
+                face.surface().point_from_surface_coords([u, Scalar::ZERO]);
+            let v = (point - on_curve).dot(&surface.path)
+                / surface.path.dot(&surface.path);
+
+            let point_surface = Point::from([u, v]);
+            let closest =
+                face.surface().point_from_surface_coords(point_surface);
+
+            (point_surface, (point - closest).magnitude())
+        }
+    };
+
+    if face_contains_point(face, point_surface) {
+        return distance;
+    }
+
+    face.all_cycles()
+        .flat_map(|cycle| cycle.edges())
+        .map(|edge| edge_point_distance(edge, point))
+        .fold(Scalar::MAX, Scalar::min)
+}
+
+/// Compute the minimum distance between a point and an edge
+pub(super) fn edge_point_distance(edge: &Edge, point: Point<3>) -> Scalar {
+    let vertices = edge.vertices().get();
+
+    match edge.curve().global().kind() {
+        CurveKind::Line(line) => match vertices {
+            Some([a, b]) => segment_point_distance(
+                [a.global().position(), b.global().position()],
+                point,
+            ),
+            None => {
+                let t = (point - line.origin()).dot(&line.direction())
+                    / line.direction().dot(&line.direction());
+                (point - line.point_from_line_coords([t])).magnitude()
+            }
+        },
+        CurveKind::Circle(circle) => {
+            let radius = circle.a().magnitude();
+            let normal = circle.a().cross(&circle.b()).normalize();
+
+            let relative = point - circle.center();
+            let height = relative.dot(&normal);
+            let in_plane = relative - normal * height;
+
+            let angle =
+                in_plane.dot(&circle.b()).atan2(in_plane.dot(&circle.a()));
+            let range = vertices.map(|[a, b]| [a.position().t, b.position().t]);
+
+            if range.map_or(true, |range| angle_in_range(angle, range)) {
+                let radial = in_plane.magnitude() - radius;
+                return Vector::from([height, radial]).magnitude();
+            }
+
+            // The closest point on the circle is not on the arc, so it must be
+            // one of the arc's ends.
+            edge.vertices()
+                .iter()
+                .map(|vertex| (point - vertex.global().position()).magnitude())
+                .fold(Scalar::MAX, Scalar::min)
+        }
+    }
+}
+
+/// Indicate whether an angle is within a range of circle coordinates
+pub(super) fn angle_in_range(angle: Scalar, range: [Scalar; 2]) -> bool {
+    let [a, b] = range;
+    let (min, max) = if a < b { (a, b) } else { (b, a) };
+
+    // Circle coordinates can be outside of `[-pi, pi]`, the range of `atan2`.
+    let turns = ((min - angle) / Scalar::TAU).ceil();
+    let angle = angle + Scalar::TAU * turns;
+
+    angle <= max
+}
+
+/// Express a vector in terms of two directions that span a plane
+///
+/// The vector is projected into the plane first.
+pub(super) fn project_onto_plane(
+    vector: Vector<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+) -> (Scalar, Scalar) {
+    let uu = u.dot(&u);
+    let uv = u.dot(&v);
+    let vv = v.dot(&v);
+    let wu = vector.dot(&u);
+    let wv = vector.dot(&v);
+
+    let det = uu * vv - uv * uv;
+    ((wu * vv - wv * uv) / det, (wv * uu - wu * uv) / det)
+}
+
+/// Find the circle coordinate of the surface line closest to a point
+///
+/// `surface` must be a swept circle. The distance between a point and the
+/// lines that make up the surface is a smooth function of the angle, which is
+/// sampled and then refined.
+fn closest_angle_on_swept_circle(surface: &Surface, point: Point<3>) -> Scalar {
+    const SAMPLES: u64 = 64;
+
+    let path = match surface {
+        Surface::SweptCurve(surface) => surface.path,
+    };
+
+    let distance_squared = |u: Scalar| {
+        let on_curve = surface.point_from_surface_coords([u, Scalar::ZERO]);
+        let relative = point - on_curve;
+        let along_path = relative.dot(&path);
+
+        relative.dot(&relative) - along_path * along_path / path.dot(&path)
+    };
+
+    let step = Scalar::TAU / SAMPLES as f64;
+    let mut best = Scalar::ZERO;
+    for i in 1..SAMPLES {
+        let u = step * i as f64;
+        if distance_squared(u) < distance_squared(best) {
+            best = u;
+        }
+    }
+
+    // The minimum is within one step of the best sample. Narrow it down, using
+    // a ternary search.
+    let mut min = best - step;
+    let mut max = best + step;
+    for _ in 0..100 {
+        let a = min + (max - min) / 3.;
+        let b = max - (max - min) / 3.;
+
+        if distance_squared(a) < distance_squared(b) {
+            max = b;
+        } else {
+            min = a;
+        }
+    }
+
+    (min + max) / 2.
+}
+
+fn aabb_distance(a: &Aabb<3>, b: &Aabb<3>) -> Scalar {
+    let gap = a
+        .min
+        .coords
+        .components
+        .into_iter()
+        .zip(a.max.coords.components)
+        .zip(
+            b.min
+                .coords
+                .components
+                .into_iter()
+                .zip(b.max.coords.components),
+        )
+        .map(|((a_min, a_max), (b_min, b_max))| {
+            (a_min - b_max).max(b_min - a_max).max(Scalar::ZERO)
+        })
+        .collect::<Vec<_>>();
+
+    Vector::from([gap[0], gap[1], gap[2]]).magnitude()
+}
+
+fn triangle_distance(a: &Triangle<3>, b: &Triangle<3>) -> Scalar {
+    let edges = |triangle: &Triangle<3>| {
+        let [p0, p1, p2] = triangle.points();
+        [[p0, p1], [p1, p2], [p2, p0]]
+    };
+
+    let intersect = edges(a)
+        .iter()
+        .any(|&edge| segment_intersects_triangle(edge, b))
+        || edges(b)
+            .iter()
+            .any(|&edge| segment_intersects_triangle(edge, a));
+    if intersect {
+        return Scalar::ZERO;
+    }
+
+    // If the triangles don't intersect, the closest points are either a vertex
+    // and a point on the other triangle, or points on two edges.
+    let vertex_distances = a
+        .points()
+        .into_iter()
+        .map(|point| triangle_point_distance(b, point))
+        .chain(
+            b.points()
+                .into_iter()
+                .map(|point| triangle_point_distance(a, point)),
+        );
+    let edge_distances = edges(a).into_iter().flat_map(|edge_a| {
+        edges(b)
+            .into_iter()
+            .map(move |edge_b| segment_segment_distance(edge_a, edge_b))
+    });
+
+    vertex_distances
+        .chain(edge_distances)
+        .fold(Scalar::MAX, Scalar::min)
+}
+
+fn segment_point_distance(segment: [Point<3>; 2], point: Point<3>) -> Scalar {
+    let [a, b] = segment;
+    let ab = b - a;
+
+    let length_squared = ab.dot(&ab);
+    if length_squared == Scalar::ZERO {
+        return (point - a).magnitude();
+    }
+
+    let t = ((point - a).dot(&ab) / length_squared)
+        .max(Scalar::ZERO)
+        .min(Scalar::ONE);
+
+    (point - (a + ab * t)).magnitude()
+}
+
+fn segment_segment_distance(a: [Point<3>; 2], b: [Point<3>; 2]) -> Scalar {
+    let d1 = a[1] - a[0];
+    let d2 = b[1] - b[0];
+    let r = a[0] - b[0];
+
+    let aa = d1.dot(&d1);
+    let bb = d2.dot(&d2);
+    let f = d2.dot(&r);
+
+    if aa == Scalar::ZERO || bb == Scalar::ZERO {
+        // At least one segment is degenerate. Fall back to the distance
+        // between a point and a segment.
+        return if aa == Scalar::ZERO {
+            segment_point_distance(b, a[0])
+        } else {
+            segment_point_distance(a, b[0])
+        };
+    }
+
+    let c = d1.dot(&r);
+    let ab = d1.dot(&d2);
+    let denom = aa * bb - ab * ab;
+
+    // Find the closest points on the infinite lines, then clamp them to the
+    // segments. If the segments are parallel, any point on `a` will do.
+    let mut s = if denom != Scalar::ZERO {
+        ((ab * f - c * bb) / denom)
+            .max(Scalar::ZERO)
+            .min(Scalar::ONE)
+    } else {
+        Scalar::ZERO
+    };
+    let mut t = (ab * s + f) / bb;
+
+    if t < Scalar::ZERO {
+        t = Scalar::ZERO;
+        s = (-c / aa).max(Scalar::ZERO).min(Scalar::ONE);
+    } else if t > Scalar::ONE {
+        t = Scalar::ONE;
+        s = ((ab - c) / aa).max(Scalar::ZERO).min(Scalar::ONE);
+    }
+
+    ((a[0] + d1 * s) - (b[0] + d2 * t)).magnitude()
+}
+
+pub(super) fn triangle_point_distance(
+    triangle: &Triangle<3>,
+    point: Point<3>,
+) -> Scalar {
+    let [a, b, c] = triangle.points();
+
+    let ab = b - a;
+    let ac = c - a;
+    let normal = ab.cross(&ac);
+
+    // If the projection of the point into the plane of the triangle is within
+    // the triangle, the distance to the plane is the distance to the triangle.
+    let inside = [(a, b), (b, c), (c, a)].into_iter().all(|(start, end)| {
+        (end - start).cross(&(point - start)).dot(&normal) >= Scalar::ZERO
+    });
+    if inside {
+        return ((point - a).dot(&normal) / normal.magnitude()).abs();
+    }
+
+    [[a, b], [b, c], [c, a]]
+        .into_iter()
+        .map(|edge| segment_point_distance(edge, point))
+        .fold(Scalar::MAX, Scalar::min)
+}
+
+fn segment_intersects_triangle(
+    segment: [Point<3>; 2],
+    triangle: &Triangle<3>,
+) -> bool {
+    let [start, end] = segment;
+
+    match ray_triangle_intersection(start, end - start, triangle) {
+        Some((t, barycentric)) => {
+            t >= Scalar::ZERO
+                && t <= Scalar::ONE
+                && barycentric
+                    .into_iter()
+                    .all(|coordinate| coordinate >= Scalar::ZERO)
+        }
+        None => false,
+    }
+}
+
+/// Compute the intersection between a ray and the plane of a triangle
+///
+/// Returns the ray parameter of the intersection point, and its barycentric
+/// coordinates within the triangle. Returns `None`, if the ray is parallel to
+/// the triangle.
+pub(super) fn ray_triangle_intersection(
+    origin: Point<3>,
+    direction: Vector<3>,
+    triangle: &Triangle<3>,
+) -> Option<(Scalar, [Scalar; 3])> {
+    let [a, b, c] = triangle.points();
+
+    let ab = b - a;
+    let ac = c - a;
+
+    let p = direction.cross(&ac);
+    let det = ab.dot(&p);
+    if det == Scalar::ZERO {
+        return None;
+    }
+
+    let s = origin - a;
+    let u = s.dot(&p) / det;
+
+    let q = s.cross(&ab);
+    let v = direction.dot(&q) / det;
+    let t = ac.dot(&q) / det;
+
+    Some((t, [Scalar::ONE - u - v, u, v]))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{approx::Tolerance, transform::TransformObject},
+        builder::fixtures::cube,
+    };
+
+    use super::{distance_between_solids, distance_to_point};
+
+    #[test]
+    fn point() -> anyhow::Result<()> {
+        let solid = cube()?;
+
+        assert_eq!(distance_to_point(&solid, [1., 1., 1.]), Scalar::ZERO);
+        assert_eq!(distance_to_point(&solid, [1., 1., 2.]), Scalar::ZERO);
+        assert_eq!(distance_to_point(&solid, [4., 1., 1.]), Scalar::from(2.));
+        assert_eq!(
+            distance_to_point(&solid, [3., 3., 1.]),
+            Scalar::from(2_f64.sqrt())
+        );
+        assert_eq!(
+            distance_to_point(&solid, [-1., -2., -2.]),
+            Scalar::from(3.)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn solids() -> anyhow::Result<()> {
+        let a = cube()?;
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        let apart = a.clone().translate([3., 0., 1.]);
+        let distance = distance_between_solids(&a, &apart, tolerance);
+        assert!((distance - Scalar::ONE).abs() < Scalar::from(1e-12));
+
+        let overlapping = a.clone().translate([1., 1., 1.]);
+        assert_eq!(
+            distance_between_solids(&a, &overlapping, tolerance),
+            Scalar::ZERO
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,442 @@
+//! Hollowing of solids
+//!
+//! Hollowing turns a solid into a thin-walled one, by offsetting its faces
+//! inward. Faces can be removed, to create openings in the wall.
+
+use std::{collections::BTreeMap, ptr};
+
+use fj_math::{Scalar, Transform, Vector};
+
+use crate::objects::{Cycle, Edge, Face, GlobalVertex, Shell, Solid};
+
+use super::{adjacency::Adjacency, blend::Plane};
+
+/// Hollow out a solid, leaving a wall of the provided thickness
+///
+/// All faces of the solid are offset inward by `thickness`, and the offset
+/// faces are added to the solid with reversed orientation. Faces are expected
+/// to be oriented outward, meaning the normal of their surface points away
+/// from the solid.
+///
+/// If none of a shell's faces are open, the result contains the original shell
+/// as the outer shell, and a new inner shell made from the offset faces.
+/// Otherwise, the open faces are replaced with rims that connect the original
+/// faces to the offset ones, and both are returned as a single shell.
+///
+/// # Limitations
+///
+/// Only solids made of planar faces, which are bounded by straight edges, are
+/// supported. Every vertex must be defined by the planes of the faces that
+/// meet there, which means that no more than three faces with different
+/// orientations may meet at a vertex. Open faces must not have holes, and must
+/// not share vertices with each other.
+pub fn hollow<'r>(
+    solid: &Solid,
+    thickness: impl Into<Scalar>,
+    open_faces: impl IntoIterator<Item = &'r Face>,
+) -> Result<Solid, HollowError> {
+    let thickness = thickness.into();
+    if thickness <= Scalar::ZERO {
+        return Err(HollowError::InvalidThickness);
+    }
+    if solid
+        .shells()
+        .any(|shell| shell.faces().any(|face| face.triangles().is_some()))
+    {
+        return Err(HollowError::TriangleRepresentation);
+    }
+
+    let open_faces = open_faces.into_iter().collect::<Vec<_>>();
+    for face in &open_faces {
+        if !solid
+            .shells()
+            .any(|shell| shell.faces().any(|f| f == *face))
+        {
+            return Err(HollowError::FaceNotFound);
+        }
+    }
+
+    let mut shells = Vec::new();
+    for shell in solid.shells() {
+        let open = shell
+            .faces()
+            .map(|face| open_faces.contains(&face))
+            .collect::<Vec<_>>();
+
+        shells.extend(hollow_shell(shell, thickness, &open)?);
+    }
+
+    Ok(Solid::new().with_shells(shells))
+}
+
+/// An error that can occur while hollowing a solid
+#[derive(Debug, thiserror::Error)]
+pub enum HollowError {
+    /// The thickness of the wall is not positive
+    #[error("Wall thickness must be positive")]
+    InvalidThickness,
+
+    /// The solid contains faces in triangle representation
+    ///
+    /// Those can't be offset, as they don't have any surfaces. They are the
+    /// result of boolean operations that were computed on triangle meshes, for
+    /// example.
+    #[error("Can't hollow solid with faces in triangle representation")]
+    TriangleRepresentation,
+
+    /// A face that should be opened is not part of the solid
+    #[error("Open face is not part of the solid")]
+    FaceNotFound,
+
+    /// A shell of the solid is not a closed manifold
+    #[error("Can't hollow a shell that is not closed")]
+    NotClosed,
+
+    /// The wall is so thick, that the offset faces would be inverted
+    #[error("Wall is too thick for the solid")]
+    TooThick,
+
+    /// The geometry of the solid is not supported
+    #[error("Can't hollow solid: {0}")]
+    Unsupported(&'static str),
+}
+
+fn hollow_shell(
+    shell: &Shell,
+    thickness: Scalar,
+    open: &[bool],
+) -> Result<Vec<Shell>, HollowError> {
+    let faces = shell.faces().collect::<Vec<_>>();
+
+    let adjacency = Adjacency::from_shell(shell);
+    if adjacency.faces().count() != faces.len() {
+        return Err(HollowError::Unsupported(
+            "face with triangle representation",
+        ));
+    }
+    if !adjacency.is_closed_manifold() {
+        return Err(HollowError::NotClosed);
+    }
+
+    let planes = faces
+        .iter()
+        .map(|face| Plane::from_surface(face.surface()))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(HollowError::Unsupported("non-planar face"))?;
+
+    for (face, &open) in faces.iter().zip(open) {
+        if open && face.interiors().next().is_some() {
+            return Err(HollowError::Unsupported("open face with holes"));
+        }
+    }
+
+    let mut offset_vertices = BTreeMap::new();
+    for vertex in adjacency.vertices() {
+        let mut constraints = Vec::new();
+        let mut num_open = 0;
+
+        for face in adjacency.faces_of_vertex(vertex) {
+            let i = faces
+                .iter()
+                .position(|f| ptr::eq(*f, face))
+                .expect("Face of vertex must be part of shell");
+
+            // The offset vertex must lie on the offset planes of the faces
+            // that remain closed, but stays on the planes of open faces.
+            let distance = if open[i] {
+                num_open += 1;
+                Scalar::ZERO
+            } else {
+                -thickness
+            };
+
+            constraints.push((planes[i].normal(), distance));
+        }
+
+        if num_open > 1 {
+            return Err(HollowError::Unsupported("open faces share vertex"));
+        }
+
+        let offset = offset_vertex(&constraints, thickness).ok_or(
+            HollowError::Unsupported("vertex not defined by adjacent faces"),
+        )?;
+
+        offset_vertices.insert(
+            *vertex,
+            GlobalVertex::from_position(vertex.position() + offset),
+        );
+    }
+
+    // If the offset is larger than the solid, the offset edges flip around.
+    for edge in adjacency.edges() {
+        let [a, b] = edge
+            .vertices()
+            .get()
+            .ok_or(HollowError::Unsupported("continuous edge"))?;
+        let [a_offset, b_offset] = [a, b].map(|vertex| offset_vertices[vertex]);
+
+        let original = b.position() - a.position();
+        let offset = b_offset.position() - a_offset.position();
+
+        if original.dot(&offset) <= Scalar::ZERO {
+            return Err(HollowError::TooThick);
+        }
+    }
+
+    let offset_cycle = |plane: &Plane, cycle: &Cycle| {
+        let vertices = cycle_vertices(cycle)
+            .into_iter()
+            .map(|vertex| offset_vertices[&vertex]);
+        polygon(plane, vertices)
+    };
+
+    let mut outer = Vec::new();
+    let mut inner = Vec::new();
+
+    for ((face, plane), &open) in faces.iter().zip(&planes).zip(open) {
+        if open {
+            // The rim stays on the open face's plane, and spans the area
+            // between the original and the offset boundary.
+            let exteriors = face
+                .exteriors()
+                .map(|cycle| polygon(plane, cycle_vertices(cycle)))
+                .collect::<Vec<_>>();
+            let interiors = face
+                .exteriors()
+                .map(|cycle| offset_cycle(plane, cycle))
+                .collect::<Vec<_>>();
+
+            outer.push(
+                Face::new(plane.surface())
+                    .with_exteriors(exteriors)
+                    .with_interiors(interiors)
+                    .with_color(face.color())
+                    .with_tolerance(face.tolerance()),
+            );
+
+            continue;
+        }
+
+        outer.push((*face).clone());
+
+        let surface = face
+            .surface()
+            .transform(&Transform::translation(plane.normal() * -thickness))
+            .reverse();
+        let plane = Plane::from_surface(&surface)
+            .expect("Offset of planar surface must be planar");
+
+        let exteriors = face
+            .exteriors()
+            .map(|cycle| offset_cycle(&plane, cycle))
+            .collect::<Vec<_>>();
+        let interiors = face
+            .interiors()
+            .map(|cycle| offset_cycle(&plane, cycle))
+            .collect::<Vec<_>>();
+
+        inner.push(
+            Face::new(plane.surface())
+                .with_exteriors(exteriors)
+                .with_interiors(interiors)
+                .with_color(face.color())
+                .with_tolerance(face.tolerance()),
+        );
+    }
+
+    if open.contains(&true) {
+        outer.extend(inner);
+        Ok(vec![Shell::new().with_faces(outer)])
+    } else {
+        Ok(vec![
+            Shell::new().with_faces(outer),
+            Shell::new().with_faces(inner),
+        ])
+    }
+}
+
+/// Compute the offset of a vertex from the planes that meet there
+///
+/// Each constraint consists of a plane normal and the distance that the vertex
+/// must be moved along that normal. Returns `None`, if the constraints
+/// contradict each other.
+fn offset_vertex(
+    constraints: &[(Vector<3>, Scalar)],
+    thickness: Scalar,
+) -> Option<Vector<3>> {
+    let epsilon = thickness * 1e-9;
+
+    // Select the constraints that are independent of each other. Any others
+    // must be satisfied by the resulting offset anyway.
+    let mut basis: Vec<(Vector<3>, Scalar)> = Vec::new();
+    for &(normal, distance) in constraints {
+        let is_independent = match basis.as_slice() {
+            [] => true,
+            [(a, _)] => a.cross(&normal).magnitude() > epsilon,
+            [(a, _), (b, _)] => a.cross(b).dot(&normal).abs() > epsilon,
+            _ => false,
+        };
+
+        if is_independent {
+            basis.push((normal, distance));
+        }
+    }
+
+    let offset = match basis.as_slice() {
+        [(n, d)] => *n * *d,
+        [(n1, d1), (n2, d2)] => {
+            // The offset is a combination of both normals, as moving the
+            // vertex along the line where the planes meet would be arbitrary.
+            let c = n1.dot(n2);
+            let det = Scalar::ONE - c * c;
+
+            let a = (*d1 - c * *d2) / det;
+            let b = (*d2 - c * *d1) / det;
+
+            *n1 * a + *n2 * b
+        }
+        [(n1, d1), (n2, d2), (n3, d3)] => {
+            (n2.cross(n3) * *d1 + n3.cross(n1) * *d2 + n1.cross(n2) * *d3)
+                / n1.dot(&n2.cross(n3))
+        }
+        _ => return None,
+    };
+
+    for (normal, distance) in constraints {
+        if (normal.dot(&offset) - *distance).abs() > epsilon {
+            return None;
+        }
+    }
+
+    Some(offset)
+}
+
+fn cycle_vertices(cycle: &Cycle) -> Vec<GlobalVertex> {
+    cycle
+        .edges()
+        .map(|edge| {
+            // All edges have been checked for vertices at this point.
+            let [a, _] = edge.vertices().get_or_panic();
+            *a.global()
+        })
+        .collect()
+}
+
+fn polygon(
+    plane: &Plane,
+    vertices: impl IntoIterator<Item = GlobalVertex>,
+) -> Cycle {
+    let vertices = vertices.into_iter().collect::<Vec<_>>();
+    let surface = plane.surface();
+
+    let edges = vertices.iter().enumerate().map(|(i, &a)| {
+        let b = vertices[(i + 1) % vertices.len()];
+        let vertices = [a, b].map(|vertex| {
+            (plane.point_to_surface_coords(vertex.position()), vertex)
+        });
+
+        Edge::build(surface).line_segment_from_vertices(vertices)
+    });
+
+    Cycle::new(surface, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Point, Triangle};
+
+    use crate::{
+        algorithms::adjacency::Adjacency,
+        builder::fixtures::cube,
+        iter::ObjectIters,
+        objects::{Face, Shell, Solid, Surface},
+    };
+
+    use super::{hollow, HollowError};
+
+    #[test]
+    fn hollow_closed() -> anyhow::Result<()> {
+        let solid = cube()?;
+        let hollowed = hollow(&solid, 0.25, [])?;
+
+        assert_eq!(hollowed.shells().count(), 2);
+        assert_eq!(hollowed.face_iter().count(), 12);
+        assert_eq!(hollowed.global_vertex_iter().count(), 16);
+        assert!(hollowed.global_vertex_iter().any(|vertex| {
+            vertex.position() == Point::from([0.25, 0.25, 0.25])
+        }));
+
+        let adjacency = Adjacency::from_solid(&hollowed);
+        assert_eq!(adjacency.edges().count(), 24);
+        assert!(adjacency.is_closed_manifold());
+
+        Ok(())
+    }
+
+    #[test]
+    fn hollow_open() -> anyhow::Result<()> {
+        let solid = cube()?;
+        let top = top_face(&solid);
+
+        let hollowed = hollow(&solid, 0.25, [top])?;
+
+        assert_eq!(hollowed.shells().count(), 1);
+        assert_eq!(hollowed.face_iter().count(), 11);
+        assert_eq!(hollowed.global_vertex_iter().count(), 16);
+        assert!(hollowed.global_vertex_iter().any(|vertex| {
+            vertex.position() == Point::from([0.25, 0.25, 2.])
+        }));
+
+        let adjacency = Adjacency::from_solid(&hollowed);
+        assert_eq!(adjacency.edges().count(), 24);
+        assert!(adjacency.is_closed_manifold());
+
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> anyhow::Result<()> {
+        let solid = cube()?;
+
+        assert!(matches!(
+            hollow(&solid, 0., []),
+            Err(HollowError::InvalidThickness)
+        ));
+        assert!(matches!(
+            hollow(&solid, 1.5, []),
+            Err(HollowError::TooThick)
+        ));
+
+        let other = Face::build(Surface::xy_plane())
+            .polygon_from_points([[0., 0.], [1., 0.], [0., 1.]])
+            .into_face();
+        assert!(matches!(
+            hollow(&solid, 0.25, [&other]),
+            Err(HollowError::FaceNotFound)
+        ));
+
+        let triangles = Face::from_triangles(vec![(
+            Triangle::from([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]]),
+            Color([255, 0, 0, 255]),
+        )]);
+        let solid = Solid::new().with_shells([Shell::new()
+            .with_faces(solid.face_iter().cloned().chain([triangles]))]);
+        assert!(matches!(
+            hollow(&solid, 0.25, []),
+            Err(HollowError::TriangleRepresentation)
+        ));
+
+        Ok(())
+    }
+
+    fn top_face(solid: &Solid) -> &Face {
+        solid
+            .face_iter()
+            .find(|face| {
+                face.global_vertex_iter()
+                    .all(|vertex| vertex.position().z == 2.into())
+            })
+            .expect("Top face not found")
+    }
+}
@@ -4,6 +4,9 @@ pub mod face_point;
 pub mod ray_edge;
 pub mod ray_face;
 pub mod ray_segment;
+pub mod ray_shell;
+pub mod ray_solid;
+pub mod solid_point;
 
 mod curve_edge;
 mod curve_face;
@@ -11,7 +14,7 @@ mod face_face;
 mod line_segment;
 mod surface_surface;
 
-use fj_math::Point;
+use fj_math::{Point, Scalar, Vector};
 
 pub use self::{
     curve_edge::CurveEdgeIntersection,
@@ -35,6 +38,38 @@ pub trait Intersect {
     fn intersect(self) -> Option<Self::Intersection>;
 }
 
+/// A ray with an arbitrary origin and direction
+///
+/// Points on the ray are described by a parameter `t`, as `origin +
+/// direction * t`. The direction doesn't need to be normalized, but if it is,
+/// `t` is the distance from the origin.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ray<const D: usize> {
+    /// The point where the ray originates
+    pub origin: Point<D>,
+
+    /// The direction of the ray
+    pub direction: Vector<D>,
+}
+
+impl<const D: usize> Ray<D> {
+    /// Create a new instance of `Ray`
+    pub fn new(
+        origin: impl Into<Point<D>>,
+        direction: impl Into<Vector<D>>,
+    ) -> Self {
+        Self {
+            origin: origin.into(),
+            direction: direction.into(),
+        }
+    }
+
+    /// Convert a ray parameter into a point on the ray
+    pub fn point_from_ray_coords(&self, t: impl Into<Scalar>) -> Point<D> {
+        self.origin + self.direction * t.into()
+    }
+}
+
 /// A horizontal ray that goes to the right
 ///
 /// For in-kernel use, we don't need anything more flexible, and being exactly
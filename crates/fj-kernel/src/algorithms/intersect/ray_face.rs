@@ -3,11 +3,21 @@
 use fj_math::{Point, Scalar, Vector};
 
 use crate::{
-    algorithms::intersect::face_point::FacePointIntersection,
-    objects::{Edge, Face, Vertex},
+    algorithms::{
+        distance::{
+            edge_point_distance, project_onto_plane, ray_triangle_intersection,
+        },
+        intersect::face_point::FacePointIntersection,
+    },
+    objects::{CurveKind, Edge, Face, Surface, Vertex},
+};
+
+use super::{
+    solid_point::face_contains_point, HorizontalRayToTheRight, Intersect, Ray,
 };
 
-use super::{HorizontalRayToTheRight, Intersect};
+/// Hits closer than this to the boundary of a face are considered on the face
+const BOUNDARY_DISTANCE: f64 = 1e-10;
 
 impl Intersect for (&HorizontalRayToTheRight<3>, &Face) {
     type Intersection = RayFaceIntersection;
@@ -148,6 +158,233 @@ pub enum RayFaceIntersection {
     RayHitsVertex(Vertex),
 }
 
+impl Intersect for (&Ray<3>, &Face) {
+    type Intersection = RayHit;
+
+    fn intersect(self) -> Option<Self::Intersection> {
+        let (ray, face) = self;
+
+        let margin = Scalar::from_f64(BOUNDARY_DISTANCE);
+
+        if let Some(triangles) = face.triangles() {
+            return triangles
+                .iter()
+                .filter_map(|(triangle, _)| {
+                    let (t, barycentric) = ray_triangle_intersection(
+                        ray.origin,
+                        ray.direction,
+                        triangle,
+                    )?;
+                    if t < Scalar::ZERO
+                        || barycentric.iter().any(|&b| b < -margin)
+                    {
+                        return None;
+                    }
+
+                    let [a, b, c] = triangle.points();
+                    let normal = (b - a).cross(&(c - a)).normalize();
+
+                    Some((t, normal))
+                })
+                .min_by_key(|&(t, _)| t)
+                .map(|(t, normal)| RayHit {
+                    t,
+                    point: ray.point_from_ray_coords(t),
+                    normal,
+                    face: face.clone(),
+                });
+        }
+
+        // If the ray lies within the surface, or just touches it, we don't
+        // consider that a hit. There's no well-defined point to return.
+        let hits = ray_surface_intersection(ray, face.surface(), margin)?;
+
+        hits.into_iter()
+            .filter(|hit| hit.t >= Scalar::ZERO)
+            .filter(|hit| {
+                let within_face = hit
+                    .surface_coords
+                    .iter()
+                    .any(|&point| face_contains_point(face, point));
+                let on_boundary = face
+                    .all_cycles()
+                    .flat_map(|cycle| cycle.edges())
+                    .any(|edge| edge_point_distance(edge, hit.point) < margin);
+
+                within_face || on_boundary
+            })
+            .min_by_key(|hit| hit.t)
+            .map(|hit| RayHit {
+                t: hit.t,
+                point: hit.point,
+                normal: hit.normal,
+                face: face.clone(),
+            })
+    }
+}
+
+/// A hit between a ray and a face
+///
+/// Returned when casting a [`Ray`] against a face, or against the faces of a
+/// shell or solid. In the latter case, this is the hit closest to the origin
+/// of the ray.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RayHit {
+    /// The ray parameter of the hit
+    ///
+    /// See [`Ray`].
+    pub t: Scalar,
+
+    /// The point where the ray hits the face
+    pub point: Point<3>,
+
+    /// The normal of the face at the point of the hit
+    ///
+    /// This follows the orientation of the face, and is normalized.
+    pub normal: Vector<3>,
+
+    /// The face that was hit
+    pub face: Face,
+}
+
+/// A point where a ray intersects a surface
+pub(super) struct SurfaceHit {
+    /// The ray parameter of the hit
+    ///
+    /// Might be negative, if the hit is behind the origin of the ray.
+    pub t: Scalar,
+
+    /// The point where the ray intersects the surface
+    pub point: Point<3>,
+
+    /// The surface coordinates of the point
+    ///
+    /// Contains multiple candidates, if the surface is periodic. A face can
+    /// be defined in any of them.
+    pub surface_coords: Vec<Point<2>>,
+
+    /// The normal of the surface at the point
+    pub normal: Vector<3>,
+}
+
+/// Compute the points where a ray intersects a surface
+///
+/// Returns `None`, if the intersection is degenerate, meaning the ray lies
+/// within the surface, or touches it tangentially. `margin` is used to decide
+/// whether that is the case.
+pub(super) fn ray_surface_intersection(
+    ray: &Ray<3>,
+    surface: &Surface,
+    margin: Scalar,
+) -> Option<Vec<SurfaceHit>> {
+    let surface = match surface {
+        Surface::SweptCurve(surface) => surface,
+    };
+
+    match surface.curve {
+        CurveKind::Line(line) => {
+            let normal = line.direction().cross(&surface.path).normalize();
+            let denominator = normal.dot(&ray.direction);
+            let offset = normal.dot(&(line.origin() - ray.origin));
+
+            if denominator.abs() < margin * ray.direction.magnitude() {
+                // The ray is parallel to the plane.
+                if offset.abs() < margin {
+                    return None;
+                }
+                return Some(Vec::new());
+            }
+
+            let t = offset / denominator;
+            let point = ray.point_from_ray_coords(t);
+            let (u, v) = project_onto_plane(
+                point - line.origin(),
+                line.direction(),
+                surface.path,
+            );
+
+            Some(vec![SurfaceHit {
+                t,
+                point,
+                surface_coords: vec![Point::from([u, v])],
+                normal,
+            }])
+        }
+        CurveKind::Circle(circle) => {
+            let radius = circle.a().magnitude();
+            let circle_normal = circle.a().cross(&circle.b()).normalize();
+
+            // Project everything into the plane of the circle, along the path.
+            // There, the surface is the circle itself.
+            let path_along_normal = surface.path.dot(&circle_normal);
+            let project = |vector: Vector<3>| {
+                vector
+                    - surface.path
+                        * (vector.dot(&circle_normal) / path_along_normal)
+            };
+
+            let q0 = project(ray.origin - circle.center());
+            let q1 = project(ray.direction);
+
+            let a = q1.dot(&q1);
+            let b = q0.dot(&q1);
+            let c = q0.dot(&q0) - radius * radius;
+
+            if a < margin * margin {
+                // The ray is parallel to the path.
+                if c.abs() < margin * radius {
+                    return None;
+                }
+                return Some(Vec::new());
+            }
+
+            let discriminant = b * b - a * c;
+            if discriminant < Scalar::ZERO {
+                return Some(Vec::new());
+            }
+            if discriminant < margin * a {
+                return None;
+            }
+
+            let root = Scalar::from_f64(discriminant.into_f64().sqrt());
+
+            let hits = [(-b - root) / a, (-b + root) / a]
+                .into_iter()
+                .map(|t| {
+                    let point = ray.point_from_ray_coords(t);
+                    let q = q0 + q1 * t;
+
+                    let u = q.dot(&circle.b()).atan2(q.dot(&circle.a()));
+                    let v = (point - circle.center()).dot(&circle_normal)
+                        / path_along_normal;
+
+                    // Circle coordinates are not limited to the range of
+                    // `atan2`, so provide neighboring turns too.
+                    let surface_coords = [u, u + Scalar::TAU, u - Scalar::TAU]
+                        .map(|u| Point::from([u, v]))
+                        .to_vec();
+
+                    // The derivative of the circle is the circle vector a
+                    // quarter turn further.
+                    let tangent = circle.vector_from_circle_coords([
+                        u + Scalar::PI / Scalar::TWO
+                    ]);
+                    let normal = tangent.cross(&surface.path).normalize();
+
+                    SurfaceHit {
+                        t,
+                        point,
+                        surface_coords,
+                        normal,
+                    }
+                })
+                .collect();
+
+            Some(hits)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use fj_math::Point;
@@ -0,0 +1,51 @@
+//! Intersection between a ray and a shell, in 3D
+
+use crate::objects::Shell;
+
+use super::{ray_face::RayHit, Intersect, Ray};
+
+impl Intersect for (&Ray<3>, &Shell) {
+    type Intersection = RayHit;
+
+    fn intersect(self) -> Option<Self::Intersection> {
+        let (ray, shell) = self;
+
+        shell
+            .faces()
+            .filter_map(|face| (ray, face).intersect())
+            .min_by_key(|hit| hit.t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::intersect::{Intersect, Ray},
+        objects::Shell,
+    };
+
+    #[test]
+    fn ray_hits_closest_face() {
+        let shell = Shell::build().cube_from_edge_length(2.);
+        let ray = Ray::new([0., 0., 5.], [0., 0., -1.]);
+
+        let hit = (&ray, &shell).intersect().unwrap();
+
+        assert_eq!(hit.t, Scalar::from(4.));
+        assert_eq!(hit.point, Point::from([0., 0., 1.]));
+        assert_eq!(hit.normal.z.abs(), Scalar::ONE);
+    }
+
+    #[test]
+    fn ray_misses_shell() {
+        let shell = Shell::build().cube_from_edge_length(2.);
+
+        let ray = Ray::new([0., 0., 5.], [0., 0., 1.]);
+        assert_eq!((&ray, &shell).intersect(), None);
+
+        let ray = Ray::new([3., 0., 5.], [0., 0., -1.]);
+        assert_eq!((&ray, &shell).intersect(), None);
+    }
+}
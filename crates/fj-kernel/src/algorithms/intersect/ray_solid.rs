@@ -0,0 +1,82 @@
+//! Intersection between a ray and a solid, in 3D
+
+use crate::objects::Solid;
+
+use super::{ray_face::RayHit, Intersect, Ray};
+
+impl Intersect for (&Ray<3>, &Solid) {
+    type Intersection = RayHit;
+
+    fn intersect(self) -> Option<Self::Intersection> {
+        let (ray, solid) = self;
+
+        solid
+            .shells()
+            .filter_map(|shell| (ray, shell).intersect())
+            .min_by_key(|hit| hit.t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance,
+            intersect::{Intersect, Ray},
+            sweep::Sweep,
+        },
+        builder::fixtures::cube,
+        objects::{Cycle, Edge, Face, Surface},
+    };
+
+    #[test]
+    fn ray_hits_solid() -> anyhow::Result<()> {
+        let solid = cube()?;
+
+        // Cast from the outside, at an angle.
+        let ray = Ray::new([0.5, 0.5, 5.], [0.25, 0.25, -1.]);
+        let hit = (&ray, &solid).intersect().unwrap();
+        assert_eq!(hit.t, Scalar::from(3.));
+        assert_eq!(hit.point, Point::from([1.25, 1.25, 2.]));
+        assert_eq!(hit.normal, Vector::from([0., 0., 1.]));
+
+        // Cast from the inside.
+        let ray = Ray::new([1., 1., 1.], [1., 0., 0.]);
+        let hit = (&ray, &solid).intersect().unwrap();
+        assert_eq!(hit.t, Scalar::ONE);
+        assert_eq!(hit.point, Point::from([2., 1., 1.]));
+        assert_eq!(hit.normal, Vector::from([1., 0., 0.]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ray_hits_cylinder() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let cycle = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+        );
+        let face = Face::new(surface).with_exteriors([cycle]);
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let solid =
+            face.sweep([0., 0., 1.], tolerance, Color([255, 0, 0, 255]));
+
+        // The top face is exact, so the hit is too.
+        let ray = Ray::new([0.5, 0., 3.], [0., 0., -1.]);
+        let hit = (&ray, &solid).intersect().unwrap();
+        assert_eq!(hit.point, Point::from([0.5, 0., 1.]));
+        assert_eq!(hit.normal, Vector::from([0., 0., 1.]));
+
+        // The side is approximated. Accept anything within tolerance.
+        let ray = Ray::new([3., 0., 0.5], [-1., 0., 0.]);
+        let hit = (&ray, &solid).intersect().unwrap();
+        assert!((hit.t - Scalar::TWO).abs() <= tolerance.inner());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+//! Intersection between solids and points in 3D
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::distance::{
+        edge_point_distance, face_point_distance, ray_triangle_intersection,
+    },
+    iter::ObjectIters,
+    objects::{CurveKind, Edge, Face, Solid, Vertex},
+};
+
+use super::{ray_face::ray_surface_intersection, Intersect, Ray};
+
+/// Points closer than this to the boundary of a solid are considered on it
+const BOUNDARY_DISTANCE: f64 = 1e-10;
+
+/// Ray hits closer than this to an edge are considered ambiguous
+const AMBIGUOUS_DISTANCE: f64 = 1e-7;
+
+/// The directions of the rays that are cast to determine containment
+///
+/// A ray that passes too close to an edge or vertex, or that grazes a face,
+/// can't be counted reliably. Then the next direction is tried. The directions
+/// are arbitrary, but fixed, to keep the results reproducible, and chosen such
+/// that they are unlikely to be aligned with features of typical models.
+const RAY_DIRECTIONS: [[f64; 3]; 4] = [
+    [0.7071, 0.5345, 0.4629],
+    [-0.3841, 0.8127, 0.2718],
+    [0.2113, -0.4472, 0.866],
+    [-0.618, -0.2361, -0.7494],
+];
+
+impl Intersect for (&Solid, &Point<3>) {
+    type Intersection = SolidPointIntersection;
+
+    fn intersect(self) -> Option<Self::Intersection> {
+        let (solid, point) = self;
+        let point = *point;
+
+        let boundary_distance = Scalar::from_f64(BOUNDARY_DISTANCE);
+
+        for vertex in solid.vertex_iter() {
+            let distance = (point - vertex.global().position()).magnitude();
+            if distance < boundary_distance {
+                return Some(SolidPointIntersection::PointIsOnVertex(*vertex));
+            }
+        }
+        for edge in solid.edge_iter() {
+            if edge_point_distance(edge, point) < boundary_distance {
+                return Some(SolidPointIntersection::PointIsOnEdge(*edge));
+            }
+        }
+        for face in solid.face_iter() {
+            if face_point_distance(face, point) < boundary_distance {
+                return Some(SolidPointIntersection::PointIsOnFace(
+                    face.clone(),
+                ));
+            }
+        }
+
+        // The point is not on the boundary, so it's either inside or outside.
+        // Cast rays until one of them crosses the boundary unambiguously. If
+        // none of them does, go with the last one. That's the best we can do.
+        let mut num_hits = 0;
+        for direction in RAY_DIRECTIONS {
+            let ray = Ray::new(point, direction);
+            let (hits, ambiguous) = count_boundary_crossings(solid, &ray);
+            num_hits = hits;
+
+            if !ambiguous {
+                break;
+            }
+        }
+
+        if num_hits % 2 == 1 {
+            Some(SolidPointIntersection::PointIsInsideSolid)
+        } else {
+            None
+        }
+    }
+}
+
+/// The intersection between a solid and a point
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SolidPointIntersection {
+    /// The point is inside of the solid
+    PointIsInsideSolid,
+
+    /// The point is on a face of the solid
+    PointIsOnFace(Face),
+
+    /// The point is coincident with an edge
+    PointIsOnEdge(Edge),
+
+    /// The point is coincident with a vertex
+    PointIsOnVertex(Vertex),
+}
+
+/// Determine whether a point in surface coordinates is within a face
+///
+/// In contrast to the intersection between a face and a point in 2D, this
+/// supports edges that are arcs. The point is expected to not be on the
+/// boundary of the face; in that case, the result is unspecified.
+pub(crate) fn face_contains_point(face: &Face, point: Point<2>) -> bool {
+    // A cycle contains the point, if it winds around it. Exterior and interior
+    // cycles are treated the same, as a point in a hole is within both of
+    // them.
+    let mut inside = false;
+
+    for cycle in face.all_cycles() {
+        let winding = cycle
+            .edges()
+            .map(|edge| winding_angle(edge, point))
+            .fold(Scalar::ZERO, |a, b| a + b);
+
+        if winding.abs() > Scalar::PI {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Count how often a ray crosses the boundary of a solid
+///
+/// Also returns whether the ray came close to an edge or grazed a face, which
+/// makes the count unreliable.
+fn count_boundary_crossings(solid: &Solid, ray: &Ray<3>) -> (u64, bool) {
+    let margin = Scalar::from_f64(AMBIGUOUS_DISTANCE);
+
+    let mut num_hits = 0;
+    let mut ambiguous = false;
+
+    for face in solid.face_iter() {
+        if let Some(triangles) = face.triangles() {
+            for (triangle, _) in triangles {
+                let (t, barycentric) = match ray_triangle_intersection(
+                    ray.origin,
+                    ray.direction,
+                    triangle,
+                ) {
+                    Some(intersection) => intersection,
+                    None => continue,
+                };
+
+                if t <= Scalar::ZERO || barycentric.iter().any(|&b| b < -margin)
+                {
+                    continue;
+                }
+
+                // The ray hits the triangle close to its boundary. That might
+                // be counted twice, or not at all.
+                if barycentric.iter().any(|&b| b < margin) {
+                    ambiguous = true;
+                }
+
+                num_hits += 1;
+            }
+
+            continue;
+        }
+
+        let hits = match ray_surface_intersection(ray, face.surface(), margin) {
+            Some(hits) => hits,
+            None => {
+                // The ray lies within the surface, or touches it tangentially.
+                // We can't say whether it enters the solid there.
+                ambiguous = true;
+                continue;
+            }
+        };
+
+        for hit in hits {
+            if hit.t <= Scalar::ZERO {
+                continue;
+            }
+
+            let near_edge = face
+                .all_cycles()
+                .flat_map(|cycle| cycle.edges())
+                .any(|edge| edge_point_distance(edge, hit.point) < margin);
+            if near_edge {
+                ambiguous = true;
+            }
+
+            if hit
+                .surface_coords
+                .into_iter()
+                .any(|point| face_contains_point(face, point))
+            {
+                num_hits += 1;
+            }
+        }
+    }
+
+    (num_hits, ambiguous)
+}
+
+/// Compute the angle that an edge sweeps out, as seen from a point
+fn winding_angle(edge: &Edge, point: Point<2>) -> Scalar {
+    match edge.curve().kind() {
+        CurveKind::Line(line) => match edge.vertices().get() {
+            Some([a, b]) => {
+                let a = line.point_from_line_coords(a.position());
+                let b = line.point_from_line_coords(b.position());
+
+                angle_between(a - point, b - point)
+            }
+            None => Scalar::ZERO,
+        },
+        CurveKind::Circle(circle) => {
+            let (range, full_circle) = match edge.vertices().get() {
+                Some([a, b]) => ([a.position().t, b.position().t], false),
+                None => ([Scalar::ZERO, Scalar::TAU], true),
+            };
+            let [t0, t1] = range;
+
+            let start = circle.point_from_circle_coords([t0]);
+            let end = circle.point_from_circle_coords([t1]);
+            let middle =
+                circle.point_from_circle_coords([(t0 + t1) / Scalar::TWO]);
+
+            // The arc sweeps out the same angle as its chord, unless the point
+            // is within the circular segment between them. Then it sweeps out
+            // an additional full turn, in the direction of the arc.
+            let chord_angle = if full_circle {
+                Scalar::ZERO
+            } else {
+                angle_between(start - point, end - point)
+            };
+
+            let within_circle =
+                (point - circle.center()).magnitude() < circle.a().magnitude();
+            let within_segment = within_circle
+                && (full_circle || {
+                    let chord = end - start;
+                    cross(chord, point - start).sign()
+                        == cross(chord, middle - start).sign()
+                });
+
+            if within_segment {
+                let orientation =
+                    cross(circle.a(), circle.b()).sign() * (t1 - t0).sign();
+                chord_angle + Scalar::TAU * orientation
+            } else {
+                chord_angle
+            }
+        }
+    }
+}
+
+/// Compute the signed angle between two vectors
+fn angle_between(a: Vector<2>, b: Vector<2>) -> Scalar {
+    cross(a, b).atan2(a.dot(&b))
+}
+
+fn cross(a: Vector<2>, b: Vector<2>) -> Scalar {
+    a.u * b.v - a.v * b.u
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Point, Scalar};
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance,
+            intersect::{solid_point::SolidPointIntersection, Intersect},
+            sweep::Sweep,
+        },
+        builder::fixtures::cube,
+        objects::{Cycle, Edge, Face, Surface},
+    };
+
+    #[test]
+    fn point_is_inside_solid() -> anyhow::Result<()> {
+        let solid = cube()?;
+
+        assert_eq!(
+            (&solid, &Point::from([1., 1., 1.])).intersect(),
+            Some(SolidPointIntersection::PointIsInsideSolid)
+        );
+        assert_eq!((&solid, &Point::from([3., 1., 1.])).intersect(), None);
+        assert_eq!((&solid, &Point::from([1., 1., -1.])).intersect(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn point_is_on_boundary() -> anyhow::Result<()> {
+        let solid = cube()?;
+
+        assert!(matches!(
+            (&solid, &Point::from([1., 1., 2.])).intersect(),
+            Some(SolidPointIntersection::PointIsOnFace(_))
+        ));
+        assert!(matches!(
+            (&solid, &Point::from([1., 0., 2.])).intersect(),
+            Some(SolidPointIntersection::PointIsOnEdge(_))
+        ));
+        assert!(matches!(
+            (&solid, &Point::from([0., 0., 0.])).intersect(),
+            Some(SolidPointIntersection::PointIsOnVertex(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn point_is_inside_cylinder() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let cycle = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+        );
+        let face = Face::new(surface).with_exteriors([cycle]);
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let solid =
+            face.sweep([0., 0., 1.], tolerance, Color([255, 0, 0, 255]));
+
+        assert_eq!(
+            (&solid, &Point::from([0.5, 0., 0.5])).intersect(),
+            Some(SolidPointIntersection::PointIsInsideSolid)
+        );
+        assert_eq!((&solid, &Point::from([0.8, 0.8, 0.5])).intersect(), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,556 @@
+//! Mass properties of shapes
+//!
+//! Mass properties are computed from the faces of a shape. The divergence
+//! theorem turns each volume integral into an integral over the surface of the
+//! shape. Those are computed for each face in surface coordinates, where
+//! Green's theorem turns them into integrals along the boundary of the face.
+//!
+//! The remaining integrals are computed numerically, using Gauss-Legendre
+//! quadrature. For planar faces that are bounded by straight edges, the
+//! integrands are polynomials, and the result is exact. Circular edges and
+//! swept circles are split into short arcs, which makes the result accurate
+//! to floating-point precision. Contrary to computing mass properties from a
+//! triangle mesh, none of this depends on a tolerance value.
+
+use std::{
+    f64::consts::{FRAC_PI_2, PI},
+    ops,
+};
+
+use fj_interop::mass_properties::MassProperties;
+use fj_math::{Circle, Point, Scalar, Triangle, Vector};
+
+use crate::objects::{CurveKind, Cycle, Face, Surface};
+
+/// Compute the mass properties of the shape bounded by the provided faces
+///
+/// The faces must form a closed shape, and they must be oriented outward,
+/// meaning the normal of their surface points away from the shape. Faces that
+/// use triangle representation are supported, but the result for them is only
+/// as accurate as their triangles.
+pub fn mass_properties<'r>(
+    faces: impl IntoIterator<Item = &'r Face>,
+) -> MassProperties {
+    let mut integrals = Integrals::default();
+
+    for face in faces {
+        match face.triangles() {
+            Some(triangles) => {
+                for (triangle, _) in triangles {
+                    integrals += triangle_integrals(triangle);
+                }
+            }
+            None => integrals += face_integrals(face),
+        }
+    }
+
+    integrals.into_mass_properties()
+}
+
+fn face_integrals(face: &Face) -> Integrals {
+    let surface = Parameterization::Surface(*face.surface());
+
+    let mut integrals = Integrals::default();
+
+    // The cycles of a face can be oriented either way. Green's theorem needs
+    // exteriors to go counter-clockwise and interiors clockwise, so correct
+    // the sign based on the area that each cycle encloses.
+    for cycle in face.exteriors() {
+        let (cycle, area) = cycle_integrals(&surface, cycle);
+        integrals += cycle * area.sign();
+    }
+    for cycle in face.interiors() {
+        let (cycle, area) = cycle_integrals(&surface, cycle);
+        integrals += cycle * -area.sign();
+    }
+
+    integrals
+}
+
+fn triangle_integrals(triangle: &Triangle<3>) -> Integrals {
+    let [a, b, c] = triangle.points();
+    let surface = Parameterization::Triangle {
+        origin: a,
+        u: b - a,
+        v: c - a,
+    };
+
+    // The triangle in surface coordinates is counter-clockwise, as required by
+    // Green's theorem.
+    let points = [[0., 0.], [1., 0.], [0., 1.]].map(Point::from);
+
+    let mut integrals = Integrals::default();
+    for (i, &start) in points.iter().enumerate() {
+        let end = points[(i + 1) % points.len()];
+
+        let (edge, _) = boundary_integrals(
+            &surface,
+            |t| start + (end - start) * t,
+            |_| end - start,
+            [Scalar::ZERO, Scalar::ONE],
+            1,
+        );
+        integrals += edge;
+    }
+
+    integrals
+}
+
+/// Integrate along a cycle of a face
+///
+/// Returns the surface integrals, assuming the cycle is counter-clockwise,
+/// and the signed area that the cycle encloses in surface coordinates.
+fn cycle_integrals(
+    surface: &Parameterization,
+    cycle: &Cycle,
+) -> (Integrals, Scalar) {
+    let mut integrals = Integrals::default();
+    let mut area = Scalar::ZERO;
+
+    for edge in cycle.edges() {
+        let range = match edge.vertices().get() {
+            Some([a, b]) => [a.position().t, b.position().t],
+            None => [Scalar::ZERO, Scalar::TAU],
+        };
+
+        let curve = *edge.curve().kind();
+        let derivative = |t: Scalar| match curve {
+            CurveKind::Circle(circle) => circle_derivative(&circle, t),
+            CurveKind::Line(line) => line.direction(),
+        };
+
+        // Integrands that contain angles are not polynomials, and need to be
+        // integrated in short pieces to be accurate.
+        let angle = match curve {
+            CurveKind::Circle(_) => range[1] - range[0],
+            CurveKind::Line(line) => match surface {
+                Parameterization::Surface(Surface::SweptCurve(surface))
+                    if matches!(surface.curve, CurveKind::Circle(_)) =>
+                {
+                    line.direction().u * (range[1] - range[0])
+                }
+                _ => Scalar::ZERO,
+            },
+        };
+        let pieces = (angle.abs() / MAX_ANGLE).ceil().into_u64().max(1);
+
+        let (edge, edge_area) = boundary_integrals(
+            surface,
+            |t| curve.point_from_curve_coords([t]),
+            derivative,
+            range,
+            pieces,
+        );
+        integrals += edge;
+        area += edge_area;
+    }
+
+    (integrals, area)
+}
+
+/// Integrate along a boundary in surface coordinates
+///
+/// By Green's theorem, the integral of `f(u, v)` over an area equals the
+/// integral of `-F(u, v) du` along its counter-clockwise boundary, where
+/// `F(u, v)` is the integral of `f(u, s)` from `s = 0` to `s = v`.
+///
+/// Returns the surface integrals and the signed area that the boundary
+/// contributes to.
+fn boundary_integrals(
+    surface: &Parameterization,
+    point: impl Fn(Scalar) -> Point<2>,
+    derivative: impl Fn(Scalar) -> Vector<2>,
+    range: [Scalar; 2],
+    pieces: u64,
+) -> (Integrals, Scalar) {
+    let mut integrals = Integrals::default();
+    let mut area = Scalar::ZERO;
+
+    let [start, end] = range;
+    let length = (end - start) / pieces as f64;
+
+    for i in 0..pieces {
+        let piece_start = start + length * i as f64;
+
+        for (x, w) in GAUSS_LEGENDRE {
+            let t = piece_start + length * (x + 1.) / 2.;
+            let weight = length * w / 2.;
+
+            let uv = point(t);
+            let du = derivative(t).u * weight;
+
+            // The inner integral, from `v = 0` to the point on the boundary.
+            let mut inner = Integrals::default();
+            for (y, w) in GAUSS_LEGENDRE {
+                let v = uv.v * (y + 1.) / 2.;
+                let weight = uv.v * w / 2.;
+
+                inner += surface.integrand(Point::from([uv.u, v])) * weight;
+            }
+
+            integrals += inner * -du;
+            area += -uv.v * du;
+        }
+    }
+
+    (integrals, area)
+}
+
+/// Compute the derivative of a circle at the provided circle coordinate
+fn circle_derivative<const D: usize>(
+    circle: &Circle<D>,
+    t: Scalar,
+) -> Vector<D> {
+    // The derivative of `a * cos(t) + b * sin(t)` is the same vector, a
+    // quarter turn ahead.
+    circle.vector_from_circle_coords([t + Scalar::from_f64(FRAC_PI_2)])
+}
+
+/// The maximum angle that is integrated in one piece
+const MAX_ANGLE: f64 = PI / 16.;
+
+/// Nodes and weights of the 8-point Gauss-Legendre quadrature on `[-1, 1]`
+///
+/// Exact for polynomials up to degree 15.
+const GAUSS_LEGENDRE: [(f64, f64); 8] = [
+    (-0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+    (-0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (-0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (-0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.183_434_642_495_649_8, 0.362_683_783_378_362),
+    (0.525_532_409_916_329, 0.313_706_645_877_887_3),
+    (0.796_666_477_413_626_7, 0.222_381_034_453_374_5),
+    (0.960_289_856_497_536_3, 0.101_228_536_290_376_3),
+];
+
+/// A parameterization of a face, from surface coordinates to model space
+enum Parameterization {
+    Surface(Surface),
+    Triangle {
+        origin: Point<3>,
+        u: Vector<3>,
+        v: Vector<3>,
+    },
+}
+
+impl Parameterization {
+    /// Compute the surface integrands at the provided point
+    fn integrand(&self, point_surface: Point<2>) -> Integrals {
+        let (point, normal) = match self {
+            Self::Surface(surface) => {
+                let swept = match surface {
+                    Surface::SweptCurve(swept) => swept,
+                };
+
+                let derivative = match swept.curve {
+                    CurveKind::Circle(circle) => {
+                        circle_derivative(&circle, point_surface.u)
+                    }
+                    CurveKind::Line(line) => line.direction(),
+                };
+
+                (
+                    surface.point_from_surface_coords(point_surface),
+                    derivative.cross(&swept.path),
+                )
+            }
+            Self::Triangle { origin, u, v } => (
+                *origin + *u * point_surface.u + *v * point_surface.v,
+                u.cross(v),
+            ),
+        };
+
+        Integrals::from_point_and_normal(point, normal)
+    }
+}
+
+/// The surface integrals that make up the mass properties
+///
+/// Each volume integral is represented by the integral of a vector field over
+/// the surface, whose divergence is the original integrand.
+#[derive(Clone, Copy, Default)]
+struct Integrals {
+    area: Scalar,
+    volume: Scalar,
+
+    /// The integrals of `x`, `y`, and `z` over the volume
+    first: Vector<3>,
+
+    /// The integrals of `x²`, `y²`, and `z²` over the volume
+    second: Vector<3>,
+
+    /// The integrals of `xy`, `yz`, and `zx` over the volume
+    products: Vector<3>,
+}
+
+impl Integrals {
+    fn from_point_and_normal(point: Point<3>, normal: Vector<3>) -> Self {
+        let [x, y, z] = point.coords.components;
+        let [nx, ny, nz] = normal.components;
+
+        Self {
+            area: normal.magnitude(),
+            volume: point.coords.dot(&normal) / 3.,
+            first: Vector::from([x * x * nx, y * y * ny, z * z * nz]) / 2.,
+            second: Vector::from([
+                x * x * x * nx,
+                y * y * y * ny,
+                z * z * z * nz,
+            ]) / 3.,
+            products: Vector::from([
+                x * x * y * nx,
+                y * y * z * ny,
+                z * z * x * nz,
+            ]) / 2.,
+        }
+    }
+
+    fn into_mass_properties(self) -> MassProperties {
+        let volume = self.volume;
+
+        if volume == Scalar::ZERO {
+            return MassProperties {
+                volume,
+                area: self.area,
+                centroid: Point::origin(),
+                inertia: [[Scalar::ZERO; 3]; 3],
+            };
+        }
+
+        let centroid = Point {
+            coords: self.first / volume,
+        };
+        let [cx, cy, cz] = centroid.coords.components;
+
+        // Move the second moments to the centroid.
+        let [xx, yy, zz] = self.second.components;
+        let [xy, yz, zx] = self.products.components;
+
+        let xx = xx - volume * cx * cx;
+        let yy = yy - volume * cy * cy;
+        let zz = zz - volume * cz * cz;
+        let xy = xy - volume * cx * cy;
+        let yz = yz - volume * cy * cz;
+        let zx = zx - volume * cz * cx;
+
+        MassProperties {
+            volume,
+            area: self.area,
+            centroid,
+            inertia: [
+                [yy + zz, -xy, -zx],
+                [-xy, xx + zz, -yz],
+                [-zx, -yz, xx + yy],
+            ],
+        }
+    }
+}
+
+impl ops::AddAssign for Integrals {
+    fn add_assign(&mut self, other: Self) {
+        self.area += other.area;
+        self.volume += other.volume;
+        self.first = self.first + other.first;
+        self.second = self.second + other.second;
+        self.products = self.products + other.products;
+    }
+}
+
+impl ops::Mul<Scalar> for Integrals {
+    type Output = Self;
+
+    fn mul(self, factor: Scalar) -> Self::Output {
+        Self {
+            area: self.area * factor,
+            volume: self.volume * factor,
+            first: self.first * factor,
+            second: self.second * factor,
+            products: self.products * factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::{
+            approx::Tolerance, reverse::Reverse, sweep::Sweep,
+            transform::TransformObject,
+        },
+        iter::ObjectIters,
+        objects::{
+            Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalVertex,
+            Sketch, Surface, SweptCurve, Vertex, VerticesOfEdge,
+        },
+    };
+
+    use super::mass_properties;
+
+    #[test]
+    fn cuboid() -> anyhow::Result<()> {
+        let sketch = Sketch::build(Surface::xy_plane()).polygon_from_points([
+            [0., 0.],
+            [2., 0.],
+            [2., 4.],
+            [0., 4.],
+        ]);
+        let solid = sketch.sweep(
+            [0., 0., 6.],
+            Tolerance::from_scalar(0.001)?,
+            Color([255, 0, 0, 255]),
+        );
+
+        let properties = mass_properties(solid.face_iter());
+
+        assert_approx_eq(properties.volume, 48.);
+        assert_approx_eq(properties.area, 88.);
+        for (coord, expected) in properties
+            .centroid
+            .coords
+            .components
+            .into_iter()
+            .zip([1., 2., 3.])
+        {
+            assert_approx_eq(coord, expected);
+        }
+
+        // For a cuboid with the side lengths a, b, c, and mass m, the moment of
+        // inertia around the x-axis is m * (b² + c²) / 12.
+        let inertia = properties.inertia;
+        assert_approx_eq(inertia[0][0], 48. * (16. + 36.) / 12.);
+        assert_approx_eq(inertia[1][1], 48. * (4. + 36.) / 12.);
+        assert_approx_eq(inertia[2][2], 48. * (4. + 16.) / 12.);
+        assert_approx_eq(inertia[0][1], 0.);
+        assert_approx_eq(inertia[1][2], 0.);
+        assert_approx_eq(inertia[2][0], 0.);
+
+        Ok(())
+    }
+
+    #[test]
+    fn cylinder() {
+        let radius = 2.;
+        let height = 3.;
+
+        // Sweeping a circle would approximate the side of the cylinder with
+        // triangles, so build its faces directly.
+        let surface = Surface::xy_plane();
+        let cap = Face::new(surface).with_exteriors([Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::from(radius))],
+        )]);
+        let bottom = cap.clone().reverse();
+        let top = cap.translate([0., 0., height]);
+
+        let circle =
+            Circle::new([0., 0., 0.], [radius, 0., 0.], [0., radius, 0.]);
+        let surface = Surface::SweptCurve(SweptCurve {
+            curve: CurveKind::Circle(circle),
+            path: Vector::from([0., 0., height]),
+        });
+        let vertices =
+            [0., height].map(|z| GlobalVertex::from_position([radius, 0., z]));
+
+        let arc = |v: f64, range: [f64; 2], vertex: GlobalVertex| {
+            let curve = Curve::new(
+                surface,
+                CurveKind::Line(Line::from_origin_and_direction(
+                    Point::from([0., v]),
+                    Vector::from([1., 0.]),
+                )),
+                GlobalCurve::from_kind(CurveKind::Circle(Circle::new(
+                    [0., 0., height * v],
+                    circle.a(),
+                    circle.b(),
+                ))),
+            );
+            let ends = range.map(|t| Vertex::new([t], curve, vertex));
+
+            Edge::from_curve_and_vertices(
+                curve,
+                VerticesOfEdge::from_vertices(ends),
+            )
+        };
+        let seam = |u: f64, ends: [usize; 2]| {
+            Edge::build(surface).line_segment_from_vertices(
+                ends.map(|i| (Point::from([u, i as f64]), vertices[i])),
+            )
+        };
+
+        let tau = Scalar::TAU.into_f64();
+        let side = Face::new(surface).with_exteriors([Cycle::new(
+            surface,
+            [
+                arc(0., [0., tau], vertices[0]),
+                seam(tau, [0, 1]),
+                arc(1., [tau, 0.], vertices[1]),
+                seam(0., [1, 0]),
+            ],
+        )]);
+
+        let properties = mass_properties([&bottom, &top, &side]);
+
+        let pi = Scalar::PI.into_f64();
+        let mass = pi * radius * radius * height;
+        assert_approx_eq(properties.volume, mass);
+        assert_approx_eq(
+            properties.area,
+            2. * pi * radius * radius + 2. * pi * radius * height,
+        );
+        for (coord, expected) in properties
+            .centroid
+            .coords
+            .components
+            .into_iter()
+            .zip([0., 0., height / 2.])
+        {
+            assert_approx_eq(coord, expected);
+        }
+
+        // For a solid cylinder with radius r, height h, and mass m, the moment
+        // of inertia around its axis is m * r² / 2, and around the other axes
+        // through its center m * (3r² + h²) / 12.
+        let inertia = properties.inertia;
+        let transverse = mass * (3. * radius * radius + height * height) / 12.;
+        assert_approx_eq(inertia[0][0], transverse);
+        assert_approx_eq(inertia[1][1], transverse);
+        assert_approx_eq(inertia[2][2], mass * radius * radius / 2.);
+        assert_approx_eq(inertia[0][1], 0.);
+        assert_approx_eq(inertia[1][2], 0.);
+        assert_approx_eq(inertia[2][0], 0.);
+    }
+
+    #[test]
+    fn disc() {
+        let surface = Surface::xy_plane();
+
+        let exterior = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::from(2.))],
+        );
+        let interior = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::from(1.))],
+        );
+        let face = Face::new(surface)
+            .with_exteriors([exterior])
+            .with_interiors([interior]);
+
+        let properties = mass_properties([&face]);
+
+        // The area doesn't depend on any approximation of the circles.
+        assert_approx_eq(properties.area, Scalar::PI.into_f64() * 3.);
+        assert_eq!(properties.centroid, Point::origin());
+    }
+
+    fn assert_approx_eq(value: Scalar, expected: f64) {
+        let difference = (value.into_f64() - expected).abs();
+        assert!(
+            difference < 1e-9,
+            "Expected {expected}, got {value:?} (difference: {difference})"
+        );
+    }
+}
@@ -5,11 +5,21 @@
 
 mod triangulate;
 
+pub mod adjacency;
 pub mod approx;
+pub mod blend;
+pub mod boolean;
+pub mod deviation;
+pub mod distance;
+pub mod hollow;
 pub mod intersect;
+pub mod mass_properties;
+pub mod projection;
 pub mod reverse;
+pub mod section;
+pub mod sew;
 pub mod sweep;
 pub mod transform;
 pub mod validate;
 
-pub use self::triangulate::triangulate;
+pub use self::triangulate::{triangulate, triangulate_refined, Refinement};
@@ -0,0 +1,352 @@
+//! Orthographic projection of solids into 2D drawings
+//!
+//! See [`project`].
+
+use std::collections::BTreeMap;
+
+use fj_interop::drawing::{Drawing, DrawingLine, LineKind, Visibility};
+use fj_math::{Point, Scalar, Vector};
+
+use crate::objects::{CurveKind, Face, Surface};
+
+use super::{
+    approx::{Approx, Tolerance},
+    distance::project_onto_plane,
+    intersect::{Intersect, Ray},
+};
+
+/// The number of samples per segment, used to determine visibility
+///
+/// If the visibility changes between two samples, the point where it changes
+/// is refined further. Features of the occluding faces that are smaller than
+/// the distance between samples might be missed.
+const SAMPLES_PER_SEGMENT: u64 = 8;
+
+/// Project the faces of a solid onto a plane, creating a drawing
+///
+/// The projection is orthographic. The viewer looks at the plane from the side
+/// its normal points to. The lines of the drawing are defined in the surface
+/// coordinates of the plane.
+///
+/// The edges of the faces are approximated, using the provided tolerance, then
+/// classified into visible and hidden parts, by casting rays towards the
+/// viewer. Curved faces in triangle representation additionally contribute
+/// their silhouettes, the lines where they turn away from the viewer. Edges
+/// between the triangles of those faces are not drawn.
+pub fn project<'r>(
+    faces: impl IntoIterator<Item = &'r Face>,
+    plane: &Surface,
+    tolerance: Tolerance,
+) -> Result<Drawing, ProjectionError> {
+    let (origin, u, v) = {
+        let surface = match plane {
+            Surface::SweptCurve(surface) => surface,
+        };
+
+        match surface.curve {
+            CurveKind::Line(line) => {
+                (line.origin(), line.direction(), surface.path)
+            }
+            CurveKind::Circle(_) => {
+                return Err(ProjectionError::Unsupported(
+                    "non-planar projection surface",
+                ))
+            }
+        }
+    };
+    let towards_viewer = u.cross(&v).normalize();
+
+    let faces = faces.into_iter().collect::<Vec<_>>();
+
+    let mut polylines: Vec<(Vec<Point<3>>, LineKind)> = Vec::new();
+    for &face in &faces {
+        match face.triangles() {
+            Some(triangles) => {
+                let triangles =
+                    triangles.iter().map(|(triangle, _)| triangle.points());
+                for segment in silhouette(triangles, towards_viewer) {
+                    polylines.push((segment.to_vec(), LineKind::Silhouette));
+                }
+            }
+            None => {
+                for edge in face.all_cycles().flat_map(|cycle| cycle.edges()) {
+                    let mut points = edge
+                        .approx(tolerance, ())
+                        .into_iter()
+                        .map(|(_, point)| point)
+                        .collect::<Vec<_>>();
+
+                    // The approximation doesn't include the end of the edge.
+                    let end = match edge.vertices().get() {
+                        Some([_, b]) => b.global().position(),
+                        None => points[0],
+                    };
+                    points.push(end);
+
+                    // Edges are shared between faces. Only draw them once.
+                    let is_duplicate = polylines.iter().any(|(other, _)| {
+                        same_polyline(&points, other, tolerance.inner())
+                    });
+                    if !is_duplicate {
+                        polylines.push((points, LineKind::Edge));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut drawing = Drawing::new();
+
+    for (points, kind) in polylines {
+        let pieces = split_by_visibility(
+            &points,
+            &faces,
+            towards_viewer,
+            tolerance.inner(),
+        );
+
+        for (visibility, points) in pieces {
+            let points = points
+                .into_iter()
+                .map(|point| {
+                    let (u, v) = project_onto_plane(point - origin, u, v);
+                    Point::from([u, v])
+                })
+                .collect::<Vec<_>>();
+
+            // Lines that are parallel to the view direction are projected
+            // into a single point. There's nothing to draw for those.
+            let length = points
+                .windows(2)
+                .map(|segment| (segment[1] - segment[0]).magnitude())
+                .fold(Scalar::ZERO, |a, b| a + b);
+            if length <= tolerance.inner() {
+                continue;
+            }
+
+            drawing.lines.push(DrawingLine {
+                points,
+                kind,
+                visibility,
+            });
+        }
+    }
+
+    Ok(drawing)
+}
+
+/// An error that can occur when projecting a solid
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectionError {
+    /// The geometry is not supported by the operation
+    #[error("Projection not supported: {0}")]
+    Unsupported(&'static str),
+}
+
+/// Find the edges of a triangle mesh, where it turns away from the viewer
+///
+/// These are the edges between a triangle that faces the viewer, and one that
+/// doesn't.
+fn silhouette(
+    triangles: impl IntoIterator<Item = [Point<3>; 3]>,
+    towards_viewer: Vector<3>,
+) -> Vec<[Point<3>; 2]> {
+    let mut edges = BTreeMap::new();
+
+    for [a, b, c] in triangles {
+        let normal = (b - a).cross(&(c - a));
+        let faces_viewer = normal.dot(&towards_viewer) > Scalar::ZERO;
+
+        for [start, end] in [[a, b], [b, c], [c, a]] {
+            let key = if start < end {
+                [start, end]
+            } else {
+                [end, start]
+            };
+            edges.entry(key).or_insert_with(Vec::new).push(faces_viewer);
+        }
+    }
+
+    edges
+        .into_iter()
+        .filter(|(_, faces_viewer)| {
+            faces_viewer.contains(&true) && faces_viewer.contains(&false)
+        })
+        .map(|(edge, _)| edge)
+        .collect()
+}
+
+/// Split a polyline into pieces that are either completely visible or hidden
+fn split_by_visibility(
+    points: &[Point<3>],
+    faces: &[&Face],
+    towards_viewer: Vector<3>,
+    tolerance: Scalar,
+) -> Vec<(Visibility, Vec<Point<3>>)> {
+    let mut pieces: Vec<(Visibility, Vec<Point<3>>)> = Vec::new();
+
+    for segment in points.windows(2) {
+        let [a, b] = [segment[0], segment[1]];
+        // Return the end points exactly, so pieces from consecutive segments
+        // can be joined.
+        let point_at = |s: Scalar| {
+            if s == Scalar::ONE {
+                b
+            } else {
+                a + (b - a) * s
+            }
+        };
+        let visibility_at = |s: Scalar| {
+            visibility(point_at(s), faces, towards_viewer, tolerance)
+        };
+
+        // Sample the visibility in the middle of sub-segments, then refine
+        // the points where it changes.
+        let step = Scalar::ONE / SAMPLES_PER_SEGMENT as f64;
+        let samples = (0..SAMPLES_PER_SEGMENT)
+            .map(|i| step * (i as f64 + 0.5))
+            .map(|s| (s, visibility_at(s)))
+            .collect::<Vec<_>>();
+
+        let mut breaks = vec![(Scalar::ZERO, samples[0].1)];
+        for pair in samples.windows(2) {
+            let [(mut low, before), (mut high, after)] = [pair[0], pair[1]];
+            if before == after {
+                continue;
+            }
+
+            while (high - low) * (b - a).magnitude() > tolerance {
+                let middle = (low + high) / 2.;
+                if visibility_at(middle) == before {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+
+            breaks.push(((low + high) / 2., after));
+        }
+
+        for (i, &(start, visibility)) in breaks.iter().enumerate() {
+            let end = breaks.get(i + 1).map_or(Scalar::ONE, |&(end, _)| end);
+            let [start, end] = [start, end].map(point_at);
+
+            match pieces.last_mut() {
+                Some((last_visibility, points))
+                    if *last_visibility == visibility
+                        && points.last() == Some(&start) =>
+                {
+                    points.push(end);
+                }
+                _ => pieces.push((visibility, vec![start, end])),
+            }
+        }
+    }
+
+    pieces
+}
+
+/// Determine whether a point is hidden by any of the faces
+fn visibility(
+    point: Point<3>,
+    faces: &[&Face],
+    towards_viewer: Vector<3>,
+    tolerance: Scalar,
+) -> Visibility {
+    // The point is on the boundary of some faces. Start the ray a bit closer
+    // to the viewer, so those don't count.
+    let ray = Ray::new(point + towards_viewer * tolerance, towards_viewer);
+
+    let hidden = faces.iter().any(|&face| (&ray, face).intersect().is_some());
+
+    if hidden {
+        Visibility::Hidden
+    } else {
+        Visibility::Visible
+    }
+}
+
+fn same_polyline(a: &[Point<3>], b: &[Point<3>], tolerance: Scalar) -> bool {
+    let same = |b: &mut dyn Iterator<Item = &Point<3>>| {
+        a.iter()
+            .zip(b)
+            .all(|(a, b)| (*a - *b).magnitude() <= tolerance)
+    };
+
+    a.len() == b.len() && (same(&mut b.iter()) || same(&mut b.iter().rev()))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        drawing::{LineKind, Visibility},
+        mesh::Color,
+    };
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{approx::Tolerance, sweep::Sweep},
+        builder::fixtures::cube,
+        iter::ObjectIters,
+        objects::{Cycle, Edge, Face, Surface},
+    };
+
+    use super::project;
+
+    #[test]
+    fn cube() -> anyhow::Result<()> {
+        let solid = cube()?;
+
+        // Look at the cube from a corner. The three edges that meet at the
+        // opposite corner are hidden.
+        let plane = Surface::plane_from_points([
+            [0., 0., 0.],
+            [1., -1., 0.],
+            [0., 1., -1.],
+        ]);
+        let drawing = project(solid.face_iter(), &plane, tolerance)?;
+
+        assert_eq!(
+            drawing.lines_with_visibility(Visibility::Visible).count(),
+            9
+        );
+        assert_eq!(
+            drawing.lines_with_visibility(Visibility::Hidden).count(),
+            3
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn cylinder() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let cycle = Cycle::new(
+            surface,
+            [Edge::build(surface).circle_from_radius(Scalar::ONE)],
+        );
+        let face = Face::new(surface).with_exteriors([cycle]);
+
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let shell =
+            face.sweep([0., 0., 2.], tolerance, Color([255, 0, 0, 255]));
+
+        // Look at the cylinder from the side. Its outline consists of the two
+        // silhouettes, plus the circles at the top and bottom, which appear as
+        // straight lines.
+        let drawing =
+            project(shell.face_iter(), &Surface::xz_plane(), tolerance)?;
+
+        let silhouettes = drawing
+            .lines
+            .iter()
+            .filter(|line| line.kind == LineKind::Silhouette)
+            .collect::<Vec<_>>();
+        assert_eq!(silhouettes.len(), 2);
+        assert!(silhouettes
+            .iter()
+            .all(|line| line.visibility == Visibility::Visible));
+
+        Ok(())
+    }
+}
@@ -19,6 +19,7 @@ impl Reverse for Face {
             .with_exteriors(exteriors)
             .with_interiors(interiors)
             .with_color(self.color())
+            .with_tolerance(self.tolerance())
     }
 }
 
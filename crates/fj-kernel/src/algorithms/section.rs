@@ -0,0 +1,424 @@
+//! Plane cross-sections of solids
+//!
+//! See [`section`].
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::objects::{CurveKind, Cycle, Face, Sketch, Surface};
+
+use super::intersect::{CurveFaceIntersection, SurfaceSurfaceIntersection};
+
+/// Points closer than this are considered identical, when chaining segments
+const POINT_DISTANCE: f64 = 1e-9;
+
+/// Compute the cross-section of a solid with a plane
+///
+/// Intersects each of the provided faces with the plane, and chains the
+/// resulting segments into closed cycles. Cycles that are nested within other
+/// cycles become holes in the faces of the returned sketch, which is defined
+/// on `plane`.
+///
+/// Exterior cycles of the sketch are oriented counter-clockwise with respect
+/// to the plane, interior cycles clockwise.
+///
+/// # Limitations
+///
+/// Only solids made of planar faces bounded by straight edges, or of faces in
+/// triangle representation, are supported. The plane must not touch any
+/// vertex of the solid, as the intersections would be ambiguous there.
+pub fn section<'r>(
+    faces: impl IntoIterator<Item = &'r Face>,
+    plane: &Surface,
+) -> Result<Sketch, SectionError> {
+    let (plane_origin, plane_normal) = plane_of(plane)
+        .ok_or(SectionError::Unsupported("non-planar cutting surface"))?;
+
+    let mut segments = Vec::new();
+
+    for face in faces {
+        if let Some(triangles) = face.triangles() {
+            for (triangle, _) in triangles {
+                let points = triangle.points();
+                if let Some(segment) =
+                    intersect_triangle(points, plane_origin, plane_normal)?
+                {
+                    segments.push(
+                        segment.map(|point| surface_coords(plane, point)),
+                    );
+                }
+            }
+
+            continue;
+        }
+        if plane_of(face.surface()).is_none() {
+            return Err(SectionError::Unsupported("non-planar face"));
+        }
+
+        let mut above = false;
+        let mut below = false;
+
+        for edge in face.all_cycles().flat_map(|cycle| cycle.edges()) {
+            let vertices = match (edge.curve().kind(), edge.vertices().get()) {
+                (CurveKind::Line(_), Some(vertices)) => vertices,
+                _ => return Err(SectionError::Unsupported("curved edge")),
+            };
+
+            for vertex in vertices {
+                let distance = (vertex.global().position() - plane_origin)
+                    .dot(&plane_normal);
+
+                if distance.abs() < Scalar::from_f64(POINT_DISTANCE) {
+                    return Err(SectionError::PlaneTouchesVertex);
+                }
+                if distance > Scalar::ZERO {
+                    above = true;
+                } else {
+                    below = true;
+                }
+            }
+        }
+
+        // If the face is completely on one side of the plane, there's nothing
+        // to intersect. This also skips faces that are parallel to the plane.
+        if !(above && below) {
+            continue;
+        }
+
+        let intersection = match SurfaceSurfaceIntersection::compute([
+            plane,
+            face.surface(),
+        ]) {
+            Some(intersection) => intersection,
+            None => continue,
+        };
+        let [on_plane, on_face] = intersection.intersection_curves;
+
+        // Both curves represent the same global curve, so curve coordinates
+        // on one apply to the other too.
+        let intervals = CurveFaceIntersection::compute(&on_face, face);
+        for interval in intervals.intervals {
+            segments.push(
+                [interval.start, interval.end].map(|point| {
+                    on_plane.kind().point_from_curve_coords(point)
+                }),
+            );
+        }
+    }
+
+    let polygons = chain_segments(segments)?;
+
+    // Polygons that are contained in an odd number of other polygons are
+    // holes. They belong to the innermost polygon that contains them.
+    let depths = polygons
+        .iter()
+        .enumerate()
+        .map(|(i, polygon)| {
+            polygons
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| {
+                    i != j && polygon_contains_point(other, polygon[0])
+                })
+                .count()
+        })
+        .collect::<Vec<_>>();
+
+    let mut faces = Vec::new();
+
+    for (i, exterior) in polygons.iter().enumerate() {
+        if depths[i] % 2 == 1 {
+            continue;
+        }
+
+        let interiors = polygons
+            .iter()
+            .enumerate()
+            .filter(|&(j, interior)| {
+                depths[j] == depths[i] + 1
+                    && polygon_contains_point(exterior, interior[0])
+            })
+            .map(|(_, interior)| {
+                Cycle::build(*plane)
+                    .polygon_from_points(oriented(interior, false))
+            });
+
+        let face = Face::new(*plane)
+            .with_exteriors([Cycle::build(*plane)
+                .polygon_from_points(oriented(exterior, true))])
+            .with_interiors(interiors);
+
+        faces.push(face);
+    }
+
+    Ok(Sketch::new().with_faces(faces))
+}
+
+/// An error that can occur when computing a cross-section
+#[derive(Debug, thiserror::Error)]
+pub enum SectionError {
+    /// The plane touches a vertex of the solid
+    #[error("Cutting plane touches a vertex of the solid")]
+    PlaneTouchesVertex,
+
+    /// The intersection segments don't form closed cycles
+    ///
+    /// This happens, if the faces don't form a closed shell.
+    #[error("Cross-section is not closed")]
+    NotClosed,
+
+    /// The geometry is not supported by the operation
+    #[error("Cross-section not supported: {0}")]
+    Unsupported(&'static str),
+}
+
+fn plane_of(surface: &Surface) -> Option<(Point<3>, Vector<3>)> {
+    let surface = match surface {
+        Surface::SweptCurve(surface) => surface,
+    };
+
+    match surface.curve {
+        CurveKind::Line(line) => Some((
+            line.origin(),
+            line.direction().cross(&surface.path).normalize(),
+        )),
+        CurveKind::Circle(_) => None,
+    }
+}
+
+/// Intersect a triangle with the plane
+///
+/// Returns `None`, if the triangle is completely on one side of the plane.
+fn intersect_triangle(
+    points: [Point<3>; 3],
+    plane_origin: Point<3>,
+    plane_normal: Vector<3>,
+) -> Result<Option<[Point<3>; 2]>, SectionError> {
+    let distances =
+        points.map(|point| (point - plane_origin).dot(&plane_normal));
+
+    if distances
+        .iter()
+        .any(|distance| distance.abs() < Scalar::from_f64(POINT_DISTANCE))
+    {
+        return Err(SectionError::PlaneTouchesVertex);
+    }
+
+    let mut crossings = Vec::new();
+    for i in 0..3 {
+        let [mut a, mut b] = [i, (i + 1) % 3];
+        if (distances[a] > Scalar::ZERO) == (distances[b] > Scalar::ZERO) {
+            continue;
+        }
+
+        // Neighboring triangles share this edge, but in opposite direction.
+        // Always interpolate from the point above the plane, so they compute
+        // exactly the same crossing.
+        if distances[a] < Scalar::ZERO {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        let t = distances[a] / (distances[a] - distances[b]);
+        crossings.push(points[a] + (points[b] - points[a]) * t);
+    }
+
+    match crossings.as_slice() {
+        [a, b] => Ok(Some([*a, *b])),
+        _ => Ok(None),
+    }
+}
+
+/// Convert a point on the plane into its surface coordinates
+fn surface_coords(plane: &Surface, point: Point<3>) -> Point<2> {
+    let surface = match plane {
+        Surface::SweptCurve(surface) => surface,
+    };
+    let (origin, u) = match surface.curve {
+        CurveKind::Line(line) => (line.origin(), line.direction()),
+        CurveKind::Circle(_) => unreachable!("Plane must be defined by line"),
+    };
+    let v = surface.path;
+
+    // The axes of the plane aren't necessarily orthogonal, so solve for the
+    // coordinates, instead of projecting onto the axes.
+    let d = point - origin;
+    let [uu, uv, vv] = [u.dot(&u), u.dot(&v), v.dot(&v)];
+    let [du, dv] = [d.dot(&u), d.dot(&v)];
+    let det = uu * vv - uv * uv;
+
+    Point::from([(du * vv - dv * uv) / det, (dv * uu - du * uv) / det])
+}
+
+/// Chain segments with matching end points into closed polygons
+fn chain_segments(
+    mut segments: Vec<[Point<2>; 2]>,
+) -> Result<Vec<Vec<Point<2>>>, SectionError> {
+    let max_distance = Scalar::from_f64(POINT_DISTANCE);
+
+    let mut polygons = Vec::new();
+
+    while let Some([start, mut end]) = segments.pop() {
+        let mut polygon = vec![start];
+
+        while (end - start).magnitude() >= max_distance {
+            // Segments aren't oriented, so the next one might connect with
+            // either of its end points.
+            let (i, flip, distance) = segments
+                .iter()
+                .enumerate()
+                .flat_map(|(i, [a, b])| {
+                    [
+                        (i, false, (*a - end).magnitude()),
+                        (i, true, (*b - end).magnitude()),
+                    ]
+                })
+                .min_by_key(|&(_, _, distance)| distance)
+                .ok_or(SectionError::NotClosed)?;
+
+            if distance >= max_distance {
+                return Err(SectionError::NotClosed);
+            }
+
+            let [a, b] = segments.swap_remove(i);
+            polygon.push(end);
+            end = if flip { a } else { b };
+        }
+
+        polygons.push(polygon);
+    }
+
+    Ok(polygons)
+}
+
+/// Orient a polygon counter-clockwise, or clockwise
+fn oriented(polygon: &[Point<2>], counter_clockwise: bool) -> Vec<Point<2>> {
+    let mut polygon = polygon.to_vec();
+
+    if (signed_area(&polygon) > Scalar::ZERO) != counter_clockwise {
+        polygon.reverse();
+    }
+
+    polygon
+}
+
+fn signed_area(polygon: &[Point<2>]) -> Scalar {
+    let mut area = Scalar::ZERO;
+
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        area = area + (a.u * b.v - b.u * a.v);
+    }
+
+    area / 2.
+}
+
+fn polygon_contains_point(polygon: &[Point<2>], point: Point<2>) -> bool {
+    let mut inside = false;
+
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+
+        if (a.v > point.v) != (b.v > point.v) {
+            let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+            if point.u < u {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::Color;
+    use fj_math::{Point, Scalar, Triangle};
+
+    use crate::{
+        algorithms::{
+            hollow::hollow, mass_properties::mass_properties,
+            transform::TransformObject,
+        },
+        builder::fixtures::cube,
+        iter::ObjectIters,
+        objects::{Face, Surface},
+    };
+
+    use super::{section, SectionError};
+
+    #[test]
+    fn section_cube() -> anyhow::Result<()> {
+        let solid = cube()?;
+        let plane = Surface::xy_plane().translate([0., 0., 1.]);
+
+        let sketch = section(solid.face_iter(), &plane)?;
+
+        let faces = sketch.faces().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].edge_iter().count(), 4);
+
+        let properties = mass_properties(sketch.faces());
+        assert_approx_eq(properties.area, 4.);
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_hollow_cube() -> anyhow::Result<()> {
+        let solid = hollow(&cube()?, 0.25, [])?;
+        let plane = Surface::xy_plane().translate([0., 0., 1.]);
+
+        let sketch = section(solid.face_iter(), &plane)?;
+
+        let faces = sketch.faces().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+
+        let properties = mass_properties(sketch.faces());
+        assert_approx_eq(properties.area, 1.75);
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_triangles() -> anyhow::Result<()> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [2., 0., 0.], [0., 2., 0.], [0., 0., 2.]]
+                .map(Point::from);
+        let faces =
+            [[a, c, b], [a, b, d], [b, c, d], [c, a, d]].map(|points| {
+                Face::from_triangles(vec![(
+                    Triangle::from(points),
+                    Color([255, 0, 0, 255]),
+                )])
+            });
+        let plane = Surface::xy_plane().translate([0., 0., 1.]);
+
+        let sketch = section(&faces, &plane)?;
+
+        let faces = sketch.faces().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].edge_iter().count(), 3);
+
+        let properties = mass_properties(sketch.faces());
+        assert_approx_eq(properties.area, 0.5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn plane_touches_vertex() -> anyhow::Result<()> {
+        let solid = cube()?;
+        let plane = Surface::xy_plane().translate([0., 0., 2.]);
+
+        assert!(matches!(
+            section(solid.face_iter(), &plane),
+            Err(SectionError::PlaneTouchesVertex)
+        ));
+
+        Ok(())
+    }
+
+    fn assert_approx_eq(value: Scalar, expected: f64) {
+        assert!((value.into_f64() - expected).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,417 @@
+//! Sewing of loose faces into shells
+//!
+//! Faces that are created independently of each other, for example by
+//! [`transform_faces`] or by combining shapes in a group, don't share their
+//! vertices and edges, even if they touch. Sewing merges vertices that are
+//! close to each other and splits edges, where a vertex of another face lies
+//! on them, so that neighboring faces end up referencing the same topology.
+//!
+//! [`transform_faces`]: crate::algorithms::transform::transform_faces
+
+use std::collections::BTreeMap;
+
+use fj_math::{Circle, Point, Scalar};
+
+use crate::{
+    iter::ObjectIters,
+    objects::{
+        Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalEdge,
+        GlobalVertex, Shell, Surface, Vertex, VerticesOfEdge,
+    },
+};
+
+use super::adjacency::Adjacency;
+
+/// Sew the provided faces into a shell
+///
+/// Global vertices that are closer than `max_distance` to each other are
+/// merged. Straight edges that have a merged vertex lying on them (within
+/// `max_distance`) are split at that vertex. Continuous circular edges are
+/// merged, if their circles coincide within `max_distance`, regardless of their
+/// direction.
+///
+/// The local (surface) coordinates of merged vertices are left unchanged, so
+/// `max_distance` should be kept small, compared to the size of the faces.
+///
+/// Faces that use triangle representation are passed through as-is, and are
+/// not considered when determining whether the resulting shell is closed.
+pub fn sew(
+    faces: impl IntoIterator<Item = Face>,
+    max_distance: impl Into<Scalar>,
+) -> SewedShell {
+    let max_distance = max_distance.into();
+    let faces = faces.into_iter().collect::<Vec<_>>();
+
+    let mut vertices = VertexMerger::new(max_distance);
+    for face in &faces {
+        if face.triangles().is_some() {
+            continue;
+        }
+
+        for vertex in face.global_vertex_iter() {
+            vertices.merge(*vertex);
+        }
+    }
+
+    let mut circles = CircleMerger::new(max_distance);
+
+    let faces = faces.into_iter().filter_map(|face| {
+        if face.triangles().is_some() {
+            return Some(face);
+        }
+
+        let surface = *face.surface();
+
+        let exteriors = face
+            .exteriors()
+            .filter_map(|cycle| {
+                sew_cycle(surface, cycle, &vertices, &mut circles)
+            })
+            .collect::<Vec<_>>();
+        let interiors = face
+            .interiors()
+            .filter_map(|cycle| {
+                sew_cycle(surface, cycle, &vertices, &mut circles)
+            })
+            .collect::<Vec<_>>();
+
+        // If all edges of the face have been merged into nothing, the face was
+        // degenerate to begin with.
+        if exteriors.is_empty() {
+            return None;
+        }
+
+        Some(
+            Face::new(surface)
+                .with_exteriors(exteriors)
+                .with_interiors(interiors)
+                .with_color(face.color())
+                .with_tolerance(face.tolerance()),
+        )
+    });
+
+    let shell = Shell::new().with_faces(faces);
+    let open_edges = Adjacency::from_shell(&shell)
+        .boundary_edges()
+        .copied()
+        .collect();
+
+    SewedShell { shell, open_edges }
+}
+
+/// The result of [`sew`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SewedShell {
+    shell: Shell,
+    open_edges: Vec<GlobalEdge>,
+}
+
+impl SewedShell {
+    /// Access the sewed shell
+    pub fn shell(&self) -> &Shell {
+        &self.shell
+    }
+
+    /// Consume this instance and return the sewed shell
+    pub fn into_shell(self) -> Shell {
+        self.shell
+    }
+
+    /// Access the edges that bound only a single face of the shell
+    ///
+    /// See [`Adjacency::boundary_edges`].
+    pub fn open_edges(&self) -> impl Iterator<Item = &GlobalEdge> + '_ {
+        self.open_edges.iter()
+    }
+
+    /// Indicate whether the sewed shell is closed
+    ///
+    /// This is the case, if no edge bounds only a single face.
+    pub fn is_closed(&self) -> bool {
+        self.open_edges.is_empty()
+    }
+}
+
+fn sew_cycle(
+    surface: Surface,
+    cycle: &Cycle,
+    vertices: &VertexMerger,
+    circles: &mut CircleMerger,
+) -> Option<Cycle> {
+    let mut edges = Vec::new();
+
+    for edge in cycle.edges() {
+        let [a, b] = match edge.vertices().get() {
+            Some(edge_vertices) => edge_vertices,
+            None => {
+                edges.push(sew_continuous_edge(edge, circles));
+                continue;
+            }
+        };
+
+        let [a, b] = [a, b].map(|vertex| {
+            let position = edge
+                .curve()
+                .kind()
+                .point_from_curve_coords(vertex.position());
+            let global = vertices.get(vertex.global());
+
+            (vertex.position(), position, global)
+        });
+
+        if a.2 == b.2 {
+            // Both vertices have been merged into one. The edge has collapsed.
+            continue;
+        }
+
+        match edge.curve().global().kind() {
+            CurveKind::Circle(_) => {
+                let sewed = edge.vertices().map(|vertex| {
+                    let global = vertices.get(vertex.global());
+                    Vertex::new(vertex.position(), *vertex.curve(), global)
+                });
+
+                edges.push(Edge::from_curve_and_vertices(*edge.curve(), sewed));
+            }
+            CurveKind::Line(_) => {
+                let mut points = vec![(a.1, a.2)];
+                points.extend(split_points(edge, a, b, vertices));
+                points.push((b.1, b.2));
+
+                for segment in points.windows(2) {
+                    edges.push(
+                        Edge::build(surface).line_segment_from_vertices([
+                            segment[0], segment[1],
+                        ]),
+                    );
+                }
+            }
+        }
+    }
+
+    if edges.is_empty() {
+        return None;
+    }
+
+    Some(Cycle::new(surface, edges))
+}
+
+fn sew_continuous_edge(edge: &Edge, circles: &mut CircleMerger) -> Edge {
+    let global = match edge.curve().global().kind() {
+        CurveKind::Circle(circle) => {
+            GlobalCurve::from_kind(CurveKind::Circle(circles.merge(*circle)))
+        }
+        CurveKind::Line(_) => *edge.curve().global(),
+    };
+
+    let curve =
+        Curve::new(*edge.curve().surface(), *edge.curve().kind(), global);
+    Edge::from_curve_and_vertices(curve, VerticesOfEdge::none())
+}
+
+/// Find the merged vertices that lie on a straight edge, between its ends
+///
+/// Returns the local and global form of each vertex, ordered from `a` to `b`.
+fn split_points(
+    edge: &Edge,
+    a: (Point<1>, Point<2>, GlobalVertex),
+    b: (Point<1>, Point<2>, GlobalVertex),
+    vertices: &VertexMerger,
+) -> Vec<(Point<2>, GlobalVertex)> {
+    let start = a.2.position();
+    let direction = b.2.position() - start;
+    let length = direction.magnitude();
+
+    let mut points = Vec::new();
+
+    for &vertex in vertices.all() {
+        if vertex == a.2 || vertex == b.2 {
+            continue;
+        }
+
+        let t = (vertex.position() - start).dot(&direction) / (length * length);
+
+        // Vertices close to either end of the edge would create a segment
+        // that is shorter than the merge distance.
+        let margin = vertices.max_distance / length;
+        if t <= margin || t >= Scalar::ONE - margin {
+            continue;
+        }
+
+        let on_edge = start + direction * t;
+        if (vertex.position() - on_edge).magnitude() > vertices.max_distance {
+            continue;
+        }
+
+        let position_on_curve = a.0 + (b.0 - a.0) * t;
+        let position_on_surface = edge
+            .curve()
+            .kind()
+            .point_from_curve_coords(position_on_curve);
+
+        points.push((t, position_on_surface, vertex));
+    }
+
+    points.sort_by_key(|&(t, _, _)| t);
+    points
+        .into_iter()
+        .map(|(_, position, vertex)| (position, vertex))
+        .collect()
+}
+
+/// Merges global vertices that are close to each other
+///
+/// Each vertex is mapped to the first vertex that was merged and is closer than
+/// the maximum distance.
+struct VertexMerger {
+    max_distance: Scalar,
+    merged: Vec<GlobalVertex>,
+    by_vertex: BTreeMap<GlobalVertex, GlobalVertex>,
+}
+
+impl VertexMerger {
+    fn new(max_distance: Scalar) -> Self {
+        Self {
+            max_distance,
+            merged: Vec::new(),
+            by_vertex: BTreeMap::new(),
+        }
+    }
+
+    fn merge(&mut self, vertex: GlobalVertex) {
+        if self.by_vertex.contains_key(&vertex) {
+            return;
+        }
+
+        let merged = self
+            .merged
+            .iter()
+            .find(|merged| {
+                Point::distance(&merged.position(), &vertex.position())
+                    <= self.max_distance
+            })
+            .copied();
+
+        let merged = merged.unwrap_or_else(|| {
+            self.merged.push(vertex);
+            vertex
+        });
+
+        self.by_vertex.insert(vertex, merged);
+    }
+
+    fn get(&self, vertex: &GlobalVertex) -> GlobalVertex {
+        // All vertices are merged before any are queried.
+        self.by_vertex[vertex]
+    }
+
+    fn all(&self) -> impl Iterator<Item = &GlobalVertex> + '_ {
+        self.merged.iter()
+    }
+}
+
+/// Merges circles that coincide with each other
+struct CircleMerger {
+    max_distance: Scalar,
+    merged: Vec<Circle<3>>,
+}
+
+impl CircleMerger {
+    fn new(max_distance: Scalar) -> Self {
+        Self {
+            max_distance,
+            merged: Vec::new(),
+        }
+    }
+
+    fn merge(&mut self, circle: Circle<3>) -> Circle<3> {
+        for &merged in &self.merged {
+            if self.coincide(&merged, &circle) {
+                return merged;
+            }
+            if self.coincide(&merged.reverse(), &circle) {
+                return merged.reverse();
+            }
+        }
+
+        self.merged.push(circle);
+        circle
+    }
+
+    fn coincide(&self, a: &Circle<3>, b: &Circle<3>) -> bool {
+        Point::distance(&a.center(), &b.center()) <= self.max_distance
+            && (a.a() - b.a()).magnitude() <= self.max_distance
+            && (a.b() - b.b()).magnitude() <= self.max_distance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{adjacency::Adjacency, transform::TransformObject},
+        iter::ObjectIters,
+        objects::{Face, Shell, Surface},
+    };
+
+    use super::sew;
+
+    #[test]
+    fn sew_cube() {
+        let faces = Shell::build()
+            .cube_from_edge_length(2.)
+            .into_faces()
+            .enumerate()
+            .map(|(i, face)| {
+                // Displace each face slightly, so none of them share vertices
+                // anymore.
+                let offset = i as f64 * 1e-9;
+                face.translate([offset, offset, offset])
+            })
+            .collect::<Vec<_>>();
+
+        let shell = Shell::new().with_faces(faces.clone());
+        assert!(!Adjacency::from_shell(&shell).is_closed_manifold());
+
+        let sewed = sew(faces, 1e-6);
+        assert!(sewed.is_closed());
+        assert_eq!(sewed.shell().global_vertex_iter().count(), 8);
+    }
+
+    #[test]
+    fn sew_open_shell() {
+        let faces = Shell::build()
+            .cube_from_edge_length(2.)
+            .into_faces()
+            .skip(1);
+
+        let sewed = sew(faces, 1e-6);
+        assert!(!sewed.is_closed());
+        assert_eq!(sewed.open_edges().count(), 4);
+    }
+
+    #[test]
+    fn split_edge() {
+        let surface = Surface::xy_plane();
+
+        let large = Face::build(surface)
+            .polygon_from_points([[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+            .into_face();
+        let lower = Face::build(surface)
+            .polygon_from_points([[2., 0.], [3., 0.], [3., 1.], [2., 1.]])
+            .into_face();
+        let upper = Face::build(surface)
+            .polygon_from_points([[2., 1.], [3., 1.], [3., 2.], [2., 2.]])
+            .into_face();
+
+        let sewed = sew([large, lower, upper], 1e-6);
+
+        let large = sewed
+            .shell()
+            .faces()
+            .find(|face| face.edge_iter().count() == 5);
+        assert!(large.is_some());
+
+        // 13 edges in total, 3 of which are shared between two faces.
+        assert_eq!(sewed.open_edges().count(), 7);
+    }
+}
@@ -82,11 +82,13 @@ impl TransformObject for Face {
         let interiors = transform_cycles(self.interiors(), transform);
 
         let color = self.color();
+        let tolerance = self.tolerance();
 
         Face::new(surface)
             .with_exteriors(exteriors)
             .with_interiors(interiors)
             .with_color(color)
+            .with_tolerance(tolerance)
     }
 }
 
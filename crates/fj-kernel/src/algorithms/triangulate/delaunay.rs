@@ -1,35 +1,270 @@
-use fj_math::{Point, Scalar, Triangle, Winding};
-use spade::HasPosition;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-/// Create a Delaunay triangulation of all points
+use fj_math::{Point, Scalar, Segment, Triangle, Winding};
+use spade::{
+    handles::{FixedFaceHandle, InnerTag},
+    HasPosition, PositionInTriangulation,
+};
+
+use super::Refinement;
+
+/// The maximum number of refinement passes
+///
+/// Each pass inserts a point into every triangle that doesn't meet the quality
+/// requirements. Refinement usually converges long before this limit is
+/// reached, but might not, if the requirements are too strict.
+const MAX_REFINEMENT_PASSES: usize = 32;
+
+/// Create a constrained Delaunay triangulation of the provided cycles
+///
+/// The edges of the cycles are used as constraints, meaning they are
+/// guaranteed to be edges of the triangulation. Since no triangle crosses a
+/// cycle, it's either completely inside or outside of the area bounded by the
+/// cycles. See [`inner_faces`].
+///
+/// If refinement is requested, points are inserted within the area bounded by
+/// the cycles, until all triangles meet the requirements. `point_global`
+/// converts the inserted points into global coordinates. Cycle segments that
+/// are longer than the maximum edge length are split evenly, which results in
+/// the same points for all faces that share the segment. Apart from that, no
+/// points are inserted on the cycles, as the triangulations of neighboring
+/// faces wouldn't match up anymore.
 pub fn triangulate(
-    points: Vec<TriangulationPoint>,
+    cycles: Vec<Vec<TriangulationPoint>>,
+    refinement: &Refinement,
+    point_global: impl Fn(Point<2>) -> Point<3>,
 ) -> Vec<[TriangulationPoint; 3]> {
     use spade::Triangulation as _;
 
-    let triangulation = spade::DelaunayTriangulation::<_>::bulk_load(points)
-        .expect("Inserted invalid values into triangulation");
-
-    let mut triangles = Vec::new();
-    for triangle in triangulation.inner_faces() {
-        let [v0, v1, v2] = triangle.vertices().map(|vertex| *vertex.data());
-        let orientation = Triangle::<2>::from_points([
-            v0.point_surface,
-            v1.point_surface,
-            v2.point_surface,
-        ])
-        .expect("invalid triangle")
-        .winding_direction();
-
-        let triangle = match orientation {
-            Winding::Ccw => [v0, v1, v2],
-            Winding::Cw => [v0, v2, v1],
+    let mut triangulation = Cdt::new();
+    let mut constraints = Vec::new();
+
+    for cycle in cycles {
+        let cycle = match refinement.max_edge_length {
+            Some(max_edge_length) => split_segments(cycle, max_edge_length),
+            None => cycle,
         };
 
-        triangles.push(triangle);
+        let vertices = cycle
+            .iter()
+            .map(|&point| {
+                triangulation
+                    .insert(point)
+                    .expect("Inserted invalid values into triangulation")
+            })
+            .collect::<Vec<_>>();
+
+        for (i, &from) in vertices.iter().enumerate() {
+            let to = vertices[(i + 1) % vertices.len()];
+
+            // Cycles are closed by repeating their first point, and the
+            // constraints of a self-intersecting cycle can't all be added.
+            // Skip those, instead of failing the whole triangulation.
+            if from == to || !triangulation.can_add_constraint(from, to) {
+                continue;
+            }
+
+            triangulation.add_constraint(from, to);
+            constraints.push(Segment::from_points([
+                cycle[i].point_surface,
+                cycle[(i + 1) % cycle.len()].point_surface,
+            ]));
+        }
+    }
+
+    if refinement.is_enabled() {
+        for _ in 0..MAX_REFINEMENT_PASSES {
+            let inner = inner_faces(&triangulation);
+
+            let points = inner_triangles(&triangulation, &inner)
+                .into_iter()
+                .filter(|triangle| !refinement.is_satisfied_by(triangle))
+                .filter_map(|triangle| circumcenter(&triangle))
+                .filter(|&point| {
+                    // Points that are too close to a constraint would require
+                    // splitting it, to result in well-shaped triangles. We
+                    // can't do that, so the triangle needs to stay as it is.
+                    let encroaches = constraints.iter().any(|segment| {
+                        let [a, b] = segment.points();
+                        (a - point).dot(&(b - point)) <= Scalar::ZERO
+                    });
+
+                    !encroaches && is_inner(&triangulation, &inner, point)
+                })
+                .collect::<Vec<_>>();
+
+            if points.is_empty() {
+                break;
+            }
+
+            for point_surface in points {
+                triangulation
+                    .insert(TriangulationPoint {
+                        point_surface,
+                        point_global: point_global(point_surface),
+                    })
+                    .expect("Inserted invalid values into triangulation");
+            }
+        }
+    }
+
+    inner_triangles(&triangulation, &inner_faces(&triangulation))
+        .into_iter()
+        .map(|[v0, v1, v2]| {
+            let orientation = Triangle::<2>::from_points([
+                v0.point_surface,
+                v1.point_surface,
+                v2.point_surface,
+            ])
+            .expect("invalid triangle")
+            .winding_direction();
+
+            match orientation {
+                Winding::Ccw => [v0, v1, v2],
+                Winding::Cw => [v0, v2, v1],
+            }
+        })
+        .collect()
+}
+
+/// Split the segments of a cycle, so none is longer than `max_length`
+fn split_segments(
+    cycle: Vec<TriangulationPoint>,
+    max_length: Scalar,
+) -> Vec<TriangulationPoint> {
+    let mut points = Vec::new();
+
+    for (i, &a) in cycle.iter().enumerate() {
+        let b = cycle[(i + 1) % cycle.len()];
+        points.push(a);
+
+        let length = (b.point_global - a.point_global).magnitude();
+        let num_segments = (length / max_length).ceil().into_f64() as usize;
+
+        // Neighboring faces might traverse the segment in the other
+        // direction, and use different surface coordinates for it. Always
+        // interpolate from the same end in global coordinates, using the same
+        // index, so they end up with exactly the same points.
+        let (start, end, reversed) = if a.point_global <= b.point_global {
+            (a, b, false)
+        } else {
+            (b, a, true)
+        };
+
+        for j in 1..num_segments {
+            let k = if reversed { num_segments - j } else { j };
+            let t = k as f64 / num_segments as f64;
+
+            points.push(TriangulationPoint {
+                point_surface: start.point_surface
+                    + (end.point_surface - start.point_surface) * t,
+                point_global: start.point_global
+                    + (end.point_global - start.point_global) * t,
+            });
+        }
+    }
+
+    points
+}
+
+type Cdt = spade::ConstrainedDelaunayTriangulation<TriangulationPoint>;
+
+/// Find the triangles within the area bounded by the constraints
+///
+/// The triangles next to the convex hull are outside of that area, unless the
+/// hull edge they're adjacent to is a constraint. Crossing a constraint edge
+/// toggles between inside and outside, so starting from there, the triangles
+/// are classified by flood-filling across the triangulation.
+fn inner_faces(triangulation: &Cdt) -> HashSet<FixedFaceHandle<InnerTag>> {
+    use spade::Triangulation as _;
+
+    let mut is_inner = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for face in triangulation.inner_faces() {
+        for edge in face.adjacent_edges() {
+            if edge.rev().face().as_inner().is_some() {
+                continue;
+            }
+
+            let inner =
+                triangulation.is_constraint_edge(edge.as_undirected().fix());
+            if is_inner.insert(face.fix(), inner).is_none() {
+                queue.push_back(face);
+            }
+        }
     }
 
-    triangles
+    while let Some(face) = queue.pop_front() {
+        let face_is_inner = is_inner[&face.fix()];
+
+        for edge in face.adjacent_edges() {
+            let neighbor = match edge.rev().face().as_inner() {
+                Some(neighbor) => neighbor,
+                None => continue,
+            };
+            if is_inner.contains_key(&neighbor.fix()) {
+                continue;
+            }
+
+            let is_constraint =
+                triangulation.is_constraint_edge(edge.as_undirected().fix());
+            is_inner.insert(neighbor.fix(), face_is_inner != is_constraint);
+            queue.push_back(neighbor);
+        }
+    }
+
+    is_inner
+        .into_iter()
+        .filter_map(|(face, is_inner)| is_inner.then_some(face))
+        .collect()
+}
+
+fn inner_triangles(
+    triangulation: &Cdt,
+    inner: &HashSet<FixedFaceHandle<InnerTag>>,
+) -> Vec<[TriangulationPoint; 3]> {
+    use spade::Triangulation as _;
+
+    triangulation
+        .inner_faces()
+        .filter(|face| inner.contains(&face.fix()))
+        .map(|face| face.vertices().map(|vertex| *vertex.data()))
+        .collect()
+}
+
+/// Indicate whether a point is strictly within one of the inner triangles
+fn is_inner(
+    triangulation: &Cdt,
+    inner: &HashSet<FixedFaceHandle<InnerTag>>,
+    point: Point<2>,
+) -> bool {
+    use spade::Triangulation as _;
+
+    let position = triangulation.locate(spade::Point2 {
+        x: point.u,
+        y: point.v,
+    });
+
+    match position {
+        PositionInTriangulation::OnFace(face) => inner.contains(&face),
+        _ => false,
+    }
+}
+
+fn circumcenter(triangle: &[TriangulationPoint; 3]) -> Option<Point<2>> {
+    let [a, b, c] = triangle.map(|point| point.point_surface);
+
+    let d = (a.u * (b.v - c.v) + b.u * (c.v - a.v) + c.u * (a.v - b.v)) * 2.;
+    if d == Scalar::ZERO {
+        return None;
+    }
+
+    let [aa, bb, cc] = [a, b, c].map(|p| p.u * p.u + p.v * p.v);
+    let u = (aa * (b.v - c.v) + bb * (c.v - a.v) + cc * (a.v - b.v)) / d;
+    let v = (aa * (c.u - b.u) + bb * (a.u - c.u) + cc * (b.u - a.u)) / d;
+
+    Some(Point::from([u, v]))
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -49,3 +284,45 @@ impl HasPosition for TriangulationPoint {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use super::{split_segments, TriangulationPoint};
+
+    #[test]
+    fn split_segments_in_both_directions() {
+        let point = |surface: [f64; 2], global: [f64; 3]| TriangulationPoint {
+            point_surface: Point::from(surface),
+            point_global: Point::from(global),
+        };
+
+        // The same segment, as seen by two neighboring faces. They traverse it
+        // in opposite directions, and their surface coordinates differ.
+        let a = [0.1, 0.2, 0.3];
+        let b = [1.7, 0.9, -0.4];
+        let forward = [point([0., 0.], a), point([1., 0.], b)];
+        let backward = [point([0., 0.], b), point([3., 1.], a)];
+
+        let max_length = Scalar::from_f64(0.3);
+        let split = |cycle: [TriangulationPoint; 2]| {
+            // The cycle is closed, so the segment is split a second time, on
+            // the way back. Only the points inserted into the first segment
+            // are of interest.
+            let points = split_segments(cycle.to_vec(), max_length);
+            let num_points = points.len() / 2;
+            points[1..num_points]
+                .iter()
+                .map(|point| point.point_global)
+                .collect::<Vec<_>>()
+        };
+
+        let forward = split(forward);
+        let mut backward = split(backward);
+        backward.reverse();
+
+        assert!(forward.len() > 1);
+        assert_eq!(forward, backward);
+    }
+}
@@ -1,84 +1,236 @@
 mod delaunay;
-mod polygon;
 
-use fj_interop::{debug::DebugInfo, mesh::Mesh};
-use fj_math::Point;
+use std::{collections::HashMap, f64::consts::FRAC_PI_3};
+
+use fj_interop::mesh::{Color, FaceId, Mesh, Triangle};
+use fj_math::{Point, Scalar, Vector};
 
 use crate::objects::Face;
 
-use self::{delaunay::TriangulationPoint, polygon::Polygon};
+use self::delaunay::TriangulationPoint;
 
-use super::approx::{Approx, Tolerance};
+use super::approx::{EdgeApproxCache, FaceApprox, Tolerance};
 
 /// Triangulate a shape
-pub fn triangulate(
+///
+/// Faces that have their own tolerance are approximated using that, instead
+/// of the provided one. Edges that are shared between faces with different
+/// tolerances are approximated using the finer one.
+pub fn triangulate(faces: Vec<Face>, tolerance: Tolerance) -> Mesh<Point<3>> {
+    triangulate_refined(faces, tolerance, &Refinement::default())
+}
+
+/// Triangulate a shape, refining the triangles until they are well-shaped
+///
+/// Works like [`triangulate`], but additionally inserts points into faces,
+/// until the triangles meet the requirements of `refinement`. Points on the
+/// boundaries of faces are only inserted to split edges that are too long, so
+/// the triangles along the boundaries might not meet all requirements.
+///
+/// Faces that are already in triangle representation are not refined.
+///
+/// If the `parallel` feature is enabled, faces are triangulated on multiple
+/// threads. The result is the same either way.
+pub fn triangulate_refined(
     faces: Vec<Face>,
     tolerance: Tolerance,
-    debug_info: &mut DebugInfo,
+    refinement: &Refinement,
 ) -> Mesh<Point<3>> {
+    let cache = EdgeApproxCache::new(&faces, tolerance);
+
+    let process_face =
+        |face: &Face| triangulate_face(face, tolerance, &cache, refinement);
+
+    // Collecting preserves the order of the faces, which keeps the mesh
+    // deterministic.
+    #[cfg(feature = "parallel")]
+    let triangulated_faces = {
+        use rayon::prelude::*;
+        faces.par_iter().map(process_face).collect::<Vec<_>>()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let triangulated_faces = faces.iter().map(process_face).collect::<Vec<_>>();
+
     let mut mesh = Mesh::new();
 
-    for face in faces {
-        if let Some(triangles) = face.triangles() {
-            for &(triangle, color) in triangles {
-                mesh.push_triangle(triangle, color);
-            }
-            continue;
+    for (i, triangles) in triangulated_faces.into_iter().enumerate() {
+        for triangle in triangles {
+            mesh.push(triangle.with_face(FaceId(i)));
         }
+    }
+
+    mesh
+}
 
-        let surface = face.surface();
-        let approx = face.approx(tolerance, ());
+/// The maximum angle between triangles whose normals are smoothed
+///
+/// Only applies to faces in triangle representation, as the exact surface is
+/// not known for those. Triangles that meet at a sharper angle are considered
+/// to be separated by a crease.
+const SMOOTHING_ANGLE: f64 = FRAC_PI_3;
 
-        let points: Vec<_> = approx
-            .points
+fn triangulate_face(
+    face: &Face,
+    tolerance: Tolerance,
+    cache: &EdgeApproxCache,
+    refinement: &Refinement,
+) -> Vec<Triangle> {
+    if let Some(triangles) = face.triangles() {
+        return smooth_normals(triangles)
             .into_iter()
-            .map(|(point_surface, point_global)| TriangulationPoint {
-                point_surface,
-                point_global,
+            .zip(triangles)
+            .map(|(normals, &(triangle, color))| {
+                Triangle::new(triangle, color).with_normals(normals)
             })
             .collect();
-        let face_as_polygon = Polygon::new(*surface)
-            .with_exterior(
-                approx
-                    .exterior
-                    .points
-                    .into_iter()
-                    .map(|(point_surface, _)| point_surface),
-            )
-            .with_interiors(approx.interiors.into_iter().map(|interior| {
-                interior
-                    .points
-                    .into_iter()
-                    .map(|(point_surface, _)| point_surface)
-            }));
-
-        let mut triangles = delaunay::triangulate(points);
-        triangles.retain(|triangle| {
-            face_as_polygon.contains_triangle(
-                triangle.map(|point| point.point_surface),
-                debug_info,
-            )
-        });
+    }
 
-        for triangle in triangles {
+    let surface = face.surface();
+    let approx = FaceApprox::with_edge_cache(face, tolerance, cache);
+
+    let cycles = Some(&approx.exterior)
+        .into_iter()
+        .chain(&approx.interiors)
+        .map(|cycle| {
+            cycle
+                .points
+                .iter()
+                .map(|&(point_surface, point_global)| TriangulationPoint {
+                    point_surface,
+                    point_global,
+                })
+                .collect()
+        })
+        .collect();
+
+    let triangles = delaunay::triangulate(cycles, refinement, |point| {
+        surface.point_from_surface_coords(point)
+    });
+
+    triangles
+        .into_iter()
+        .map(|triangle| {
             let points = triangle.map(|point| point.point_global);
-            mesh.push_triangle(points, face.color());
+            let normals = triangle.map(|point| {
+                surface.normal_from_surface_coords(point.point_surface)
+            });
+
+            Triangle::new(points, face.color()).with_normals(normals)
+        })
+        .collect()
+}
+
+/// Compute smooth normals for triangles, whose exact surface is not known
+///
+/// The normal at each point is the average of the normals of all triangles
+/// that share the point and don't meet at a crease, weighted by their area.
+fn smooth_normals(
+    triangles: &[(fj_math::Triangle<3>, Color)],
+) -> Vec<[Vector<3>; 3]> {
+    let min_cos = Scalar::from_f64(SMOOTHING_ANGLE.cos());
+
+    // The length of the cross product is proportional to the area, which
+    // gives larger triangles more weight.
+    let weighted_normals = triangles
+        .iter()
+        .map(|(triangle, _)| {
+            let [a, b, c] = triangle.points();
+            (b - a).cross(&(c - a))
+        })
+        .collect::<Vec<_>>();
+
+    let mut triangles_by_point: HashMap<Point<3>, Vec<usize>> = HashMap::new();
+    for (i, (triangle, _)) in triangles.iter().enumerate() {
+        for point in triangle.points() {
+            triangles_by_point.entry(point).or_default().push(i);
         }
     }
 
-    mesh
+    triangles
+        .iter()
+        .map(|(triangle, _)| {
+            let normal = triangle.normal();
+
+            triangle.points().map(|point| {
+                let sum = triangles_by_point[&point]
+                    .iter()
+                    .map(|&j| weighted_normals[j])
+                    .filter(|other| other.normalize().dot(&normal) >= min_cos)
+                    .fold(Vector::from([0., 0., 0.]), |sum, other| sum + other);
+
+                if sum.magnitude() == Scalar::ZERO {
+                    normal
+                } else {
+                    sum.normalize()
+                }
+            })
+        })
+        .collect()
+}
+
+/// Quality requirements for the triangles of a mesh
+///
+/// All requirements are optional. The default value doesn't have any, which
+/// means no refinement is done.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Refinement {
+    /// The maximum length of a triangle edge
+    pub max_edge_length: Option<Scalar>,
+
+    /// The minimum angle of a triangle, in radians
+    ///
+    /// Angles above 30 degrees (`PI / 6`) might prevent the refinement from
+    /// converging.
+    pub min_angle: Option<Scalar>,
+}
+
+impl Refinement {
+    fn is_enabled(&self) -> bool {
+        self.max_edge_length.is_some() || self.min_angle.is_some()
+    }
+
+    fn is_satisfied_by(&self, triangle: &[TriangulationPoint; 3]) -> bool {
+        let [a, b, c] = triangle.map(|point| point.point_global);
+        let edges = [b - a, c - b, a - c];
+
+        if let Some(max_edge_length) = self.max_edge_length {
+            if edges.iter().any(|edge| edge.magnitude() > max_edge_length) {
+                return false;
+            }
+        }
+
+        if let Some(min_angle) = self.min_angle {
+            for i in 0..3 {
+                let incoming = -edges[(i + 2) % 3];
+                let outgoing = edges[i];
+
+                // Clamp the cosine, to protect against rounding errors.
+                let cos = (incoming.dot(&outgoing)
+                    / (incoming.magnitude() * outgoing.magnitude()))
+                .max(-Scalar::ONE)
+                .min(Scalar::ONE);
+                if cos.acos() < min_angle {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use fj_interop::{debug::DebugInfo, mesh::Mesh};
-    use fj_math::{Point, Scalar};
+    use fj_interop::mesh::{Color, FaceId, Mesh};
+    use fj_math::{Point, Scalar, Vector};
 
     use crate::{
         algorithms::approx::Tolerance,
         objects::{Face, Surface},
     };
 
+    use super::Refinement;
+
     #[test]
     fn simple() -> anyhow::Result<()> {
         let a = [0., 0.];
@@ -144,7 +296,42 @@ mod tests {
         Ok(())
     }
 
-    #[ignore]
+    #[test]
+    fn triangular_hole() -> anyhow::Result<()> {
+        // A hole that is a triangle itself, is also a valid triangle of the
+        // constrained triangulation. It must not end up in the mesh.
+
+        let a = [0., 0.];
+        let b = [3., 0.];
+        let c = [0., 3.];
+
+        let d = [1., 1.];
+        let e = [2., 1.];
+        let f = [1., 2.];
+
+        let surface = Surface::xy_plane();
+        let face = Face::build(surface)
+            .polygon_from_points([a, b, c])
+            .with_hole([d, e, f]);
+
+        let triangles = triangulate(face)?;
+
+        let [d, e, f] = [d, e, f].map(|point| Point::from(point).to_xyz());
+        assert!(!triangles.contains_triangle([d, e, f]));
+
+        // Everything but the hole must be covered.
+        let area = triangles
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a)).magnitude() / 2.
+            })
+            .fold(Scalar::ZERO, |a, b| a + b);
+        assert!((area.into_f64() - 4.).abs() < 1e-9);
+
+        Ok(())
+    }
+
     #[test]
     fn sharp_concave_shape() -> anyhow::Result<()> {
         //
@@ -186,14 +373,120 @@ mod tests {
         Ok(())
     }
 
-    fn triangulate(face: impl Into<Face>) -> anyhow::Result<Mesh<Point<3>>> {
+    #[test]
+    fn refinement() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let face = Face::build(surface).polygon_from_points([
+            [0., 0.],
+            [4., 0.],
+            [4., 4.],
+            [0., 4.],
+        ]);
+
+        let refinement = Refinement {
+            max_edge_length: Some(Scalar::ONE),
+            min_angle: Some(Scalar::PI / 9.),
+        };
+
+        let unrefined = triangulate(face.clone())?;
+        let refined = triangulate_refined(face, &refinement)?;
+
+        assert!(refined.triangles().count() > unrefined.triangles().count());
+
+        // Refinement must not change the area that is covered.
+        let area = refined
+            .triangles()
+            .map(|triangle| {
+                let [a, b, c] = triangle.inner.points();
+                (b - a).cross(&(c - a)).magnitude() / 2.
+            })
+            .fold(Scalar::ZERO, |a, b| a + b);
+        assert!((area.into_f64() - 16.).abs() < 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn preserves_face_order() -> anyhow::Result<()> {
+        let colors = [Color([255, 0, 0, 255]), Color([0, 0, 255, 255])];
+
+        let surface = Surface::xy_plane();
+        let faces = colors
+            .iter()
+            .enumerate()
+            .map(|(i, &color)| {
+                let offset = i as f64 * 2.;
+                Face::build(surface)
+                    .polygon_from_points([
+                        [offset, 0.],
+                        [offset + 1., 0.],
+                        [offset + 1., 1.],
+                        [offset, 1.],
+                    ])
+                    .into_face()
+                    .with_color(color)
+            })
+            .collect();
+
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let mesh = super::triangulate(faces, tolerance);
 
-        let mut debug_info = DebugInfo::new();
-        Ok(super::triangulate(
+        let mesh_colors = mesh
+            .triangles()
+            .map(|triangle| triangle.color)
+            .collect::<Vec<_>>();
+        assert_eq!(mesh_colors, [colors[0], colors[0], colors[1], colors[1]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn normals_and_face_ids() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let faces = [0., 2.]
+            .into_iter()
+            .map(|offset| {
+                Face::build(surface)
+                    .polygon_from_points([
+                        [offset, 0.],
+                        [offset + 1., 0.],
+                        [offset + 1., 1.],
+                        [offset, 1.],
+                    ])
+                    .into_face()
+            })
+            .collect();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let mesh = super::triangulate(faces, tolerance);
+
+        let faces = mesh
+            .triangles()
+            .map(|triangle| triangle.face)
+            .collect::<Vec<_>>();
+        assert_eq!(faces, [0, 0, 1, 1].map(|i| Some(FaceId(i))),);
+
+        let normal = Vector::from([0., 0., 1.]);
+        for triangle in mesh.triangles() {
+            assert_eq!(triangle.normals, Some([normal; 3]));
+        }
+
+        Ok(())
+    }
+
+    fn triangulate(face: impl Into<Face>) -> anyhow::Result<Mesh<Point<3>>> {
+        triangulate_refined(face, &Refinement::default())
+    }
+
+    fn triangulate_refined(
+        face: impl Into<Face>,
+        refinement: &Refinement,
+    ) -> anyhow::Result<Mesh<Point<3>>> {
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        Ok(super::triangulate_refined(
             vec![face.into()],
             tolerance,
-            &mut debug_info,
+            refinement,
         ))
     }
 }
@@ -149,6 +149,15 @@ pub enum ValidationError {
     #[error("Geometric validation failed")]
     Geometric,
 
+    /// An operation failed to produce valid geometry
+    ///
+    /// Wraps the error returned by the operation, for example a
+    /// [`BlendError`].
+    ///
+    /// [`BlendError`]: crate::algorithms::blend::BlendError
+    #[error("Operation failed")]
+    Operation(#[source] Box<dyn std::error::Error + Send + Sync>),
+
     /// Uniqueness validation failed
     #[error("Uniqueness validation failed")]
     Uniqueness(#[from] UniquenessIssues),
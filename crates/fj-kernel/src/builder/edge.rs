@@ -43,13 +43,31 @@ impl EdgeBuilder {
         &self,
         points: [impl Into<Point<2>>; 2],
     ) -> Edge {
-        let points = points.map(Into::into);
+        let vertices = points.map(|position| {
+            let position = position.into();
+            let global = GlobalVertex::from_position(
+                self.surface.point_from_surface_coords(position),
+            );
 
-        let global_vertices = points.map(|position| {
-            let position = self.surface.point_from_surface_coords(position);
-            GlobalVertex::from_position(position)
+            (position, global)
         });
 
+        self.line_segment_from_vertices(vertices)
+    }
+
+    /// Create a line segment from two vertices
+    ///
+    /// Each vertex is provided as its position in surface coordinates, and the
+    /// global vertex it refers to. Unlike with
+    /// [`EdgeBuilder::line_segment_from_points`], the global vertices are used
+    /// as-is, which makes it possible to connect the edge to existing ones.
+    pub fn line_segment_from_vertices(
+        &self,
+        vertices: [(Point<2>, GlobalVertex); 2],
+    ) -> Edge {
+        let points = vertices.map(|(position, _)| position);
+        let global_vertices = vertices.map(|(_, global)| global);
+
         let curve = {
             let curve_local = CurveKind::Line(Line::from_points(points));
             let curve_global = {
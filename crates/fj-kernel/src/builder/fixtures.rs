@@ -0,0 +1,25 @@
+//! Objects that are shared between tests
+
+use fj_interop::mesh::Color;
+use fj_math::Point;
+
+use crate::{
+    algorithms::{approx::Tolerance, sweep::Sweep},
+    objects::{Sketch, Solid, Surface},
+};
+
+/// Sweep a polygon in the xy-plane along the z-axis
+pub fn prism(
+    points: impl IntoIterator<Item = impl Into<Point<2>>>,
+    height: f64,
+) -> anyhow::Result<Solid> {
+    let sketch = Sketch::build(Surface::xy_plane()).polygon_from_points(points);
+    let tolerance = Tolerance::from_scalar(0.001)?;
+
+    Ok(sketch.sweep([0., 0., height], tolerance, Color([255, 0, 0, 255])))
+}
+
+/// A cube with an edge length of 2, spanning from the origin to `[2, 2, 2]`
+pub fn cube() -> anyhow::Result<Solid> {
+    prism([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], 2.)
+}
@@ -8,6 +8,9 @@ mod shell;
 mod sketch;
 mod solid;
 
+#[cfg(test)]
+pub(crate) mod fixtures;
+
 pub use self::{
     curve::{CurveBuilder, GlobalCurveBuilder},
     cycle::CycleBuilder,
@@ -1,7 +1,7 @@
 use fj_interop::mesh::Color;
 use fj_math::Triangle;
 
-use crate::builder::FaceBuilder;
+use crate::{algorithms::approx::Tolerance, builder::FaceBuilder};
 
 use super::{Cycle, Surface};
 
@@ -28,6 +28,7 @@ impl Face {
                 exteriors: Vec::new(),
                 interiors: Vec::new(),
                 color: Color::default(),
+                tolerance: None,
             }),
         }
     }
@@ -102,6 +103,20 @@ impl Face {
         self
     }
 
+    /// Update the tolerance that is used to approximate the face
+    ///
+    /// If a tolerance is set, it is used instead of the one that is passed to
+    /// the triangulation. Faces in triangle representation have already been
+    /// approximated, so they are left unchanged.
+    ///
+    /// Consumes the face and returns the updated instance.
+    pub fn with_tolerance(mut self, tolerance: Option<Tolerance>) -> Self {
+        if let Representation::BRep(brep) = &mut self.representation {
+            brep.tolerance = tolerance;
+        }
+        self
+    }
+
     /// Access this face's surface
     pub fn surface(&self) -> &Surface {
         &self.brep().surface
@@ -132,6 +147,16 @@ impl Face {
         self.brep().color
     }
 
+    /// Access the tolerance that is used to approximate the face, if set
+    ///
+    /// Always returns `None` for faces in triangle representation.
+    pub fn tolerance(&self) -> Option<Tolerance> {
+        match &self.representation {
+            Representation::BRep(brep) => brep.tolerance,
+            Representation::TriRep(_) => None,
+        }
+    }
+
     /// Access triangles, if this face uses triangle representation
     ///
     /// Only some faces still use triangle representation. At some point, none
@@ -180,6 +205,7 @@ struct BRep {
     exteriors: Vec<Cycle>,
     interiors: Vec<Cycle>,
     color: Color,
+    tolerance: Option<Tolerance>,
 }
 
 type TriRep = Vec<(Triangle<3>, Color)>;
@@ -1,4 +1,6 @@
-use fj_math::{Line, Point, Transform, Vector};
+use std::f64::consts::FRAC_PI_2;
+
+use fj_math::{Line, Point, Scalar, Transform, Vector};
 
 use super::CurveKind;
 
@@ -67,6 +69,21 @@ impl Surface {
             }
         }
     }
+
+    /// Compute the normal of the surface at a point in surface coordinates
+    ///
+    /// The normal is normalized, and points to the side from which the
+    /// surface's u- and v-axes appear in counter-clockwise order.
+    pub fn normal_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Vector<3> {
+        match self {
+            Self::SweptCurve(surface) => {
+                surface.normal_from_surface_coords(point)
+            }
+        }
+    }
 }
 
 /// A surface that was swept from a curve
@@ -115,6 +132,27 @@ impl SweptCurve {
             + self.path_to_line().vector_from_line_coords([vector.v])
     }
 
+    /// Compute the normal of the surface at a point in surface coordinates
+    pub fn normal_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Vector<3> {
+        let point = point.into();
+
+        let tangent = match self.curve {
+            CurveKind::Circle(circle) => {
+                // The derivative of `a * cos(t) + b * sin(t)` is the same
+                // vector, a quarter turn ahead.
+                circle.vector_from_circle_coords([
+                    point.u + Scalar::from_f64(FRAC_PI_2)
+                ])
+            }
+            CurveKind::Line(line) => line.direction(),
+        };
+
+        tangent.cross(&self.path).normalize()
+    }
+
     fn path_to_line(&self) -> Line<3> {
         Line::from_origin_and_direction(self.curve.origin(), self.path)
     }
@@ -122,7 +160,7 @@ impl SweptCurve {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use fj_math::{Circle, Line, Point, Scalar, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::objects::CurveKind;
@@ -182,4 +220,31 @@ mod tests {
             Vector::from([0., 4., 8.]),
         );
     }
+
+    #[test]
+    fn normal_from_surface_coords() {
+        let plane = SweptCurve {
+            curve: CurveKind::Line(Line::from_origin_and_direction(
+                Point::from([1., 0., 0.]),
+                Vector::from([0., 2., 0.]),
+            )),
+            path: Vector::from([0., 0., 2.]),
+        };
+        assert_eq!(
+            plane.normal_from_surface_coords([2., 4.]),
+            Vector::from([1., 0., 0.]),
+        );
+
+        let cylinder = SweptCurve {
+            curve: CurveKind::Circle(Circle::new(
+                Point::from([0., 0., 0.]),
+                Vector::from([2., 0., 0.]),
+                Vector::from([0., 2., 0.]),
+            )),
+            path: Vector::from([0., 0., 1.]),
+        };
+        let normal = cylinder.normal_from_surface_coords([0., 0.]);
+        let error = (normal - Vector::from([1., 0., 0.])).magnitude();
+        assert!(error < Scalar::from_f64(1e-12));
+    }
 }
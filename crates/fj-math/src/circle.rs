@@ -33,21 +33,23 @@ impl<const D: usize> Circle<D> {
         let a = a.into();
         let b = b.into();
 
-        assert_eq!(
-            a.magnitude(),
-            b.magnitude(),
+        let radius = a.magnitude();
+
+        // Requiring the vectors to be of *precisely* equal length is not
+        // practical, if they have been computed (for example by rotating a
+        // circle in 3D space). Allow for a small relative error.
+        assert!(
+            (radius - b.magnitude()).abs() <= radius * Scalar::from_f64(1e-12),
             "`a` and `b` must be of equal length"
         );
-        assert_ne!(
-            a.magnitude(),
-            Scalar::ZERO,
-            "circle radius must not be zero"
-        );
+        assert_ne!(radius, Scalar::ZERO, "circle radius must not be zero");
         // Requiring the vector to be *precisely* perpendicular is not
         // practical, because of numerical inaccuracy. This epsilon value seems
         // seems to work for now, but maybe it needs to become configurable.
         assert!(
-            a.dot(&b) < Scalar::default_epsilon(),
+            a.dot(&b)
+                < Scalar::default_epsilon()
+                    * (radius * radius).max(Scalar::ONE),
             "`a` and `b` must be perpendicular to each other"
         );
 
@@ -150,7 +152,7 @@ impl<const D: usize> approx::AbsDiffEq for Circle<D> {
 mod tests {
     use std::f64::consts::{FRAC_PI_2, PI};
 
-    use crate::{Point, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Circle;
 
@@ -179,4 +181,28 @@ mod tests {
             Point::from([FRAC_PI_2 * 3.]),
         );
     }
+
+    #[test]
+    fn new_with_rounding_errors() {
+        // Vectors that have been computed are rarely exact. Those are off by
+        // a few ULPs in length and angle.
+        let circle = Circle::new(
+            [0., 0., 0.],
+            [10., 0., 0.],
+            [1e-15, 10. * (1. + 1e-15), 0.],
+        );
+        assert_eq!(circle.a().magnitude(), Scalar::from(10.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_with_different_lengths() {
+        Circle::new([0., 0., 0.], [10., 0., 0.], [0., 10.001, 0.]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_with_non_perpendicular_vectors() {
+        Circle::new([0., 0., 0.], [10., 0., 0.], [0.001, 10., 0.]);
+    }
 }
@@ -0,0 +1,103 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{
+        adjacency::Adjacency,
+        approx::Tolerance,
+        blend::{blend_edges, BlendKind},
+        sew::sew,
+        validate::{Validate, Validated, ValidationConfig, ValidationError},
+    },
+    objects::{CurveKind, GlobalEdge, Solid},
+};
+use fj_math::{Aabb, Point, Scalar};
+
+use super::{unit_vector, Shape};
+
+impl Shape for fj::Blend {
+    type Brep = Solid;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let faces = self
+            .shape()
+            .compute_brep(config, tolerance, debug_info)?
+            .into_inner();
+
+        // The faces of the shape don't necessarily share their edges, for
+        // example if they have been transformed. The blend operation needs to
+        // know which faces are adjacent though.
+        let shell = sew(faces, config.distinct_min_distance).into_shell();
+        let solid = Solid::new().with_shells([shell]);
+
+        let edges = select_edges(&solid, self.edges())?;
+        let kind = match self.kind() {
+            fj::BlendKind::Fillet(radius) => BlendKind::Fillet {
+                radius: Scalar::from_f64(radius),
+            },
+            fj::BlendKind::Chamfer(distance) => BlendKind::Chamfer {
+                distance: Scalar::from_f64(distance),
+            },
+        };
+
+        let solid = blend_edges(&solid, edges, kind)
+            .map_err(|err| ValidationError::Operation(Box::new(err)))?;
+
+        solid.validate_with_config(config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // Blending only ever removes material, so the bounding volume of the
+        // original shape still contains the result.
+        self.shape().bounding_volume()
+    }
+}
+
+fn select_edges(
+    solid: &Solid,
+    selection: &fj::EdgeSelection,
+) -> Result<Vec<GlobalEdge>, ValidationError> {
+    let adjacency = Adjacency::from_solid(solid);
+    let edges = adjacency.edges().copied();
+
+    let edges = match selection {
+        fj::EdgeSelection::Index(index) => {
+            edges.skip(*index as usize).take(1).collect()
+        }
+        fj::EdgeSelection::Direction(direction) => {
+            let direction = unit_vector(*direction)?;
+
+            edges
+                .filter(|edge| match edge.curve().kind() {
+                    CurveKind::Line(line) => {
+                        let dot =
+                            line.direction().normalize().dot(&direction).abs();
+                        dot >= Scalar::ONE - Scalar::from_f64(1e-9)
+                    }
+                    CurveKind::Circle(_) => false,
+                })
+                .collect()
+        }
+        fj::EdgeSelection::BoundingBox { min, max } => {
+            let aabb = Aabb {
+                min: Point::from(*min),
+                max: Point::from(*max),
+            };
+
+            edges
+                .filter(|edge| {
+                    edge.vertices().get().map_or(false, |vertices| {
+                        vertices
+                            .iter()
+                            .all(|vertex| aabb.contains(vertex.position()))
+                    })
+                })
+                .collect()
+        }
+    };
+
+    Ok(edges)
+}
@@ -0,0 +1,69 @@
+use fj_interop::{debug::DebugInfo, mesh::Mesh};
+use fj_kernel::{
+    algorithms::{
+        approx::Tolerance,
+        boolean::{brep_boolean, mesh_boolean, BooleanError, BooleanOperation},
+        triangulate,
+        validate::{Validate, Validated, ValidationConfig, ValidationError},
+    },
+    objects::Face,
+};
+use fj_math::{Aabb, Point};
+
+use super::Shape;
+
+impl Shape for fj::Boolean {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let a = self.a.compute_brep(config, tolerance, debug_info)?;
+        let b = self.b.compute_brep(config, tolerance, debug_info)?;
+
+        let operation = match self.operation {
+            fj::BooleanOperation::Union => BooleanOperation::Union,
+            fj::BooleanOperation::Difference => BooleanOperation::Difference,
+            fj::BooleanOperation::Intersection => {
+                BooleanOperation::Intersection
+            }
+        };
+
+        // Use the exact geometry, if the kernel supports it. Otherwise, fall
+        // back to computing the boolean on the triangle meshes. The result of
+        // that is a single face in triangle representation.
+        match brep_boolean(&a, &b, operation) {
+            Ok(faces) => return faces.validate_with_config(config),
+            Err(BooleanError::Unsupported(_)) => {}
+        }
+
+        let mesh_of = |faces: Validated<Vec<Face>>| -> Mesh<Point<3>> {
+            let mut mesh = triangulate(faces.into_inner(), tolerance);
+            mesh.weld(config.distinct_min_distance);
+            mesh
+        };
+
+        let mesh = mesh_boolean(&mesh_of(a), &mesh_of(b), operation);
+        let triangles = mesh
+            .triangles()
+            .map(|triangle| (triangle.inner, triangle.color))
+            .collect();
+
+        vec![Face::from_triangles(triangles)].validate_with_config(config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let a = self.a.bounding_volume();
+        let b = self.b.bounding_volume();
+
+        // The result of the other operations is contained within `a`.
+        match self.operation {
+            fj::BooleanOperation::Union => a.merged(&b),
+            fj::BooleanOperation::Difference
+            | fj::BooleanOperation::Intersection => a,
+        }
+    }
+}
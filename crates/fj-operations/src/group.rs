@@ -19,13 +19,14 @@ impl Shape for fj::Group {
         tolerance: Tolerance,
         debug_info: &mut DebugInfo,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
-        let mut faces = Vec::new();
+        let mut parts = Vec::new();
+        collect_parts(&self.a, &mut parts);
+        collect_parts(&self.b, &mut parts);
 
-        let a = self.a.compute_brep(config, tolerance, debug_info)?;
-        let b = self.b.compute_brep(config, tolerance, debug_info)?;
-
-        faces.extend(a.into_inner());
-        faces.extend(b.into_inner());
+        let faces = compute_breps(&parts, config, tolerance, debug_info)?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
 
         faces.validate_with_config(config)
     }
@@ -37,3 +38,64 @@ impl Shape for fj::Group {
         a.merged(&b)
     }
 }
+
+/// Flatten nested groups into their parts
+pub(crate) fn collect_parts<'r>(
+    shape: &'r fj::Shape,
+    parts: &mut Vec<&'r fj::Shape>,
+) {
+    match shape {
+        fj::Shape::Group(group) => {
+            collect_parts(&group.a, parts);
+            collect_parts(&group.b, parts);
+        }
+        shape => parts.push(shape),
+    }
+}
+
+/// Compute the faces of independent shapes
+///
+/// With the `parallel` feature enabled, the shapes are computed on multiple
+/// threads. Debug info is merged in the order of the shapes, and errors from
+/// earlier shapes take precedence, so the result doesn't depend on which shape
+/// finishes first.
+pub(crate) fn compute_breps(
+    shapes: &[&fj::Shape],
+    config: &ValidationConfig,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> Result<Vec<Vec<Face>>, ValidationError> {
+    #[cfg(feature = "parallel")]
+    let breps = {
+        use rayon::prelude::*;
+
+        let results = shapes
+            .par_iter()
+            .map(|shape| {
+                let mut debug_info = DebugInfo::new();
+                let brep =
+                    shape.compute_brep(config, tolerance, &mut debug_info);
+                (brep, debug_info)
+            })
+            .collect::<Vec<_>>();
+
+        let mut breps = Vec::new();
+        for (brep, shape_debug_info) in results {
+            debug_info.append(shape_debug_info);
+            breps.push(brep?.into_inner());
+        }
+
+        breps
+    };
+    #[cfg(not(feature = "parallel"))]
+    let breps = shapes
+        .iter()
+        .map(|shape| {
+            shape
+                .compute_brep(config, tolerance, debug_info)
+                .map(|brep| brep.into_inner())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(breps)
+}
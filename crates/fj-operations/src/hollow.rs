@@ -0,0 +1,92 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{
+        approx::Tolerance,
+        hollow::{hollow, HollowError},
+        sew::sew,
+        validate::{Validate, Validated, ValidationConfig, ValidationError},
+    },
+    iter::ObjectIters,
+    objects::{CurveKind, Face, Solid, Surface},
+};
+use fj_math::{Aabb, Scalar};
+
+use super::{unit_vector, Shape};
+
+impl Shape for fj::Hollow {
+    type Brep = Solid;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let faces = self
+            .shape()
+            .compute_brep(config, tolerance, debug_info)?
+            .into_inner();
+
+        // Faces in triangle representation don't have a surface, which the
+        // selection of the open faces depends on.
+        if faces.iter().any(|face| face.triangles().is_some()) {
+            return Err(ValidationError::Operation(Box::new(
+                HollowError::TriangleRepresentation,
+            )));
+        }
+
+        // The faces of the shape need to share their vertices, for the offset
+        // vertices to be computed from the faces that meet there.
+        let shell = sew(faces, config.distinct_min_distance).into_shell();
+        let solid = Solid::new().with_shells([shell]);
+
+        let mut open_faces = Vec::new();
+        for (i, face) in solid.face_iter().enumerate() {
+            for selection in self.open_faces().iter() {
+                if is_selected(selection, i, face)? {
+                    open_faces.push(face);
+                    break;
+                }
+            }
+        }
+
+        let solid = hollow(&solid, self.thickness(), open_faces)
+            .map_err(|err| ValidationError::Operation(Box::new(err)))?;
+
+        solid.validate_with_config(config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // Hollowing only ever removes material, so the bounding volume of the
+        // original shape still contains the result.
+        self.shape().bounding_volume()
+    }
+}
+
+fn is_selected(
+    selection: &fj::FaceSelection,
+    index: usize,
+    face: &Face,
+) -> Result<bool, ValidationError> {
+    let is_selected = match selection {
+        fj::FaceSelection::Index(i) => *i as usize == index,
+        fj::FaceSelection::Normal(normal) => {
+            let normal = unit_vector(*normal)?;
+
+            let surface = match face.surface() {
+                Surface::SweptCurve(surface) => surface,
+            };
+            match surface.curve {
+                CurveKind::Line(line) => {
+                    let face_normal =
+                        line.direction().cross(&surface.path).normalize();
+                    face_normal.dot(&normal)
+                        >= Scalar::ONE - Scalar::from_f64(1e-9)
+                }
+                CurveKind::Circle(_) => false,
+            }
+        }
+    };
+
+    Ok(is_selected)
+}
@@ -18,10 +18,15 @@
 
 pub mod shape_processor;
 
+mod blend;
+mod boolean;
 mod difference_2d;
 mod group;
+mod hollow;
+mod section;
 mod sketch;
 mod sweep;
+mod tessellated;
 mod transform;
 
 use fj_interop::debug::DebugInfo;
@@ -32,7 +37,7 @@ use fj_kernel::{
     },
     objects::{Face, Sketch},
 };
-use fj_math::Aabb;
+use fj_math::{Aabb, Scalar, Vector};
 
 /// Implemented for all operations from the [`fj`] crate
 pub trait Shape {
@@ -64,6 +69,16 @@ impl Shape for fj::Shape {
         debug_info: &mut DebugInfo,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
         match self {
+            Self::Blend(shape) => shape
+                .compute_brep(config, tolerance, debug_info)?
+                .into_inner()
+                .into_shells()
+                .flat_map(|shell| shell.into_faces())
+                .collect::<Vec<_>>()
+                .validate_with_config(config),
+            Self::Boolean(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
             Self::Shape2d(shape) => shape
                 .compute_brep(config, tolerance, debug_info)?
                 .into_inner()
@@ -80,6 +95,16 @@ impl Shape for fj::Shape {
                 .flat_map(|shell| shell.into_faces())
                 .collect::<Vec<_>>()
                 .validate_with_config(config),
+            Self::Hollow(shape) => shape
+                .compute_brep(config, tolerance, debug_info)?
+                .into_inner()
+                .into_shells()
+                .flat_map(|shell| shell.into_faces())
+                .collect::<Vec<_>>()
+                .validate_with_config(config),
+            Self::Tessellated(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
             Self::Transform(shape) => {
                 shape.compute_brep(config, tolerance, debug_info)
             }
@@ -88,9 +113,13 @@ impl Shape for fj::Shape {
 
     fn bounding_volume(&self) -> Aabb<3> {
         match self {
+            Self::Blend(shape) => shape.bounding_volume(),
+            Self::Boolean(shape) => shape.bounding_volume(),
             Self::Shape2d(shape) => shape.bounding_volume(),
             Self::Group(shape) => shape.bounding_volume(),
+            Self::Hollow(shape) => shape.bounding_volume(),
             Self::Sweep(shape) => shape.bounding_volume(),
+            Self::Tessellated(shape) => shape.bounding_volume(),
             Self::Transform(shape) => shape.bounding_volume(),
         }
     }
@@ -109,6 +138,9 @@ impl Shape for fj::Shape2d {
             Self::Difference(shape) => {
                 shape.compute_brep(config, tolerance, debug_info)
             }
+            Self::Section(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
             Self::Sketch(shape) => {
                 shape.compute_brep(config, tolerance, debug_info)
             }
@@ -118,7 +150,26 @@ impl Shape for fj::Shape2d {
     fn bounding_volume(&self) -> Aabb<3> {
         match self {
             Self::Difference(shape) => shape.bounding_volume(),
+            Self::Section(shape) => shape.bounding_volume(),
             Self::Sketch(shape) => shape.bounding_volume(),
         }
     }
 }
+
+/// A direction passed to an operation has zero length
+#[derive(Debug, thiserror::Error)]
+#[error("Direction must not have zero length: {0:?}")]
+pub struct ZeroDirection(pub [f64; 3]);
+
+/// Convert a direction from the [`fj`] crate into a unit vector
+fn unit_vector(direction: [f64; 3]) -> Result<Vector<3>, ValidationError> {
+    let vector = Vector::from(direction);
+
+    if vector.magnitude() == Scalar::ZERO {
+        return Err(ValidationError::Operation(Box::new(ZeroDirection(
+            direction,
+        ))));
+    }
+
+    Ok(vector.normalize())
+}
@@ -0,0 +1,65 @@
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::{
+        approx::Tolerance,
+        section::section,
+        validate::{Validate, Validated, ValidationConfig, ValidationError},
+    },
+    objects::{Sketch, Surface},
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use super::{unit_vector, Shape};
+
+impl Shape for fj::Section {
+    type Brep = Sketch;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let faces = self
+            .shape()
+            .compute_brep(config, tolerance, debug_info)?
+            .into_inner();
+
+        let plane = plane(self.origin(), self.normal())?;
+
+        let sketch = section(&faces, &plane)
+            .map_err(|err| ValidationError::Operation(Box::new(err)))?;
+
+        let faces = sketch
+            .into_faces()
+            .map(|face| face.with_color(Color(self.color())));
+        Sketch::new().with_faces(faces).validate_with_config(config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // The section is contained within the shape it was cut from.
+        self.shape().bounding_volume()
+    }
+}
+
+/// Create the cutting plane from a point and a normal
+///
+/// If the normal points along the z-axis, the resulting plane has the same
+/// orientation as the xy-plane. Otherwise, its u-axis is horizontal.
+fn plane(
+    origin: [f64; 3],
+    normal: [f64; 3],
+) -> Result<Surface, ValidationError> {
+    let origin = Point::from(origin);
+    let normal = unit_vector(normal)?;
+
+    let z = Vector::unit_z();
+    let u = if normal.cross(&z).magnitude() == Scalar::ZERO {
+        Vector::unit_x()
+    } else {
+        z.cross(&normal).normalize()
+    };
+    let v = normal.cross(&u);
+
+    Ok(Surface::plane_from_points([origin, origin + u, origin + v]))
+}
@@ -1,24 +1,102 @@
 //! API for processing shapes
 
-use fj_interop::{debug::DebugInfo, processed_shape::ProcessedShape};
-use fj_kernel::algorithms::{
-    approx::{InvalidTolerance, Tolerance},
-    triangulate,
-    validate::{ValidationConfig, ValidationError},
+use std::ops::Range;
+
+use fj_interop::{
+    analysis::analyze, debug::DebugInfo, processed_shape::ProcessedShape,
+};
+use fj_kernel::{
+    algorithms::{
+        approx::{InvalidTolerance, Tolerance},
+        deviation::mesh_deviation,
+        mass_properties::mass_properties,
+        sew::sew,
+        triangulate_refined,
+        validate::{Validate, Validated, ValidationConfig, ValidationError},
+        Refinement,
+    },
+    objects::{Face, Shell},
 };
 use fj_math::Scalar;
 
-use crate::Shape as _;
+use crate::{
+    group::{collect_parts, compute_breps},
+    Shape as _,
+};
 
 /// Processes an [`fj::Shape`] into a [`ProcessedShape`]
 pub struct ShapeProcessor {
     /// The tolerance value used for creating the triangle mesh
     pub tolerance: Option<Tolerance>,
+
+    /// The maximum angle between neighboring segments of curves, in radians
+    pub angular_deviation: Option<Scalar>,
+
+    /// The maximum length of the segments of approximated edges
+    pub max_edge_length: Option<Scalar>,
+
+    /// The minimum number of segments that circles are approximated with
+    pub min_circle_segments: Option<u64>,
+
+    /// Quality requirements for the triangles of the mesh
+    ///
+    /// By default, there are none, and the triangles are not refined.
+    pub refinement: Refinement,
+
+    /// Whether to measure the deviation of the mesh from the shape
+    ///
+    /// The points with the largest deviation are added to the debug info.
+    pub analyze_deviation: bool,
+
+    /// Whether to check the mesh for holes, non-manifold edges, and
+    /// self-intersections
+    pub analyze_mesh: bool,
 }
 
 impl ShapeProcessor {
     /// Process an [`fj::Shape`] into [`ProcessedShape`]
     pub fn process(&self, shape: &fj::Shape) -> Result<ProcessedShape, Error> {
+        let (processed_shape, _) = self.process_with_faces(shape)?;
+        Ok(processed_shape)
+    }
+
+    /// Process an [`fj::Shape`] into [`ProcessedShape`], keeping its shells
+    ///
+    /// Works like [`ShapeProcessor::process`], but additionally returns the
+    /// exact geometry that the mesh was created from, which is required for
+    /// exporting it. Each part of the shape results in one validated shell,
+    /// in the order of [`ProcessedShape::parts`]. The faces of each part are
+    /// sewn, so neighboring faces share their vertices and edges.
+    pub fn process_with_brep(
+        &self,
+        shape: &fj::Shape,
+    ) -> Result<(ProcessedShape, Vec<Validated<Shell>>), Error> {
+        let (processed_shape, faces) = self.process_with_faces(shape)?;
+
+        let config = ValidationConfig::default();
+        let shells = processed_shape
+            .parts
+            .iter()
+            .map(|range| {
+                let faces = faces[range.clone()].iter().cloned();
+                sew(faces, config.distinct_min_distance)
+                    .into_shell()
+                    .validate_with_config(&config)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((processed_shape, shells))
+    }
+
+    /// Process an [`fj::Shape`], keeping the faces that the mesh was created
+    /// from
+    ///
+    /// The faces of each part are at the indices given by
+    /// [`ProcessedShape::parts`].
+    fn process_with_faces(
+        &self,
+        shape: &fj::Shape,
+    ) -> Result<(ProcessedShape, Vec<Face>), Error> {
         let aabb = shape.bounding_volume();
 
         let tolerance = match self.tolerance {
@@ -38,20 +116,89 @@ impl ShapeProcessor {
             }
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
+        let tolerance = self.apply_limits(tolerance).map_err(Error::Limits)?;
 
         let config = ValidationConfig::default();
         let mut debug_info = DebugInfo::new();
-        let shape = shape.compute_brep(&config, tolerance, &mut debug_info)?;
-        let mesh = triangulate(shape.into_inner(), tolerance, &mut debug_info);
+        let (faces, parts) =
+            compute_parts(shape, &config, tolerance, &mut debug_info)?;
+
+        let mass_properties = mass_properties(&faces);
+        let mut mesh =
+            triangulate_refined(faces.clone(), tolerance, &self.refinement);
 
-        Ok(ProcessedShape {
+        // Shared edges are approximated the same way for all faces, but faces
+        // that merely touch, without sharing edges, might still not line up
+        // exactly. Vertices that are closer than this can't be considered
+        // distinct anyway.
+        mesh.weld(config.distinct_min_distance);
+
+        let deviation = self
+            .analyze_deviation
+            .then(|| mesh_deviation(&faces, &mesh, tolerance, &mut debug_info));
+
+        let analysis = self.analyze_mesh.then(|| analyze(&mesh));
+
+        let processed_shape = ProcessedShape {
             aabb,
             mesh,
+            parts,
+            mass_properties,
+            deviation,
+            analysis,
             debug_info,
-        })
+        };
+
+        Ok((processed_shape, faces))
+    }
+
+    fn apply_limits(
+        &self,
+        mut tolerance: Tolerance,
+    ) -> Result<Tolerance, InvalidTolerance> {
+        if let Some(angular_deviation) = self.angular_deviation {
+            tolerance = tolerance.with_angular_deviation(angular_deviation)?;
+        }
+        if let Some(max_edge_length) = self.max_edge_length {
+            tolerance = tolerance.with_max_edge_length(max_edge_length)?;
+        }
+        if let Some(min_circle_segments) = self.min_circle_segments {
+            tolerance = tolerance.with_min_circle_segments(min_circle_segments);
+        }
+
+        Ok(tolerance)
     }
 }
 
+/// Compute the faces of a shape, keeping track of which part they belong to
+///
+/// The shapes of a group are computed separately, to tell their faces apart.
+/// The result is the same as computing the group as a whole.
+fn compute_parts(
+    shape: &fj::Shape,
+    config: &ValidationConfig,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> Result<(Vec<Face>, Vec<Range<usize>>), ValidationError> {
+    let mut parts = Vec::new();
+    collect_parts(shape, &mut parts);
+
+    let breps = compute_breps(&parts, config, tolerance, debug_info)?;
+
+    let mut faces = Vec::new();
+    let mut ranges = Vec::new();
+
+    for brep in breps {
+        let start = faces.len();
+        faces.extend(brep);
+        ranges.push(start..faces.len());
+    }
+
+    let faces = faces.validate_with_config(config)?.into_inner();
+
+    Ok((faces, ranges))
+}
+
 /// A shape processing error
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, thiserror::Error)]
@@ -63,4 +210,8 @@ pub enum Error {
     /// Model has zero size
     #[error("Model has zero size")]
     Extent(#[from] InvalidTolerance),
+
+    /// Invalid approximation limits
+    #[error("Invalid approximation limits")]
+    Limits(#[source] InvalidTolerance),
 }
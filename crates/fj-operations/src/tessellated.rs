@@ -0,0 +1,76 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{
+        approx::{InvalidTolerance, Tolerance},
+        validate::{Validate, Validated, ValidationConfig, ValidationError},
+    },
+    objects::Face,
+};
+use fj_math::{Aabb, Scalar};
+
+use super::Shape;
+
+impl Shape for fj::Tessellated {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let tolerance = make_tolerance(self.hint(), tolerance)
+            .map_err(|err| ValidationError::Operation(Box::new(err)))?;
+
+        // Faces that already have a tolerance come from a nested hint, which
+        // takes precedence.
+        self.shape()
+            .compute_brep(config, tolerance, debug_info)?
+            .into_inner()
+            .into_iter()
+            .map(|face| match face.tolerance() {
+                Some(_) => face,
+                None => face.with_tolerance(Some(tolerance)),
+            })
+            .collect::<Vec<_>>()
+            .validate_with_config(config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        self.shape().bounding_volume()
+    }
+}
+
+/// Create the tolerance for a hint
+///
+/// Limits that the hint doesn't set are taken from the global tolerance.
+fn make_tolerance(
+    hint: &fj::TessellationHint,
+    global: Tolerance,
+) -> Result<Tolerance, InvalidTolerance> {
+    let mut tolerance = Tolerance::from_scalar(hint.tolerance())?;
+
+    let angular_deviation = hint
+        .angular_deviation()
+        .map(|angle| Scalar::from_f64(angle.rad()))
+        .or_else(|| global.angular_deviation());
+    let max_edge_length = hint
+        .max_edge_length()
+        .map(Scalar::from_f64)
+        .or_else(|| global.max_edge_length());
+    let min_circle_segments = hint
+        .min_circle_segments()
+        .or_else(|| global.min_circle_segments());
+
+    if let Some(angular_deviation) = angular_deviation {
+        tolerance = tolerance.with_angular_deviation(angular_deviation)?;
+    }
+    if let Some(max_edge_length) = max_edge_length {
+        tolerance = tolerance.with_max_edge_length(max_edge_length)?;
+    }
+    if let Some(min_circle_segments) = min_circle_segments {
+        tolerance = tolerance.with_min_circle_segments(min_circle_segments);
+    }
+
+    Ok(tolerance)
+}
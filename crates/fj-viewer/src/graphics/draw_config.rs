@@ -9,6 +9,8 @@ pub struct DrawConfig {
     pub draw_mesh: bool,
     /// Toggle for displaying model debug information
     pub draw_debug: bool,
+    /// Toggle for analyzing the mesh for defects
+    pub analyze_mesh: bool,
 }
 
 impl Default for DrawConfig {
@@ -17,6 +19,7 @@ impl Default for DrawConfig {
             draw_model: true,
             draw_mesh: false,
             draw_debug: false,
+            analyze_mesh: false,
         }
     }
 }
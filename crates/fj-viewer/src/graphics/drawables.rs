@@ -1,3 +1,5 @@
+use crate::camera::Camera;
+
 use super::{
     geometries::{Geometries, Geometry},
     pipelines::{Pipeline, Pipelines},
@@ -10,9 +12,15 @@ pub struct Drawables<'r> {
 }
 
 impl<'r> Drawables<'r> {
-    pub fn new(geometries: &'r Geometries, pipelines: &'r Pipelines) -> Self {
-        let model = Drawable::new(&geometries.mesh, &pipelines.model);
-        let mesh = Drawable::new(&geometries.mesh, &pipelines.mesh);
+    pub fn new(
+        geometries: &'r Geometries,
+        pipelines: &'r Pipelines,
+        camera: &Camera,
+    ) -> Self {
+        let geometry = geometries.mesh_for(camera);
+
+        let model = Drawable::new(geometry, &pipelines.model);
+        let mesh = Drawable::new(geometry, &pipelines.mesh);
         let lines = Drawable::new(&geometries.lines, &pipelines.lines);
 
         Self { model, mesh, lines }
@@ -3,11 +3,23 @@ use std::convert::TryInto;
 use fj_math::Aabb;
 use wgpu::util::DeviceExt;
 
+use crate::camera::Camera;
+
 use super::vertices::{Vertex, Vertices};
 
+/// The levels of detail that are used for big meshes
+///
+/// Each level is defined by the distance from which it is used, relative to the
+/// size of the model, and the fraction of triangles that it keeps.
+pub const LEVELS_OF_DETAIL: [(f64, f64); 2] = [(4., 0.25), (16., 0.05)];
+
+/// The minimum number of triangles, for levels of detail to be used
+pub const LEVEL_OF_DETAIL_MIN_TRIANGLES: usize = 10_000;
+
 #[derive(Debug)]
 pub struct Geometries {
     pub mesh: Geometry,
+    pub levels_of_detail: Vec<Geometry>,
     pub lines: Geometry,
     pub aabb: Aabb<3>,
 }
@@ -20,10 +32,57 @@ impl Geometries {
         aabb: Aabb<3>,
     ) -> Self {
         let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
+        let levels_of_detail = Vec::new();
         let lines =
             Geometry::new(device, debug_info.vertices(), debug_info.indices());
 
-        Self { mesh, lines, aabb }
+        Self {
+            mesh,
+            levels_of_detail,
+            lines,
+            aabb,
+        }
+    }
+
+    /// Replace the levels of detail, leaving the full mesh unchanged
+    pub fn update_levels_of_detail(
+        &mut self,
+        device: &wgpu::Device,
+        levels_of_detail: &[Vertices],
+    ) {
+        self.levels_of_detail = levels_of_detail
+            .iter()
+            .map(|level| {
+                Geometry::new(device, level.vertices(), level.indices())
+            })
+            .collect();
+    }
+
+    /// Replace the debug lines, leaving the meshes unchanged
+    pub fn update_lines(&mut self, device: &wgpu::Device, lines: &Vertices) {
+        self.lines = Geometry::new(device, lines.vertices(), lines.indices());
+    }
+
+    /// Select the mesh to draw, depending on the distance of the camera
+    pub fn mesh_for(&self, camera: &Camera) -> &Geometry {
+        let size = self.aabb.size().magnitude().into_f64();
+        let distance = (camera.position() - self.aabb.center())
+            .magnitude()
+            .into_f64();
+
+        let level = LEVELS_OF_DETAIL
+            .iter()
+            .filter(|(min_distance, _)| distance > min_distance * size)
+            .count();
+
+        match level.checked_sub(1) {
+            Some(level) => self
+                .levels_of_detail
+                .get(level)
+                .or_else(|| self.levels_of_detail.last())
+                .unwrap_or(&self.mesh),
+            None => &self.mesh,
+        }
     }
 }
 
@@ -1,7 +1,13 @@
-use std::{io, mem::size_of};
+use std::{io, mem::size_of, sync::mpsc, thread};
 
 use egui_winit::winit::event_loop::EventLoop;
-use fj_interop::status_report::StatusReport;
+use fj_interop::{
+    analysis::MeshAnalysis,
+    decimation::{decimate, Decimation},
+    mass_properties::MassProperties,
+    mesh::Mesh,
+    status_report::StatusReport,
+};
 use fj_math::{Aabb, Point};
 use thiserror::Error;
 use tracing::debug;
@@ -14,9 +20,15 @@ use crate::{
 };
 
 use super::{
-    config_ui::ConfigUi, draw_config::DrawConfig, drawables::Drawables,
-    geometries::Geometries, pipelines::Pipelines, transform::Transform,
-    uniforms::Uniforms, vertices::Vertices, DEPTH_FORMAT,
+    config_ui::ConfigUi,
+    draw_config::DrawConfig,
+    drawables::Drawables,
+    geometries::{Geometries, LEVELS_OF_DETAIL, LEVEL_OF_DETAIL_MIN_TRIANGLES},
+    pipelines::Pipelines,
+    transform::Transform,
+    uniforms::Uniforms,
+    vertices::Vertices,
+    DEPTH_FORMAT,
 };
 
 #[derive(Default)]
@@ -57,8 +69,12 @@ pub struct Renderer {
     bind_group: wgpu::BindGroup,
 
     geometries: Geometries,
+    levels_of_detail: Option<mpsc::Receiver<Vec<Vertices>>>,
     pipelines: Pipelines,
 
+    mass_properties: Option<MassProperties>,
+    mesh_analysis: Option<MeshAnalysis>,
+
     config_ui: ConfigUi,
 
     /// State required for integration with `egui`.
@@ -265,8 +281,12 @@ impl Renderer {
             bind_group,
 
             geometries,
+            levels_of_detail: None,
             pipelines,
 
+            mass_properties: None,
+            mesh_analysis: None,
+
             config_ui,
 
             egui: EguiState {
@@ -279,13 +299,89 @@ impl Renderer {
     }
 
     /// Updates the geometry of the model being rendered.
+    ///
+    /// If the mesh is big, simplified versions of it are created on a
+    /// background thread, to be drawn when the model is viewed from far away.
+    /// Until they are ready, the full mesh is drawn at any distance.
     pub fn update_geometry(
         &mut self,
-        mesh: Vertices,
+        mesh: &Mesh<Point<3>>,
         lines: Vertices,
         aabb: Aabb<3>,
     ) {
-        self.geometries = Geometries::new(&self.device, &mesh, &lines, aabb);
+        self.geometries =
+            Geometries::new(&self.device, &mesh.into(), &lines, aabb);
+
+        // Dropping the receiver of a previous model means that its levels of
+        // detail, if still being computed, are discarded when done.
+        self.levels_of_detail = None;
+
+        let num_triangles = mesh.triangles().count();
+        if num_triangles < LEVEL_OF_DETAIL_MIN_TRIANGLES {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mesh = mesh.clone();
+        thread::spawn(move || {
+            let levels_of_detail = LEVELS_OF_DETAIL
+                .iter()
+                .map(|&(_, fraction)| {
+                    let decimation = Decimation {
+                        max_triangles: Some(
+                            (num_triangles as f64 * fraction) as usize,
+                        ),
+                        ..Decimation::default()
+                    };
+                    (&decimate(&mesh, &decimation)).into()
+                })
+                .collect();
+
+            // The renderer might have moved on to another model already, in
+            // which case nobody is interested in the result.
+            let _ = tx.send(levels_of_detail);
+        });
+        self.levels_of_detail = Some(rx);
+    }
+
+    /// Uploads the levels of detail, if their computation has finished
+    fn receive_levels_of_detail(&mut self) {
+        let levels_of_detail = match &self.levels_of_detail {
+            Some(rx) => match rx.try_recv() {
+                Ok(levels_of_detail) => levels_of_detail,
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    // The thread panicked. Keep drawing the full mesh.
+                    self.levels_of_detail = None;
+                    return;
+                }
+            },
+            None => return,
+        };
+
+        self.geometries
+            .update_levels_of_detail(&self.device, &levels_of_detail);
+        self.levels_of_detail = None;
+    }
+
+    /// Updates the mass properties of the model being rendered.
+    pub fn update_mass_properties(&mut self, mass_properties: MassProperties) {
+        self.mass_properties = Some(mass_properties);
+    }
+
+    /// Updates the debug lines of the model being rendered.
+    ///
+    /// Unlike [`Renderer::update_geometry`], this leaves the mesh unchanged.
+    pub fn update_lines(&mut self, lines: Vertices) {
+        self.geometries.update_lines(&self.device, &lines);
+    }
+
+    /// Updates the analysis of the mesh being rendered.
+    pub fn update_mesh_analysis(
+        &mut self,
+        mesh_analysis: Option<MeshAnalysis>,
+    ) {
+        self.mesh_analysis = mesh_analysis;
     }
 
     /// Resizes the render surface.
@@ -311,6 +407,8 @@ impl Renderer {
         window: &egui_winit::winit::window::Window,
         status: &mut StatusReport,
     ) -> Result<(), DrawError> {
+        self.receive_levels_of_detail();
+
         let aspect_ratio = self.surface_config.width as f64
             / self.surface_config.height as f64;
         let uniforms = Uniforms {
@@ -348,7 +446,8 @@ impl Renderer {
 
         self.clear_views(&mut encoder, &color_view);
 
-        let drawables = Drawables::new(&self.geometries, &self.pipelines);
+        let drawables =
+            Drawables::new(&self.geometries, &self.pipelines, camera);
 
         if config.draw_model {
             drawables.model.draw(
@@ -443,6 +542,38 @@ impl Renderer {
             info
         }
 
+        fn get_mass_properties_text(
+            mass_properties: &MassProperties,
+        ) -> String {
+            /* Render volume, surface area, and centroid of model */
+            let centroid = mass_properties.centroid.coords.components;
+            let info = format!(
+                "Volume: {:0.1}\nSurface area: {:0.1}\n\
+                Centroid:\n{:0.1} {:0.1} {:0.1}",
+                mass_properties.volume.into_f32(),
+                mass_properties.area.into_f32(),
+                centroid[0].into_f32(),
+                centroid[1].into_f32(),
+                centroid[2].into_f32()
+            );
+            info
+        }
+
+        fn get_mesh_analysis_text(mesh_analysis: &MeshAnalysis) -> String {
+            /* Render the problems found in the mesh */
+            let info = format!(
+                "Components: {}\nBoundary edges: {}\n\
+                Non-manifold edges: {}\nFlipped triangles: {}\n\
+                Intersecting triangles: {}",
+                mesh_analysis.num_components,
+                mesh_analysis.boundary_edges.len(),
+                mesh_analysis.non_manifold_edges.len(),
+                mesh_analysis.flipped_triangles.len(),
+                mesh_analysis.intersecting_triangles.len()
+            );
+            info
+        }
+
         let line_drawing_available = self.is_line_drawing_available();
 
         egui::SidePanel::left("fj-left-panel").show(&self.egui.context, |ui| {
@@ -461,12 +592,24 @@ impl Renderer {
                     .on_disabled_hover_text(
                         "Rendering device does not have line rendering feature support"
                     );
+                ui.checkbox(&mut config.analyze_mesh, "Analyze mesh")
+                    .on_hover_text_at_pointer(
+                        "Check the mesh for holes, non-manifold edges, and self-intersections",
+                    );
                 ui.checkbox(
                     &mut self.egui.options.show_original_ui,
                     "Render original UI",
                 );
                 ui.add_space(16.0);
                 ui.strong(get_bbox_size_text(&self.geometries.aabb));
+                if let Some(mass_properties) = &self.mass_properties {
+                    ui.add_space(16.0);
+                    ui.strong(get_mass_properties_text(mass_properties));
+                }
+                if let Some(mesh_analysis) = &self.mesh_analysis {
+                    ui.add_space(16.0);
+                    ui.strong(get_mesh_analysis_text(mesh_analysis));
+                }
             });
 
             ui.add_space(16.0);
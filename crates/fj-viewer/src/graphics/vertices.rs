@@ -1,5 +1,6 @@
 use bytemuck::{Pod, Zeroable};
 use fj_interop::{
+    analysis::MeshAnalysis,
     debug::DebugInfo,
     mesh::{Index, Mesh},
 };
@@ -70,6 +71,17 @@ impl Vertices {
             color,
         );
     }
+
+    pub fn push_triangle_outline(
+        &mut self,
+        [a, b, c]: [Point<3>; 3],
+        normal: [f32; 3],
+        color: [f32; 4],
+    ) {
+        self.push_line([a, b], normal, color);
+        self.push_line([b, c], normal, color);
+        self.push_line([c, a], normal, color);
+    }
 }
 
 impl From<&Mesh<fj_math::Point<3>>> for Vertices {
@@ -77,14 +89,13 @@ impl From<&Mesh<fj_math::Point<3>>> for Vertices {
         let mut m = Mesh::new();
 
         for triangle in mesh.triangles() {
-            let [a, b, c] = triangle.inner.points();
-
-            let normal = (b - a).cross(&(c - a)).normalize();
+            let points = triangle.inner.points();
+            let normals = triangle.vertex_normals();
             let color = triangle.color;
 
-            m.push_vertex((a, normal, color));
-            m.push_vertex((b, normal, color));
-            m.push_vertex((c, normal, color));
+            for (point, normal) in points.into_iter().zip(normals) {
+                m.push_vertex((point, normal, color));
+            }
         }
 
         let vertices = m
@@ -128,6 +139,55 @@ impl From<&DebugInfo> for Vertices {
             }
         }
 
+        for deviation_check in &debug_info.deviation_checks {
+            let normal = [0.; 3];
+
+            let color = if deviation_check.exceeds_tolerance() {
+                [1., 0., 1., 1.]
+            } else {
+                [0., 0., 1., 1.]
+            };
+
+            self_.push_cross(deviation_check.point, normal, color);
+        }
+
+        self_
+    }
+}
+
+impl From<(&DebugInfo, Option<&MeshAnalysis>)> for Vertices {
+    fn from(
+        (debug_info, analysis): (&DebugInfo, Option<&MeshAnalysis>),
+    ) -> Self {
+        let mut self_ = Self::from(debug_info);
+
+        let analysis = match analysis {
+            Some(analysis) => analysis,
+            None => return self_,
+        };
+
+        let normal = [0.; 3];
+
+        let orange = [1., 0.5, 0., 1.];
+        let purple = [0.5, 0., 1., 1.];
+        let yellow = [1., 1., 0., 1.];
+        let cyan = [0., 1., 1., 1.];
+
+        for &edge in &analysis.boundary_edges {
+            self_.push_line(edge, normal, orange);
+        }
+        for &edge in &analysis.non_manifold_edges {
+            self_.push_line(edge, normal, purple);
+        }
+        for triangle in &analysis.flipped_triangles {
+            self_.push_triangle_outline(triangle.points(), normal, yellow);
+        }
+        for triangles in &analysis.intersecting_triangles {
+            for triangle in triangles {
+                self_.push_triangle_outline(triangle.points(), normal, cyan);
+            }
+        }
+
         self_
     }
 }
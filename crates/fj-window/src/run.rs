@@ -6,7 +6,7 @@
 use std::error;
 
 use fj_host::Watcher;
-use fj_interop::status_report::StatusReport;
+use fj_interop::{analysis::analyze, status_report::StatusReport};
 use fj_operations::shape_processor::ShapeProcessor;
 use fj_viewer::{
     camera::Camera,
@@ -30,7 +30,7 @@ use crate::window::{self, Window};
 /// Initializes a model viewer for a given model and enters its process loop.
 pub fn run(
     watcher: Watcher,
-    shape_processor: ShapeProcessor,
+    mut shape_processor: ShapeProcessor,
     mut status: StatusReport,
 ) -> Result<(), Error> {
     let event_loop = EventLoop::new();
@@ -43,7 +43,10 @@ pub fn run(
     let mut input_handler = input::Handler::default();
     let mut renderer = block_on(Renderer::new(&window, &event_loop))?;
 
-    let mut draw_config = DrawConfig::default();
+    let mut draw_config = DrawConfig {
+        analyze_mesh: shape_processor.analyze_mesh,
+        ..DrawConfig::default()
+    };
 
     let mut shape = None;
     let mut camera = None;
@@ -55,10 +58,13 @@ pub fn run(
             match shape_processor.process(&new_shape) {
                 Ok(new_shape) => {
                     renderer.update_geometry(
-                        (&new_shape.mesh).into(),
-                        (&new_shape.debug_info).into(),
+                        &new_shape.mesh,
+                        (&new_shape.debug_info, new_shape.analysis.as_ref())
+                            .into(),
                         new_shape.aabb,
                     );
+                    renderer.update_mass_properties(new_shape.mass_properties);
+                    renderer.update_mesh_analysis(new_shape.analysis.clone());
 
                     if camera.is_none() {
                         camera = Some(Camera::new(&new_shape.aabb));
@@ -84,6 +90,22 @@ pub fn run(
             }
         }
 
+        // The analysis of the mesh can be toggled in the UI. Update the current
+        // shape right away, instead of waiting for the model to be reloaded.
+        if draw_config.analyze_mesh != shape_processor.analyze_mesh {
+            shape_processor.analyze_mesh = draw_config.analyze_mesh;
+
+            if let Some(shape) = &mut shape {
+                shape.analysis =
+                    shape_processor.analyze_mesh.then(|| analyze(&shape.mesh));
+
+                renderer.update_lines(
+                    (&shape.debug_info, shape.analysis.as_ref()).into(),
+                );
+                renderer.update_mesh_analysis(shape.analysis.clone());
+            }
+        }
+
         //
 
         if let Event::WindowEvent {
@@ -127,6 +127,29 @@ impl<T> Drop for Vec<T> {
 unsafe impl<T: Send> Send for Vec<T> {}
 unsafe impl<T: Sync> Sync for Vec<T> {}
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Vec<T> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Vec<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        std::vec::Vec::deserialize(deserializer).map(Vec::from)
+    }
+}
+
 /// A FFI-safe version of `Box<str>`.
 #[repr(transparent)]
 #[derive(Debug, PartialEq, Clone)]
@@ -327,7 +350,7 @@ impl From<Error> for BoxedError {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[repr(C)]
 pub enum Option<T> {
     Some(T),
@@ -363,3 +386,29 @@ impl<T> From<Option<T>> for std::option::Option<T> {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Option<T> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Option::Some(value) => serializer.serialize_some(value),
+            Option::None => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Option<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        std::option::Option::deserialize(deserializer).map(Option::from)
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Shape;
+
+/// A 3-dimensional shape with some of its edges rounded or chamfered
+///
+/// # Limitations
+///
+/// Only straight edges between planar faces can be blended, and the blended
+/// edges must not share any vertices. Fillets additionally require the faces
+/// at either end of the edge to be perpendicular to it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Blend {
+    /// The shape whose edges are blended
+    shape: Shape,
+
+    /// The kind of blend
+    kind: BlendKind,
+
+    /// The edges that are blended
+    edges: EdgeSelection,
+}
+
+impl Blend {
+    /// Round the selected edges of a shape
+    pub fn fillet(shape: Shape, radius: f64, edges: EdgeSelection) -> Self {
+        Self {
+            shape,
+            kind: BlendKind::Fillet(radius),
+            edges,
+        }
+    }
+
+    /// Chamfer the selected edges of a shape
+    pub fn chamfer(shape: Shape, distance: f64, edges: EdgeSelection) -> Self {
+        Self {
+            shape,
+            kind: BlendKind::Chamfer(distance),
+            edges,
+        }
+    }
+
+    /// Access the shape whose edges are blended
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the kind of blend
+    pub fn kind(&self) -> BlendKind {
+        self.kind
+    }
+
+    /// Access the edges that are blended
+    pub fn edges(&self) -> &EdgeSelection {
+        &self.edges
+    }
+}
+
+impl From<Blend> for Shape {
+    fn from(shape: Blend) -> Self {
+        Self::Blend(Box::new(shape))
+    }
+}
+
+/// The kind of a [`Blend`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum BlendKind {
+    /// Round the edges with the given radius
+    Fillet(f64),
+
+    /// Bevel the edges, trimming the faces next to them by the given distance
+    Chamfer(f64),
+}
+
+/// A selection of the edges of a shape
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum EdgeSelection {
+    /// Select a single edge by its index
+    ///
+    /// Edges are ordered in the same way every time the shape is computed,
+    /// but that order is not otherwise specified. Prefer the other variants,
+    /// if they can select the desired edges.
+    Index(u64),
+
+    /// Select all straight edges that are parallel to the given direction
+    Direction([f64; 3]),
+
+    /// Select all edges whose vertices are within the given bounding box
+    BoundingBox {
+        /// The minimum coordinates of the bounding box
+        min: [f64; 3],
+
+        /// The maximum coordinates of the bounding box
+        max: [f64; 3],
+    },
+}
@@ -0,0 +1,53 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Shape;
+
+/// A boolean operation of two 3-dimensional shapes
+///
+/// Unlike a [`Group`], the shapes may touch and overlap. The result is a
+/// single solid.
+///
+/// # Limitations
+///
+/// Booleans are computed on the triangle meshes of the shapes. The result
+/// therefore no longer has exact geometry, and can't be the input of
+/// operations that need it, like [`Blend`] or [`Hollow`].
+///
+/// [`Group`]: crate::Group
+/// [`Blend`]: crate::Blend
+/// [`Hollow`]: crate::Hollow
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Boolean {
+    /// The first shape
+    pub a: Shape,
+
+    /// The second shape
+    pub b: Shape,
+
+    /// The operation that is applied to the shapes
+    pub operation: BooleanOperation,
+}
+
+impl From<Boolean> for Shape {
+    fn from(shape: Boolean) -> Self {
+        Self::Boolean(Box::new(shape))
+    }
+}
+
+/// The kind of a [`Boolean`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum BooleanOperation {
+    /// Everything that is in either shape
+    Union,
+
+    /// Everything that is in the first shape, but not in the second
+    Difference,
+
+    /// Everything that is in both shapes
+    Intersection,
+}
@@ -0,0 +1,81 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{abi::ffi_safe, Shape};
+
+/// A 3-dimensional shape that has been hollowed out
+///
+/// All faces of the shape are offset inward by the wall thickness. The open
+/// faces are removed, leaving openings in the wall.
+///
+/// # Limitations
+///
+/// Only shapes made of planar faces are supported, and no more than three
+/// faces with different orientations may meet at a vertex. Open faces must not
+/// have holes, and must not touch each other.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Hollow {
+    /// The shape that is hollowed out
+    shape: Shape,
+
+    /// The thickness of the remaining wall
+    thickness: f64,
+
+    /// The faces that are removed
+    open_faces: ffi_safe::Vec<FaceSelection>,
+}
+
+impl Hollow {
+    /// Create a `Hollow` from a shape, a wall thickness, and the open faces
+    pub fn from_shape(
+        shape: Shape,
+        thickness: f64,
+        open_faces: Vec<FaceSelection>,
+    ) -> Self {
+        Self {
+            shape,
+            thickness,
+            open_faces: open_faces.into(),
+        }
+    }
+
+    /// Access the shape that is hollowed out
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the thickness of the remaining wall
+    pub fn thickness(&self) -> f64 {
+        self.thickness
+    }
+
+    /// Access the faces that are removed
+    pub fn open_faces(&self) -> &[FaceSelection] {
+        &self.open_faces
+    }
+}
+
+impl From<Hollow> for Shape {
+    fn from(shape: Hollow) -> Self {
+        Self::Hollow(Box::new(shape))
+    }
+}
+
+/// A selection of the faces of a shape
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum FaceSelection {
+    /// Select a single face by its index
+    ///
+    /// Faces are ordered in the same way every time the shape is computed,
+    /// but that order is not otherwise specified. Prefer
+    /// [`FaceSelection::Normal`], if it can select the desired faces.
+    Index(u64),
+
+    /// Select all planar faces whose outward normal points in the given
+    /// direction
+    Normal([f64; 3]),
+}
@@ -23,14 +23,28 @@ pub mod syntax;
 #[doc(hidden)]
 pub mod abi;
 mod angle;
+mod blend;
+mod boolean;
 mod group;
+mod hollow;
 pub mod models;
+mod section;
 mod shape_2d;
 mod sweep;
+mod tessellation;
 mod transform;
 
 pub use self::{
-    angle::*, group::Group, shape_2d::*, sweep::Sweep, transform::Transform,
+    angle::*,
+    blend::{Blend, BlendKind, EdgeSelection},
+    boolean::{Boolean, BooleanOperation},
+    group::Group,
+    hollow::{FaceSelection, Hollow},
+    section::Section,
+    shape_2d::*,
+    sweep::Sweep,
+    tessellation::{Tessellated, TessellationHint},
+    transform::Transform,
 };
 pub use fj_proc::*;
 #[cfg(feature = "serde")]
@@ -53,4 +67,18 @@ pub enum Shape {
 
     /// A transformed 3-dimensional shape
     Transform(Box<Transform>),
+
+    // New variants are added at the end, so the discriminants of the existing
+    // ones, which are part of the model ABI, don't change.
+    /// A 3-dimensional shape with some of its edges rounded or chamfered
+    Blend(Box<Blend>),
+
+    /// A 3-dimensional shape that has been hollowed out
+    Hollow(Box<Hollow>),
+
+    /// A 3-dimensional shape with its own tessellation settings
+    Tessellated(Box<Tessellated>),
+
+    /// A boolean operation of two 3-dimensional shapes
+    Boolean(Box<Boolean>),
 }
@@ -0,0 +1,91 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Shape, Shape2d};
+
+/// A 2-dimensional cross-section of a 3-dimensional shape
+///
+/// The shape is cut by a plane, and the section is the 2-dimensional shape
+/// that remains on that plane. It can be used like any other 2-dimensional
+/// shape, for example to sweep it again.
+///
+/// The coordinate system of the plane is derived from its normal. If the
+/// normal points along the z-axis, the section lies in the xy-plane, offset by
+/// the origin of the plane.
+///
+/// # Limitations
+///
+/// Only shapes made of planar faces are supported, and the plane must not
+/// touch any of their vertices.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Section {
+    /// The shape that is cut
+    shape: Shape,
+
+    /// A point on the cutting plane
+    origin: [f64; 3],
+
+    /// The normal of the cutting plane
+    normal: [f64; 3],
+
+    /// The rendering color of the section in RGBA
+    color: [u8; 4],
+}
+
+impl Section {
+    /// Create a `Section` from a shape and a plane
+    ///
+    /// The plane is defined by a point on it, and its normal.
+    pub fn from_shape(
+        shape: Shape,
+        origin: [f64; 3],
+        normal: [f64; 3],
+    ) -> Self {
+        Self {
+            shape,
+            origin,
+            normal,
+            color: [255, 0, 0, 255],
+        }
+    }
+
+    /// Set the rendering color of the section in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Access the shape that is cut
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the point on the cutting plane
+    pub fn origin(&self) -> [f64; 3] {
+        self.origin
+    }
+
+    /// Access the normal of the cutting plane
+    pub fn normal(&self) -> [f64; 3] {
+        self.normal
+    }
+
+    /// Get the rendering color of the section in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.color
+    }
+}
+
+impl From<Section> for Shape {
+    fn from(shape: Section) -> Self {
+        Self::Shape2d(shape.into())
+    }
+}
+
+impl From<Section> for Shape2d {
+    fn from(shape: Section) -> Self {
+        Self::Section(Box::new(shape))
+    }
+}
@@ -3,7 +3,7 @@ use serde::{de, ser, Deserialize, Serialize};
 use std::mem;
 use std::sync::atomic;
 
-use crate::Shape;
+use crate::{Section, Shape};
 
 /// A 2-dimensional shape
 #[derive(Clone, Debug, PartialEq)]
@@ -15,6 +15,9 @@ pub enum Shape2d {
 
     /// A sketch
     Sketch(Sketch),
+
+    /// A cross-section of a 3-dimensional shape
+    Section(Box<Section>),
 }
 
 impl Shape2d {
@@ -23,6 +26,7 @@ impl Shape2d {
         match &self {
             Shape2d::Sketch(s) => s.color(),
             Shape2d::Difference(d) => d.color(),
+            Shape2d::Section(s) => s.color(),
         }
     }
 }
@@ -198,23 +202,7 @@ impl PolyChain {
 
     /// Return the points that define the polygonal chain
     pub fn to_points(&self) -> Vec<[f64; 2]> {
-        // This is sound. All invariants are automatically kept, as the raw
-        // parts come from an original `Vec` that is identical to the new one we
-        // create here, and aren't being modified anywhere.
-        let points = unsafe {
-            Vec::from_raw_parts(self.ptr, self.length, self.capacity)
-        };
-
-        // Ownership of the pointer in `self.raw_parts` transferred to `points`.
-        // We work around that, by returning a clone of `points` (hence not
-        // giving ownership to the caller).
-        let ret = points.clone();
-
-        // Now we just need to forget that `points` ever existed, and we keep
-        // ownership of the pointer.
-        mem::forget(points);
-
-        ret
+        self.points().to_vec()
     }
 }
 
@@ -262,10 +250,16 @@ impl Drop for PolyChain {
     }
 }
 
-// `PolyChain` can be `Send`, because it encapsulates the raw pointer it
-// contains, making sure memory ownership rules are observed.
+// SAFETY: `PolyChain` can be `Send`, because it encapsulates the raw pointer
+// it contains, making sure memory ownership rules are observed.
 unsafe impl Send for PolyChain {}
 
+// SAFETY: `PolyChain` can be `Sync`, because none of the methods that take
+// `&self` write to the points, or create anything that owns them. They only
+// ever borrow the points as a shared slice. The only state that is modified
+// through a shared reference is the reference counter, which is atomic.
+unsafe impl Sync for PolyChain {}
+
 #[cfg(feature = "serde")]
 impl ser::Serialize for PolyChain {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -3,6 +3,101 @@
 //! This model defines extension traits, which provide convenient syntax for
 //! the various operations defined in this trait.
 
+/// Convenient syntax to create an [`fj::Blend`]
+///
+/// [`fj::Blend`]: crate::Blend
+pub trait Blend {
+    /// Round the selected edges of `self`
+    fn fillet(&self, radius: f64, edges: crate::EdgeSelection) -> crate::Blend;
+
+    /// Chamfer the selected edges of `self`
+    fn chamfer(
+        &self,
+        distance: f64,
+        edges: crate::EdgeSelection,
+    ) -> crate::Blend;
+}
+
+impl<T> Blend for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn fillet(&self, radius: f64, edges: crate::EdgeSelection) -> crate::Blend {
+        let shape = self.clone().into();
+        crate::Blend::fillet(shape, radius, edges)
+    }
+
+    fn chamfer(
+        &self,
+        distance: f64,
+        edges: crate::EdgeSelection,
+    ) -> crate::Blend {
+        let shape = self.clone().into();
+        crate::Blend::chamfer(shape, distance, edges)
+    }
+}
+
+/// Convenient syntax to create an [`fj::Boolean`]
+///
+/// [`fj::Boolean`]: crate::Boolean
+pub trait Boolean {
+    /// Create the union of `self` and `other`
+    fn union<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>;
+
+    /// Subtract `other` from `self`
+    fn subtract<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>;
+
+    /// Create the intersection of `self` and `other`
+    fn intersect<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>;
+}
+
+impl<T> Boolean for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn union<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        boolean(self, other, crate::BooleanOperation::Union)
+    }
+
+    fn subtract<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        boolean(self, other, crate::BooleanOperation::Difference)
+    }
+
+    fn intersect<Other>(&self, other: &Other) -> crate::Boolean
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        boolean(self, other, crate::BooleanOperation::Intersection)
+    }
+}
+
+fn boolean<A, B>(
+    a: &A,
+    b: &B,
+    operation: crate::BooleanOperation,
+) -> crate::Boolean
+where
+    A: Clone + Into<crate::Shape>,
+    B: Clone + Into<crate::Shape>,
+{
+    let a = a.clone().into();
+    let b = b.clone().into();
+
+    crate::Boolean { a, b, operation }
+}
+
 /// Convenient syntax to create an [`fj::Difference2d`]
 ///
 /// [`fj::Difference2d`]: crate::Difference2d
@@ -53,6 +148,50 @@ where
     }
 }
 
+/// Convenient syntax to create an [`fj::Hollow`]
+///
+/// [`fj::Hollow`]: crate::Hollow
+pub trait Hollow {
+    /// Hollow out `self`, leaving a wall of the provided thickness
+    fn hollow(
+        &self,
+        thickness: f64,
+        open_faces: impl Into<Vec<crate::FaceSelection>>,
+    ) -> crate::Hollow;
+}
+
+impl<T> Hollow for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn hollow(
+        &self,
+        thickness: f64,
+        open_faces: impl Into<Vec<crate::FaceSelection>>,
+    ) -> crate::Hollow {
+        let shape = self.clone().into();
+        crate::Hollow::from_shape(shape, thickness, open_faces.into())
+    }
+}
+
+/// Convenient syntax to create an [`fj::Section`]
+///
+/// [`fj::Section`]: crate::Section
+pub trait Section {
+    /// Cut `self` with the plane through `origin` with the given `normal`
+    fn section(&self, origin: [f64; 3], normal: [f64; 3]) -> crate::Section;
+}
+
+impl<T> Section for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn section(&self, origin: [f64; 3], normal: [f64; 3]) -> crate::Section {
+        let shape = self.clone().into();
+        crate::Section::from_shape(shape, origin, normal)
+    }
+}
+
 /// Convenient syntax to create an [`fj::Sketch`]
 ///
 /// [`fj::Sketch`]: crate::Sketch
@@ -91,6 +230,24 @@ where
     }
 }
 
+/// Convenient syntax to create an [`fj::Tessellated`]
+///
+/// [`fj::Tessellated`]: crate::Tessellated
+pub trait Tessellated {
+    /// Tessellate `self` according to the provided hint
+    fn tessellated(&self, hint: crate::TessellationHint) -> crate::Tessellated;
+}
+
+impl<T> Tessellated for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn tessellated(&self, hint: crate::TessellationHint) -> crate::Tessellated {
+        let shape = self.clone().into();
+        crate::Tessellated::from_shape(shape, hint)
+    }
+}
+
 /// Convenient syntax to create an [`fj::Transform`]
 ///
 /// [`fj::Transform`]: crate::Transform
@@ -0,0 +1,117 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{abi::ffi_safe, Angle, Shape};
+
+/// A 3-dimensional shape with its own tessellation settings
+///
+/// The hint overrides the global tolerance for the shape, and everything it is
+/// made of. Use it to tessellate features that need to be precise more finely
+/// than the rest of the model, or to save triangles on cosmetic features.
+///
+/// If hints are nested, the innermost one wins.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Tessellated {
+    /// The shape that the hint applies to
+    shape: Shape,
+
+    /// The tessellation hint
+    hint: TessellationHint,
+}
+
+impl Tessellated {
+    /// Create a `Tessellated` from a shape and a hint
+    pub fn from_shape(shape: Shape, hint: TessellationHint) -> Self {
+        Self { shape, hint }
+    }
+
+    /// Access the shape that the hint applies to
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Access the tessellation hint
+    pub fn hint(&self) -> &TessellationHint {
+        &self.hint
+    }
+}
+
+impl From<Tessellated> for Shape {
+    fn from(shape: Tessellated) -> Self {
+        Self::Tessellated(Box::new(shape))
+    }
+}
+
+/// Settings for the tessellation of a shape
+///
+/// Defines the tolerance, which is the maximum distance between the shape and
+/// its tessellation, as well as optional limits on top of that. Limits that
+/// haven't been set are taken from the global settings.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct TessellationHint {
+    /// The maximum distance between the shape and its tessellation
+    tolerance: f64,
+
+    /// The maximum angle between neighboring segments of curves
+    angular_deviation: ffi_safe::Option<Angle>,
+
+    /// The maximum length of the segments of approximated edges
+    max_edge_length: ffi_safe::Option<f64>,
+
+    /// The minimum number of segments that circles are approximated with
+    min_circle_segments: ffi_safe::Option<u64>,
+}
+
+impl TessellationHint {
+    /// Create a `TessellationHint` from a tolerance
+    pub fn from_tolerance(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            angular_deviation: ffi_safe::Option::None,
+            max_edge_length: ffi_safe::Option::None,
+            min_circle_segments: ffi_safe::Option::None,
+        }
+    }
+
+    /// Limit the angle between neighboring segments of curves
+    pub fn with_angular_deviation(mut self, angle: Angle) -> Self {
+        self.angular_deviation = ffi_safe::Option::Some(angle);
+        self
+    }
+
+    /// Limit the length of the segments of approximated edges
+    pub fn with_max_edge_length(mut self, length: f64) -> Self {
+        self.max_edge_length = ffi_safe::Option::Some(length);
+        self
+    }
+
+    /// Require a minimum number of segments for circles
+    pub fn with_min_circle_segments(mut self, segments: u64) -> Self {
+        self.min_circle_segments = ffi_safe::Option::Some(segments);
+        self
+    }
+
+    /// Access the tolerance
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Access the maximum angle between neighboring segments, if set
+    pub fn angular_deviation(&self) -> Option<Angle> {
+        self.angular_deviation.clone().into()
+    }
+
+    /// Access the maximum length of edge segments, if set
+    pub fn max_edge_length(&self) -> Option<f64> {
+        self.max_edge_length.clone().into()
+    }
+
+    /// Access the minimum number of segments per circle, if set
+    pub fn min_circle_segments(&self) -> Option<u64> {
+        self.min_circle_segments.clone().into()
+    }
+}